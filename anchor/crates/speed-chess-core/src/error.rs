@@ -0,0 +1,47 @@
+// src/error.rs
+use core::fmt;
+
+// Why the rules engine rejected a move. The on-chain program maps each of these onto ChessError.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RulesError {
+    OutOfBounds,
+    EmptySource,
+    NotYourPiece,
+    CannotCaptureOwnPiece,
+    IllegalPieceMovement,
+    LeavesKingInCheck,
+    InvalidPromotionPiece,
+    PromotionNotOnLastRank,
+    PromotionNotAPawn,
+    KingNotFound,
+    NoMoveToUndo,
+    DropNotAllowed,
+    PieceNotInPocket,
+    DropOnOccupiedSquare,
+    PawnDropOnBackRank,
+    CaptureRequired,
+}
+
+impl fmt::Display for RulesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            RulesError::OutOfBounds => "coordinates out of bounds",
+            RulesError::EmptySource => "source square is empty",
+            RulesError::NotYourPiece => "not your piece to move",
+            RulesError::CannotCaptureOwnPiece => "cannot capture your own piece",
+            RulesError::IllegalPieceMovement => "illegal movement for this piece type",
+            RulesError::LeavesKingInCheck => "move leaves king in check",
+            RulesError::InvalidPromotionPiece => "piece type is not allowed for promotion",
+            RulesError::PromotionNotOnLastRank => "pawn is not on the last rank for promotion",
+            RulesError::PromotionNotAPawn => "only pawns can be promoted",
+            RulesError::KingNotFound => "king not found on the board",
+            RulesError::NoMoveToUndo => "no move to undo",
+            RulesError::DropNotAllowed => "piece drops are only allowed in crazyhouse",
+            RulesError::PieceNotInPocket => "piece is not in your pocket",
+            RulesError::DropOnOccupiedSquare => "cannot drop onto an occupied square",
+            RulesError::PawnDropOnBackRank => "pawns cannot be dropped on the first or last rank",
+            RulesError::CaptureRequired => "a capture is available and must be played",
+        };
+        f.write_str(message)
+    }
+}
@@ -0,0 +1,16 @@
+// speed-chess-core: the rules of chess shared by the on-chain program, bots and analysis tools.
+// No Solana/Anchor dependency and no_std (only `alloc` is needed, for move lists).
+#![no_std]
+
+extern crate alloc;
+
+pub mod error;
+pub mod movegen;
+pub mod position;
+pub mod types;
+pub mod variant;
+
+pub use error::*;
+pub use position::*;
+pub use types::*;
+pub use variant::*;
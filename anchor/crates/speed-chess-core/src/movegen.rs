@@ -0,0 +1,373 @@
+// src/movegen.rs
+use crate::position::{castling_for, find_king, is_variant_check, Board, Position};
+use crate::types::*;
+use crate::variant::Variant;
+
+const KNIGHT_OFFSETS: [(i8, i8); 8] = [(1, 2), (2, 1), (2, -1), (1, -2), (-1, -2), (-2, -1), (-2, 1), (-1, 2)];
+const KING_OFFSETS: [(i8, i8); 8] = [(1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1)];
+const ROOK_DIRECTIONS: [(i8, i8); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+const BISHOP_DIRECTIONS: [(i8, i8); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+fn at(board: &Board, square: Square) -> Option<Piece> {
+    board[square.row as usize][square.col as usize]
+}
+
+// --- Attack Detection ---
+// Whether any piece of `attacker` attacks `target`, found by looking outwards from the target.
+pub fn is_square_attacked(board: &Board, target: Square, attacker: Color) -> bool {
+    let is_attacker = |square: Square, kinds: &[PieceKind]| {
+        matches!(at(board, square), Some(piece) if piece.color == attacker && kinds.contains(&piece.kind))
+    };
+
+    // A pawn attacks diagonally forward, so look diagonally backwards from the target
+    let pawn_row = -attacker.pawn_direction();
+    for d_col in [-1, 1] {
+        if matches!(target.offset(pawn_row, d_col), Some(square) if is_attacker(square, &[PieceKind::Pawn])) {
+            return true;
+        }
+    }
+    for (d_row, d_col) in KNIGHT_OFFSETS {
+        if matches!(target.offset(d_row, d_col), Some(square) if is_attacker(square, &[PieceKind::Knight])) {
+            return true;
+        }
+    }
+    for (d_row, d_col) in KING_OFFSETS {
+        if matches!(target.offset(d_row, d_col), Some(square) if is_attacker(square, &[PieceKind::King])) {
+            return true;
+        }
+    }
+
+    let rays = [
+        (&ROOK_DIRECTIONS, [PieceKind::Rook, PieceKind::Queen]),
+        (&BISHOP_DIRECTIONS, [PieceKind::Bishop, PieceKind::Queen]),
+    ];
+    for (directions, sliders) in rays {
+        for &(d_row, d_col) in directions {
+            let mut square = target;
+            while let Some(next) = square.offset(d_row, d_col) {
+                if at(board, next).is_some() {
+                    if is_attacker(next, &sliders) {
+                        return true;
+                    }
+                    break;
+                }
+                square = next;
+            }
+        }
+    }
+    false
+}
+
+// --- Piece-Specific Movement Validation ---
+// Whether `piece` on `from` may move to `to` by its movement rules, ignoring the safety of its own king.
+pub fn is_pseudo_legal(position: &Position, piece: Piece, from: Square, to: Square) -> bool {
+    let board = &position.board;
+    if piece.kind == PieceKind::King && castling_for(position.variant, &position.castling_rights, piece.color, from, to).is_some() {
+        return is_valid_castling_move(position, piece.color, from, to);
+    }
+    if matches!(at(board, to), Some(target) if target.color == piece.color) {
+        return false;
+    }
+    // Atomic kings cannot capture: the explosion would take them too
+    if position.variant == Variant::Atomic && piece.kind == PieceKind::King && at(board, to).is_some() {
+        return false;
+    }
+    let d_row = to.row as i8 - from.row as i8;
+    let d_col = to.col as i8 - from.col as i8;
+
+    match piece.kind {
+        PieceKind::Pawn => is_valid_pawn_move(position, piece.color, from, to),
+        PieceKind::Knight => (d_row.abs() == 2 && d_col.abs() == 1) || (d_row.abs() == 1 && d_col.abs() == 2),
+        PieceKind::Bishop => d_row.abs() == d_col.abs() && is_path_clear(board, from, to),
+        PieceKind::Rook => (d_row == 0 || d_col == 0) && is_path_clear(board, from, to),
+        PieceKind::Queen => (d_row == 0 || d_col == 0 || d_row.abs() == d_col.abs()) && is_path_clear(board, from, to),
+        PieceKind::King => {
+            d_row.abs() <= 1 && d_col.abs() <= 1 && (d_row != 0 || d_col != 0)
+        }
+    }
+}
+
+fn is_valid_pawn_move(position: &Position, color: Color, from: Square, to: Square) -> bool {
+    let board = &position.board;
+    let direction = color.pawn_direction();
+    let d_row = to.row as i8 - from.row as i8;
+    let d_col = to.col as i8 - from.col as i8;
+    let start_row = match color {
+        Color::White => 1,
+        Color::Black => 6,
+    };
+
+    if d_col == 0 {
+        if d_row == direction {
+            return at(board, to).is_none();
+        }
+        if d_row == 2 * direction && from.row == start_row {
+            let skipped = Square::new((from.row + to.row) / 2, from.col);
+            return at(board, to).is_none() && at(board, skipped).is_none();
+        }
+        return false;
+    }
+    if d_col.abs() == 1 && d_row == direction {
+        return matches!(at(board, to), Some(target) if target.color != color)
+            || position.en_passant_target == Some(to);
+    }
+    false
+}
+
+// --- Castling Logic ---
+// Chess960 rules, which reduce to the usual ones for the standard start position: every square
+// the king or rook crosses or lands on is empty apart from those two pieces, and the king is not
+// in check and does not pass through or land on an attacked square.
+fn is_valid_castling_move(position: &Position, color: Color, from: Square, to: Square) -> bool {
+    let Some(castling) = castling_for(position.variant, &position.castling_rights, color, from, to) else { return false };
+    let home_row = color.home_row();
+    if position.piece_at(castling.rook_from) != Some(Piece::new(PieceKind::Rook, color)) {
+        return false;
+    }
+
+    let cols = [from.col, castling.king_to.col, castling.rook_from.col, castling.rook_to.col];
+    let (min_col, max_col) = (*cols.iter().min().unwrap(), *cols.iter().max().unwrap());
+    let blocked = (min_col..=max_col)
+        .map(|col| Square::new(home_row, col))
+        .any(|square| square != from && square != castling.rook_from && position.piece_at(square).is_some());
+    if blocked || is_variant_check(position.variant, &position.board, color) {
+        return false;
+    }
+
+    let mut board = position.board;
+    board[home_row as usize][from.col as usize] = None;
+    board[home_row as usize][castling.rook_from.col as usize] = None;
+    let (path_start, path_end) = (from.col.min(castling.king_to.col), from.col.max(castling.king_to.col));
+    !(path_start..=path_end).any(|col| is_square_attacked(&board, Square::new(home_row, col), color.opponent()))
+}
+
+// --- Path Clearing Helper ---
+// Squares strictly between `from` and `to` on a shared rank, file or diagonal are empty.
+fn is_path_clear(board: &Board, from: Square, to: Square) -> bool {
+    let d_row = (to.row as i8 - from.row as i8).signum();
+    let d_col = (to.col as i8 - from.col as i8).signum();
+    let mut square = from;
+    while let Some(next) = square.offset(d_row, d_col) {
+        if next == to {
+            return true;
+        }
+        if at(board, next).is_some() {
+            return false;
+        }
+        square = next;
+    }
+    false
+}
+
+// --- Atomic Explosions ---
+// Removes the non-pawn pieces around `center`, returning them in KING_OFFSETS order.
+pub fn explode_around(board: &mut Board, center: Square) -> [Option<Piece>; 8] {
+    KING_OFFSETS.map(|(d_row, d_col)| {
+        let square = center.offset(d_row, d_col)?;
+        let cell = &mut board[square.row as usize][square.col as usize];
+        match *cell {
+            Some(piece) if piece.kind != PieceKind::Pawn => cell.take(),
+            _ => None,
+        }
+    })
+}
+
+// The squares and pieces of an explosion returned by explode_around.
+pub fn explosion_squares(center: Square, exploded: &[Option<Piece>; 8]) -> impl Iterator<Item = (Square, Piece)> + '_ {
+    KING_OFFSETS
+        .into_iter()
+        .zip(exploded.iter())
+        .filter_map(move |((d_row, d_col), piece)| Some((center.offset(d_row, d_col)?, (*piece)?)))
+}
+
+pub fn restore_explosion(board: &mut Board, center: Square, exploded: &[Option<Piece>; 8]) {
+    for (square, piece) in explosion_squares(center, exploded) {
+        board[square.row as usize][square.col as usize] = Some(piece);
+    }
+}
+
+// --- King Safety ---
+// Plays a pseudo-legal move on a scratch board and reports whether the mover's king is safe afterwards.
+// Antichess has no royal king. In Atomic the move is also legal if it explodes the enemy king,
+// provided the mover's own king survives.
+pub fn leaves_king_safe(position: &Position, from: Square, to: Square) -> bool {
+    if position.variant == Variant::Antichess {
+        return true;
+    }
+    let mut board = position.board;
+    let Some(piece) = board[from.row as usize][from.col as usize].take() else { return false };
+
+    if piece.kind == PieceKind::King {
+        if let Some(castling) = castling_for(position.variant, &position.castling_rights, piece.color, from, to) {
+            let rook = board[castling.rook_from.row as usize][castling.rook_from.col as usize].take();
+            board[castling.king_to.row as usize][castling.king_to.col as usize] = Some(piece);
+            board[castling.rook_to.row as usize][castling.rook_to.col as usize] = rook;
+            return !is_variant_check(position.variant, &board, piece.color);
+        }
+    }
+    let is_capture = is_capture(position, Move::new(from, to));
+    if piece.kind == PieceKind::Pawn && from.col != to.col && position.en_passant_target == Some(to) {
+        board[from.row as usize][to.col as usize] = None;
+    }
+    board[to.row as usize][to.col as usize] = Some(piece);
+
+    if position.variant == Variant::Atomic && is_capture {
+        board[to.row as usize][to.col as usize] = None;
+        explode_around(&mut board, to);
+        if find_king(&board, piece.color).is_none() {
+            return false;
+        }
+        if find_king(&board, piece.color.opponent()).is_none() {
+            return true;
+        }
+    }
+    !is_variant_check(position.variant, &board, piece.color)
+}
+
+// Whether `mv` takes an enemy piece (en passant included). Drops and castling never capture.
+pub fn is_capture(position: &Position, mv: Move) -> bool {
+    if mv.dropped_piece().is_some() {
+        return false;
+    }
+    let Some(piece) = at(&position.board, mv.from) else { return false };
+    match at(&position.board, mv.to) {
+        Some(target) => target.color != piece.color,
+        None => piece.kind == PieceKind::Pawn && mv.from.col != mv.to.col && position.en_passant_target == Some(mv.to),
+    }
+}
+
+// Whether the side to move has any legal capture (Antichess makes capturing compulsory).
+pub fn has_capture(position: &Position) -> bool {
+    visit_moves(position, &mut |mv| is_capture(position, mv))
+}
+
+pub fn drop_leaves_king_safe(position: &Position, kind: PieceKind, to: Square) -> bool {
+    let mut board = position.board;
+    board[to.row as usize][to.col as usize] = Some(Piece::new(kind, position.side_to_move));
+    !is_variant_check(position.variant, &board, position.side_to_move)
+}
+
+// --- Move Generation ---
+// Calls `visit` with each legal move for the side to move until it returns true.
+// Returns whether the walk was stopped early.
+pub fn visit_legal_moves(position: &Position, mut visit: impl FnMut(Move) -> bool) -> bool {
+    if position.variant == Variant::Antichess && has_capture(position) {
+        return visit_moves(position, &mut |mv| is_capture(position, mv) && visit(mv));
+    }
+    visit_moves(position, &mut visit)
+}
+
+// Every move that is legal apart from Antichess compulsory capture.
+fn visit_moves(position: &Position, visit: &mut dyn FnMut(Move) -> bool) -> bool {
+    let color = position.side_to_move;
+    for row in 0..8u8 {
+        for col in 0..8u8 {
+            let from = Square::new(row, col);
+            let Some(piece) = at(&position.board, from) else { continue };
+            if piece.color != color {
+                continue;
+            }
+            let stopped = visit_targets(position, piece, from, &mut |to| {
+                if !leaves_king_safe(position, from, to) {
+                    return false;
+                }
+                if piece.kind == PieceKind::Pawn && to.row == color.opponent().home_row() {
+                    position
+                        .variant
+                        .promotion_pieces()
+                        .iter()
+                        .any(|&kind| visit(Move::with_promotion(from, to, kind)))
+                } else {
+                    visit(Move::new(from, to))
+                }
+            });
+            if stopped {
+                return true;
+            }
+        }
+    }
+    position.variant == Variant::Crazyhouse && visit_legal_drops(position, visit)
+}
+
+// Crazyhouse drops of each pocket piece onto each empty square, until `visit` returns true.
+fn visit_legal_drops(position: &Position, visit: &mut dyn FnMut(Move) -> bool) -> bool {
+    let pocket = position.pockets[position.side_to_move.index()];
+    for kind in PieceKind::DROPPABLE {
+        if pocket.count(kind) == 0 {
+            continue;
+        }
+        let rows = if kind == PieceKind::Pawn { 1..7u8 } else { 0..8u8 };
+        for row in rows {
+            for col in 0..8u8 {
+                let to = Square::new(row, col);
+                if at(&position.board, to).is_none() && drop_leaves_king_safe(position, kind, to) && visit(Move::drop(kind, to)) {
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
+
+// Calls `visit` with each pseudo-legal target square of `piece` until it returns true.
+fn visit_targets(position: &Position, piece: Piece, from: Square, visit: &mut dyn FnMut(Square) -> bool) -> bool {
+    let board = &position.board;
+    let try_square = |to: Square| is_pseudo_legal(position, piece, from, to) && visit(to);
+
+    match piece.kind {
+        PieceKind::Pawn => {
+            let direction = piece.color.pawn_direction();
+            [(direction, 0), (2 * direction, 0), (direction, -1), (direction, 1)]
+                .into_iter()
+                .filter_map(|(d_row, d_col)| from.offset(d_row, d_col))
+                .any(try_square)
+        }
+        PieceKind::Knight => KNIGHT_OFFSETS
+            .into_iter()
+            .filter_map(|(d_row, d_col)| from.offset(d_row, d_col))
+            .any(try_square),
+        PieceKind::King => {
+            let castling_targets = [CastleSide::Kingside, CastleSide::Queenside].map(|side| {
+                let rook_col = position.castling_rights.rook_col(piece.color, side)?;
+                match position.variant {
+                    Variant::Chess960 => Some(Square::new(from.row, rook_col)),
+                    _ => Some(Square::new(from.row, side.king_to_col())),
+                }
+            });
+            // Squares holding our own pieces are only reachable by castling onto a rook, listed once below
+            KING_OFFSETS
+                .into_iter()
+                .filter_map(|(d_row, d_col)| from.offset(d_row, d_col))
+                .filter(|&to| !matches!(at(board, to), Some(target) if target.color == piece.color))
+                .chain(castling_targets.into_iter().flatten())
+                .any(try_square)
+        }
+        PieceKind::Bishop | PieceKind::Rook | PieceKind::Queen => {
+            let directions: &[(i8, i8)] = match piece.kind {
+                PieceKind::Bishop => &BISHOP_DIRECTIONS,
+                PieceKind::Rook => &ROOK_DIRECTIONS,
+                _ => &[(1, 0), (0, 1), (-1, 0), (0, -1), (1, 1), (1, -1), (-1, 1), (-1, -1)],
+            };
+            for &(d_row, d_col) in directions {
+                let mut square = from;
+                while let Some(next) = square.offset(d_row, d_col) {
+                    match at(board, next) {
+                        None => {
+                            if visit(next) {
+                                return true;
+                            }
+                        }
+                        Some(target) => {
+                            if target.color != piece.color && visit(next) {
+                                return true;
+                            }
+                            break;
+                        }
+                    }
+                    square = next;
+                }
+            }
+            false
+        }
+    }
+}
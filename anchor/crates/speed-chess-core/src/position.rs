@@ -0,0 +1,562 @@
+// src/position.rs
+use alloc::vec::Vec;
+
+use crate::error::RulesError;
+use crate::movegen;
+use crate::types::*;
+use crate::variant::{chess960_back_rank, Variant, CHECKS_TO_WIN, HILL_SQUARES};
+
+pub type Board = [[Option<Piece>; 8]; 8]; // board[row][col]
+
+const BACK_RANK: [PieceKind; 8] = [
+    PieceKind::Rook, PieceKind::Knight, PieceKind::Bishop, PieceKind::Queen,
+    PieceKind::King, PieceKind::Bishop, PieceKind::Knight, PieceKind::Rook,
+];
+
+// Everything make_move overwrites that cannot be recomputed from the new position.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Undo {
+    pub from: Square,
+    pub to: Square,
+    pub moved_piece: Piece,             // As it stood on the source square (still a pawn for promotions)
+    pub captured_piece: Option<Piece>,
+    pub captured_was_promoted: bool,    // Crazyhouse: the captured piece went to the pocket as a pawn
+    pub exploded: [Option<Piece>; 8],   // Atomic: pieces blown off the squares around the capture
+    pub was_en_passant: bool,           // Captured pawn stood beside the source square, not on the target
+    pub previous_castling_rights: CastlingRights,
+    pub previous_en_passant_target: Option<Square>,
+    pub previous_halfmove_clock: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Position {
+    pub variant: Variant,
+    pub board: Board,
+    pub side_to_move: Color,
+    pub castling_rights: CastlingRights,
+    pub en_passant_target: Option<Square>, // Square a pawn skipped over on the previous ply
+    pub halfmove_clock: u8,
+    pub fullmove_number: u16,
+    pub checks_given: [u8; 2], // Three-Check: checks delivered by White and Black
+    pub pockets: [Pocket; 2], // Crazyhouse: pieces in hand for White and Black
+    pub promoted: u64, // Crazyhouse: bit row * 8 + col is set for pieces that were promoted pawns
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Self::starting()
+    }
+}
+
+impl Position {
+    // The standard starting position, White to move.
+    pub fn starting() -> Self {
+        Self {
+            variant: Variant::Standard,
+            board: starting_board(),
+            side_to_move: Color::White,
+            castling_rights: CastlingRights::default(),
+            en_passant_target: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            checks_given: [0; 2],
+            pockets: [Pocket::default(); 2],
+            promoted: 0,
+        }
+    }
+
+    // Standard start position under `variant` rules. Antichess starts without castling rights.
+    // Chess960 needs a start position index, see chess960.
+    pub fn with_variant(variant: Variant) -> Self {
+        let castling_rights = match variant {
+            Variant::Antichess => CastlingRights::NONE,
+            _ => CastlingRights::default(),
+        };
+        Self { variant, castling_rights, ..Self::starting() }
+    }
+
+    // Chess960 start position `index` (0..960, Scharnagl numbering), White to move.
+    pub fn chess960(index: u16) -> Option<Self> {
+        let back_rank = chess960_back_rank(index)?;
+        let mut rook_cols = (0..8u8).filter(|&col| back_rank[col as usize] == PieceKind::Rook);
+        let (queenside_col, kingside_col) = (rook_cols.next()?, rook_cols.next()?);
+        Some(Self {
+            variant: Variant::Chess960,
+            board: board_with_back_rank(&back_rank),
+            castling_rights: CastlingRights::from_rook_files(kingside_col, queenside_col),
+            ..Self::starting()
+        })
+    }
+
+    pub fn piece_at(&self, square: Square) -> Option<Piece> {
+        self.board[square.row as usize][square.col as usize]
+    }
+
+    pub fn king_square(&self, color: Color) -> Option<Square> {
+        find_king(&self.board, color)
+    }
+
+    // A side without a king is treated as being in check (see is_variant_check for Atomic and Antichess).
+    pub fn is_in_check(&self, color: Color) -> bool {
+        is_variant_check(self.variant, &self.board, color)
+    }
+
+    // Checks `mv` for the side to move without changing the position.
+    // Errors are reported in a fixed order: squares, ownership, piece movement, king safety, promotion.
+    pub fn validate_move(&self, mv: Move) -> Result<(), RulesError> {
+        let (from, to) = (mv.from, mv.to);
+        if !from.is_on_board() || !to.is_on_board() {
+            return Err(RulesError::OutOfBounds);
+        }
+        if let Some(kind) = mv.dropped_piece() {
+            return self.validate_drop(kind, to);
+        }
+        if from == to {
+            return Err(RulesError::IllegalPieceMovement);
+        }
+        let piece = self.piece_at(from).ok_or(RulesError::EmptySource)?;
+        if piece.color != self.side_to_move {
+            return Err(RulesError::NotYourPiece);
+        }
+        // In Chess960 a castling king "captures" its own rook
+        let castles_onto_rook = piece.kind == PieceKind::King
+            && self.variant == Variant::Chess960
+            && castling_for(self.variant, &self.castling_rights, piece.color, from, to).is_some();
+        if matches!(self.piece_at(to), Some(target) if target.color == piece.color) && !castles_onto_rook {
+            return Err(RulesError::CannotCaptureOwnPiece);
+        }
+        if !movegen::is_pseudo_legal(self, piece, from, to) {
+            return Err(RulesError::IllegalPieceMovement);
+        }
+        if !movegen::leaves_king_safe(self, from, to) {
+            return Err(RulesError::LeavesKingInCheck);
+        }
+        if self.variant == Variant::Antichess && !movegen::is_capture(self, mv) && movegen::has_capture(self) {
+            return Err(RulesError::CaptureRequired);
+        }
+
+        if piece.kind == PieceKind::Pawn {
+            if to.row == piece.color.opponent().home_row() {
+                if let Some(kind) = mv.promotion {
+                    if !self.variant.promotion_pieces().contains(&kind) {
+                        return Err(RulesError::InvalidPromotionPiece);
+                    }
+                }
+            } else if mv.promotion.is_some() {
+                return Err(RulesError::PromotionNotOnLastRank);
+            }
+        } else if mv.promotion.is_some() {
+            return Err(RulesError::PromotionNotAPawn);
+        }
+        Ok(())
+    }
+
+    // Crazyhouse drop rules: any empty square (pawns not on the first or last rank), and the
+    // drop must leave the dropping side out of check, so drops can block checks.
+    fn validate_drop(&self, kind: PieceKind, to: Square) -> Result<(), RulesError> {
+        if self.variant != Variant::Crazyhouse {
+            return Err(RulesError::DropNotAllowed);
+        }
+        if self.pockets[self.side_to_move.index()].count(kind) == 0 {
+            return Err(RulesError::PieceNotInPocket);
+        }
+        if self.piece_at(to).is_some() {
+            return Err(RulesError::DropOnOccupiedSquare);
+        }
+        if kind == PieceKind::Pawn && (to.row == 0 || to.row == 7) {
+            return Err(RulesError::PawnDropOnBackRank);
+        }
+        if !movegen::drop_leaves_king_safe(self, kind, to) {
+            return Err(RulesError::LeavesKingInCheck);
+        }
+        Ok(())
+    }
+
+    // The move encoding castling on `side` for the side to move, if that right remains.
+    // Legality is not checked.
+    pub fn castling_move(&self, side: CastleSide) -> Option<Move> {
+        let color = self.side_to_move;
+        let king = self.king_square(color)?;
+        let rook_col = self.castling_rights.rook_col(color, side)?;
+        let to = match self.variant {
+            Variant::Chess960 => Square::new(king.row, rook_col),
+            _ => Square::new(king.row, side.king_to_col()),
+        };
+        Some(Move::new(king, to))
+    }
+
+    // Which side `mv` castles on, if it is a castling move by the side to move.
+    pub fn castling_side(&self, mv: Move) -> Option<CastleSide> {
+        match self.piece_at(mv.from) {
+            Some(piece) if piece.kind == PieceKind::King && piece.color == self.side_to_move => {
+                castling_for(self.variant, &self.castling_rights, piece.color, mv.from, mv.to).map(|castling| castling.side)
+            }
+            _ => None,
+        }
+    }
+
+    // Whether the side to move may play `mv`.
+    pub fn is_legal(&self, mv: Move) -> bool {
+        self.validate_move(mv).is_ok()
+    }
+
+    // Validates and plays `mv`, returning what is needed to take it back with unmake_move.
+    pub fn make_move(&mut self, mv: Move) -> Result<Undo, RulesError> {
+        self.validate_move(mv)?;
+        Ok(self.apply_unchecked(mv))
+    }
+
+    // Plays a move known to be legal (e.g. one produced by legal_moves).
+    pub fn apply_unchecked(&mut self, mv: Move) -> Undo {
+        if let Some(kind) = mv.dropped_piece() {
+            return self.apply_drop(kind, mv.to);
+        }
+        let (from, to) = (mv.from, mv.to);
+        let mover = self.side_to_move;
+        let moved_piece = self.board[from.row as usize][from.col as usize]
+            .take()
+            .expect("apply_unchecked called without a piece on the source square");
+
+        let previous_castling_rights = self.castling_rights;
+        let previous_en_passant_target = self.en_passant_target.take();
+        let previous_halfmove_clock = self.halfmove_clock;
+
+        let castling = match moved_piece.kind {
+            PieceKind::King => castling_for(self.variant, &previous_castling_rights, mover, from, to),
+            _ => None,
+        };
+        let was_en_passant = moved_piece.kind == PieceKind::Pawn
+            && from.col != to.col
+            && previous_en_passant_target == Some(to);
+        let is_promotion = moved_piece.kind == PieceKind::Pawn && to.row == mover.opponent().home_row();
+        let captured_was_promoted = castling.is_none() && !was_en_passant && self.is_promoted(to);
+
+        let captured_piece = if let Some(castling) = castling {
+            // King and rook may land on each other's start squares, so lift the rook before placing either
+            let rook = self.board[castling.rook_from.row as usize][castling.rook_from.col as usize].take();
+            self.board[castling.king_to.row as usize][castling.king_to.col as usize] = Some(moved_piece);
+            self.board[castling.rook_to.row as usize][castling.rook_to.col as usize] = rook;
+            None
+        } else {
+            let captured_piece = if was_en_passant {
+                self.board[from.row as usize][to.col as usize].take()
+            } else {
+                self.board[to.row as usize][to.col as usize].take()
+            };
+            let mut placed_piece = moved_piece;
+            if is_promotion {
+                placed_piece.kind = mv.promotion.unwrap_or(PieceKind::Queen);
+            }
+            self.board[to.row as usize][to.col as usize] = Some(placed_piece);
+            captured_piece
+        };
+
+        let mut exploded = [None; 8];
+        if self.variant == Variant::Atomic && captured_piece.is_some() {
+            // The capturer goes up with its target, along with every neighbouring non-pawn
+            self.board[to.row as usize][to.col as usize] = None;
+            exploded = movegen::explode_around(&mut self.board, to);
+            for (square, piece) in movegen::explosion_squares(to, &exploded) {
+                if piece.kind == PieceKind::King {
+                    self.castling_rights.clear(piece.color);
+                }
+                self.castling_rights.clear_for_rook_square(square);
+            }
+        }
+
+        if self.variant == Variant::Crazyhouse && castling.is_none() {
+            // Captures go to the capturer's pocket, promoted pieces reverting to pawns
+            if let Some(captured) = captured_piece {
+                let kind = if captured_was_promoted { PieceKind::Pawn } else { captured.kind };
+                self.pockets[mover.index()].add(kind);
+            }
+            let moved_was_promoted = self.is_promoted(from);
+            self.set_promoted(from, false);
+            self.set_promoted(to, moved_was_promoted || is_promotion);
+        }
+
+        if moved_piece.kind == PieceKind::King {
+            self.castling_rights.clear(mover);
+        }
+        // A castling rook leaving or being captured on its home square ends castling on that side
+        self.castling_rights.clear_for_rook_square(from);
+        self.castling_rights.clear_for_rook_square(to);
+
+        if moved_piece.kind == PieceKind::Pawn && from.row.abs_diff(to.row) == 2 {
+            self.en_passant_target = Some(Square::new((from.row + to.row) / 2, from.col));
+        }
+
+        self.finish_ply(mover, moved_piece.kind == PieceKind::Pawn || captured_piece.is_some());
+
+        Undo {
+            from,
+            to,
+            moved_piece,
+            captured_piece,
+            captured_was_promoted,
+            exploded,
+            was_en_passant,
+            previous_castling_rights,
+            previous_en_passant_target,
+            previous_halfmove_clock,
+        }
+    }
+
+    fn apply_drop(&mut self, kind: PieceKind, to: Square) -> Undo {
+        let mover = self.side_to_move;
+        let piece = Piece::new(kind, mover);
+        let taken = self.pockets[mover.index()].remove(kind);
+        debug_assert!(taken, "apply_unchecked called with a drop of a piece not in the pocket");
+        self.board[to.row as usize][to.col as usize] = Some(piece);
+
+        let undo = Undo {
+            from: to,
+            to,
+            moved_piece: piece,
+            captured_piece: None,
+            captured_was_promoted: false,
+            exploded: [None; 8],
+            was_en_passant: false,
+            previous_castling_rights: self.castling_rights,
+            previous_en_passant_target: self.en_passant_target.take(),
+            previous_halfmove_clock: self.halfmove_clock,
+        };
+        self.finish_ply(mover, kind == PieceKind::Pawn);
+        undo
+    }
+
+    // Counters and side to move after `mover` has played; `resets_clock` for pawn moves and captures.
+    fn finish_ply(&mut self, mover: Color, resets_clock: bool) {
+        if resets_clock {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock = self.halfmove_clock.saturating_add(1);
+        }
+        if mover == Color::Black {
+            self.fullmove_number = self.fullmove_number.saturating_add(1);
+        }
+        self.side_to_move = mover.opponent();
+        if self.variant == Variant::ThreeCheck && self.is_in_check(self.side_to_move) {
+            self.checks_given[mover.index()] = self.checks_given[mover.index()].saturating_add(1);
+        }
+    }
+
+    // Takes back the move `undo` was produced for. Undos must be applied most recent first.
+    pub fn unmake_move(&mut self, undo: &Undo) {
+        let (from, to) = (undo.from, undo.to);
+        let color = undo.moved_piece.color;
+        // The check counter is not in the undo record: the move gave check iff the opponent is in check now
+        if self.variant == Variant::ThreeCheck && self.is_in_check(self.side_to_move) {
+            self.checks_given[color.index()] = self.checks_given[color.index()].saturating_sub(1);
+        }
+
+        if from == to {
+            // A drop: the piece goes back into the pocket
+            self.board[to.row as usize][to.col as usize] = None;
+            self.pockets[color.index()].add(undo.moved_piece.kind);
+            self.restore_counters(undo);
+            return;
+        }
+
+        if undo.moved_piece.kind == PieceKind::King {
+            if let Some(castling) = castling_for(self.variant, &undo.previous_castling_rights, color, from, to) {
+                let rook = self.board[castling.rook_to.row as usize][castling.rook_to.col as usize].take();
+                self.board[castling.king_to.row as usize][castling.king_to.col as usize] = None;
+                self.board[from.row as usize][from.col as usize] = Some(undo.moved_piece);
+                self.board[castling.rook_from.row as usize][castling.rook_from.col as usize] = rook;
+                self.restore_counters(undo);
+                return;
+            }
+        }
+
+        if self.variant == Variant::Crazyhouse {
+            if let Some(captured) = undo.captured_piece {
+                let kind = if undo.captured_was_promoted { PieceKind::Pawn } else { captured.kind };
+                self.pockets[color.index()].remove(kind);
+            }
+            let was_promotion = undo.moved_piece.kind == PieceKind::Pawn && to.row == color.opponent().home_row();
+            let moved_was_promoted = !was_promotion && self.is_promoted(to);
+            self.set_promoted(to, undo.captured_was_promoted);
+            self.set_promoted(from, moved_was_promoted);
+        }
+
+        movegen::restore_explosion(&mut self.board, to, &undo.exploded);
+
+        // Put the mover back (this also undoes a promotion) and restore whatever it captured
+        self.board[to.row as usize][to.col as usize] = None;
+        self.board[from.row as usize][from.col as usize] = Some(undo.moved_piece);
+        if undo.was_en_passant {
+            self.board[from.row as usize][to.col as usize] = undo.captured_piece;
+        } else {
+            self.board[to.row as usize][to.col as usize] = undo.captured_piece;
+        }
+
+        self.restore_counters(undo);
+    }
+
+    fn restore_counters(&mut self, undo: &Undo) {
+        self.castling_rights = undo.previous_castling_rights;
+        self.en_passant_target = undo.previous_en_passant_target;
+        self.halfmove_clock = undo.previous_halfmove_clock;
+        if undo.moved_piece.color == Color::Black {
+            self.fullmove_number = self.fullmove_number.saturating_sub(1);
+        }
+        self.side_to_move = undo.moved_piece.color;
+    }
+
+    // Whether the piece on `square` started the game as a pawn (Crazyhouse).
+    pub fn is_promoted(&self, square: Square) -> bool {
+        self.promoted & square_bit(square) != 0
+    }
+
+    fn set_promoted(&mut self, square: Square, promoted: bool) {
+        if promoted {
+            self.promoted |= square_bit(square);
+        } else {
+            self.promoted &= !square_bit(square);
+        }
+    }
+
+    // All legal moves for the side to move. Promotions are listed once per promotion piece.
+    pub fn legal_moves(&self) -> Vec<Move> {
+        let mut moves = Vec::new();
+        movegen::visit_legal_moves(self, |mv| {
+            moves.push(mv);
+            false
+        });
+        moves
+    }
+
+    pub fn has_legal_move(&self) -> bool {
+        movegen::visit_legal_moves(self, |_| true)
+    }
+
+    // Whether the game is over for the side to move. Variant wins are checked first, and mate
+    // and stalemate take precedence over the fifty-move rule.
+    pub fn game_result(&self) -> GameResult {
+        if let Some(result) = self.variant_result() {
+            result
+        } else if !self.has_legal_move() {
+            if self.is_in_check(self.side_to_move) {
+                GameResult::Checkmate { winner: self.side_to_move.opponent() }
+            } else {
+                GameResult::Stalemate
+            }
+        } else if self.halfmove_clock >= 100 {
+            GameResult::FiftyMoveRule
+        } else {
+            GameResult::Ongoing
+        }
+    }
+
+    // Win conditions that only exist in some variants.
+    fn variant_result(&self) -> Option<GameResult> {
+        let mut colors = [Color::White, Color::Black].into_iter();
+        match self.variant {
+            Variant::KingOfTheHill => colors
+                .find(|&color| self.king_square(color).is_some_and(|king| HILL_SQUARES.contains(&king)))
+                .map(|winner| GameResult::KingOfTheHill { winner }),
+            Variant::ThreeCheck => colors
+                .find(|&color| self.checks_given[color.index()] >= CHECKS_TO_WIN)
+                .map(|winner| GameResult::ThreeChecks { winner }),
+            Variant::Atomic => colors
+                .find(|&color| self.king_square(color).is_none())
+                .map(|exploded| GameResult::KingExploded { winner: exploded.opponent() }),
+            Variant::Antichess => {
+                let color = self.side_to_move;
+                let has_pieces = self.board.iter().flatten().any(|square| square.is_some_and(|piece| piece.color == color));
+                if !has_pieces {
+                    Some(GameResult::AllPiecesLost { winner: color })
+                } else if !self.has_legal_move() {
+                    Some(GameResult::StalemateWin { winner: color })
+                } else {
+                    None
+                }
+            }
+            Variant::Standard | Variant::Chess960 | Variant::Crazyhouse => None,
+        }
+    }
+}
+
+fn square_bit(square: Square) -> u64 {
+    1 << (square.row * 8 + square.col)
+}
+
+pub fn starting_board() -> Board {
+    board_with_back_rank(&BACK_RANK)
+}
+
+// Pawns on the second ranks and `back_rank` mirrored for both sides
+pub fn board_with_back_rank(back_rank: &[PieceKind; 8]) -> Board {
+    let mut board = [[None; 8]; 8];
+    for (col, &kind) in back_rank.iter().enumerate() {
+        board[0][col] = Some(Piece::new(kind, Color::White));
+        board[1][col] = Some(Piece::new(PieceKind::Pawn, Color::White));
+        board[6][col] = Some(Piece::new(PieceKind::Pawn, Color::Black));
+        board[7][col] = Some(Piece::new(kind, Color::Black));
+    }
+    board
+}
+
+pub fn find_king(board: &Board, color: Color) -> Option<Square> {
+    (0..8u8)
+        .flat_map(|row| (0..8u8).map(move |col| Square::new(row, col)))
+        .find(|square| board[square.row as usize][square.col as usize] == Some(Piece::new(PieceKind::King, color)))
+}
+
+// A side without a king is treated as being in check.
+pub fn is_king_in_check(board: &Board, color: Color) -> bool {
+    match find_king(board, color) {
+        Some(king) => movegen::is_square_attacked(board, king, color.opponent()),
+        None => true,
+    }
+}
+
+// Check as `variant` defines it. Antichess kings are never in check, and in Atomic a king
+// touching the enemy king cannot be checked, since capturing it would explode both.
+pub fn is_variant_check(variant: Variant, board: &Board, color: Color) -> bool {
+    match variant {
+        Variant::Antichess => false,
+        Variant::Atomic => match (find_king(board, color), find_king(board, color.opponent())) {
+            (Some(king), Some(enemy)) if king.row.abs_diff(enemy.row) <= 1 && king.col.abs_diff(enemy.col) <= 1 => false,
+            _ => is_king_in_check(board, color),
+        },
+        _ => is_king_in_check(board, color),
+    }
+}
+
+// Where the king and rook of a castling move start and end.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Castling {
+    pub side: CastleSide,
+    pub rook_from: Square,
+    pub king_to: Square,
+    pub rook_to: Square,
+}
+
+// Interprets a king move from -> to as castling under `rights`, if it encodes one.
+// Chess960 castles by moving the king onto its own rook; other variants move the king two files.
+pub(crate) fn castling_for(variant: Variant, rights: &CastlingRights, color: Color, from: Square, to: Square) -> Option<Castling> {
+    let home_row = color.home_row();
+    if from.row != home_row || to.row != home_row {
+        return None;
+    }
+    let side = if variant == Variant::Chess960 {
+        [CastleSide::Kingside, CastleSide::Queenside]
+            .into_iter()
+            .find(|&side| rights.rook_col(color, side) == Some(to.col))?
+    } else {
+        let side = if to.col > from.col { CastleSide::Kingside } else { CastleSide::Queenside };
+        if from.col.abs_diff(to.col) != 2 || to.col != side.king_to_col() {
+            return None;
+        }
+        side
+    };
+    let rook_col = rights.rook_col(color, side)?;
+    Some(Castling {
+        side,
+        rook_from: Square::new(home_row, rook_col),
+        king_to: Square::new(home_row, side.king_to_col()),
+        rook_to: Square::new(home_row, side.rook_to_col()),
+    })
+}
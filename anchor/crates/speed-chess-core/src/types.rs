@@ -0,0 +1,280 @@
+// src/types.rs
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Color {
+    #[default]
+    White,
+    Black,
+}
+
+impl Color {
+    pub fn opponent(self) -> Self {
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        }
+    }
+
+    // Index of this side in per-color arrays (White first)
+    pub fn index(self) -> usize {
+        match self {
+            Color::White => 0,
+            Color::Black => 1,
+        }
+    }
+
+    // Row of this side's back rank
+    pub fn home_row(self) -> u8 {
+        match self {
+            Color::White => 0,
+            Color::Black => 7,
+        }
+    }
+
+    // Row direction pawns of this side advance in
+    pub fn pawn_direction(self) -> i8 {
+        match self {
+            Color::White => 1,
+            Color::Black => -1,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PieceKind {
+    Pawn,
+    Knight,
+    Bishop,
+    Rook,
+    Queen,
+    King,
+}
+
+impl PieceKind {
+    pub const PROMOTIONS: [PieceKind; 4] = [PieceKind::Queen, PieceKind::Rook, PieceKind::Bishop, PieceKind::Knight];
+    pub const DROPPABLE: [PieceKind; 5] = [PieceKind::Pawn, PieceKind::Knight, PieceKind::Bishop, PieceKind::Rook, PieceKind::Queen];
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Piece {
+    pub kind: PieceKind,
+    pub color: Color,
+}
+
+impl Piece {
+    pub const fn new(kind: PieceKind, color: Color) -> Self {
+        Self { kind, color }
+    }
+}
+
+// A square as (row, col): row 0 is rank 1, col 0 is the a-file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Square {
+    pub row: u8,
+    pub col: u8,
+}
+
+impl Square {
+    pub const fn new(row: u8, col: u8) -> Self {
+        Self { row, col }
+    }
+
+    pub fn is_on_board(self) -> bool {
+        self.row < 8 && self.col < 8
+    }
+
+    // The square `(d_row, d_col)` away, if it is still on the board
+    pub fn offset(self, d_row: i8, d_col: i8) -> Option<Square> {
+        let row = self.row as i8 + d_row;
+        let col = self.col as i8 + d_col;
+        if (0..8).contains(&row) && (0..8).contains(&col) {
+            Some(Square::new(row as u8, col as u8))
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Move {
+    pub from: Square,
+    pub to: Square,
+    pub promotion: Option<PieceKind>, // None on the last rank means a queen
+}
+
+impl Move {
+    pub const fn new(from: Square, to: Square) -> Self {
+        Self { from, to, promotion: None }
+    }
+
+    pub const fn with_promotion(from: Square, to: Square, promotion: PieceKind) -> Self {
+        Self { from, to, promotion: Some(promotion) }
+    }
+
+    // Crazyhouse drop of a pocket piece: encoded as from == to with the dropped piece in the promotion slot.
+    pub const fn drop(kind: PieceKind, to: Square) -> Self {
+        Self { from: to, to, promotion: Some(kind) }
+    }
+
+    pub fn dropped_piece(self) -> Option<PieceKind> {
+        if self.from == self.to { self.promotion } else { None }
+    }
+}
+
+// Captured pieces a side holds in hand and may drop (Crazyhouse).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Pocket {
+    pub pawns: u8,
+    pub knights: u8,
+    pub bishops: u8,
+    pub rooks: u8,
+    pub queens: u8,
+}
+
+impl Pocket {
+    pub fn count(&self, kind: PieceKind) -> u8 {
+        match kind {
+            PieceKind::Pawn => self.pawns,
+            PieceKind::Knight => self.knights,
+            PieceKind::Bishop => self.bishops,
+            PieceKind::Rook => self.rooks,
+            PieceKind::Queen => self.queens,
+            PieceKind::King => 0,
+        }
+    }
+
+    fn slot(&mut self, kind: PieceKind) -> Option<&mut u8> {
+        match kind {
+            PieceKind::Pawn => Some(&mut self.pawns),
+            PieceKind::Knight => Some(&mut self.knights),
+            PieceKind::Bishop => Some(&mut self.bishops),
+            PieceKind::Rook => Some(&mut self.rooks),
+            PieceKind::Queen => Some(&mut self.queens),
+            PieceKind::King => None,
+        }
+    }
+
+    pub fn add(&mut self, kind: PieceKind) {
+        if let Some(count) = self.slot(kind) {
+            *count = count.saturating_add(1);
+        }
+    }
+
+    // Takes one `kind` out of the pocket, returning false if there is none.
+    pub fn remove(&mut self, kind: PieceKind) -> bool {
+        match self.slot(kind) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CastleSide {
+    Kingside,  // Towards the h-file: king ends on g, rook on f
+    Queenside, // Towards the a-file: king ends on c, rook on d
+}
+
+impl CastleSide {
+    pub fn king_to_col(self) -> u8 {
+        match self {
+            CastleSide::Kingside => 6,
+            CastleSide::Queenside => 2,
+        }
+    }
+
+    pub fn rook_to_col(self) -> u8 {
+        match self {
+            CastleSide::Kingside => 5,
+            CastleSide::Queenside => 3,
+        }
+    }
+}
+
+// Castling rights stored as the file of the rook each side may still castle with,
+// so Chess960 start positions are covered as well as the standard a/h rooks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CastlingRights {
+    pub white_kingside: Option<u8>,
+    pub white_queenside: Option<u8>,
+    pub black_kingside: Option<u8>,
+    pub black_queenside: Option<u8>,
+}
+
+impl Default for CastlingRights {
+    fn default() -> Self {
+        Self::from_rook_files(7, 0)
+    }
+}
+
+impl CastlingRights {
+    pub const NONE: CastlingRights = CastlingRights {
+        white_kingside: None,
+        white_queenside: None,
+        black_kingside: None,
+        black_queenside: None,
+    };
+
+    // Full rights for both sides with rooks starting on the given files.
+    pub fn from_rook_files(kingside_col: u8, queenside_col: u8) -> Self {
+        Self {
+            white_kingside: Some(kingside_col),
+            white_queenside: Some(queenside_col),
+            black_kingside: Some(kingside_col),
+            black_queenside: Some(queenside_col),
+        }
+    }
+
+    pub fn rook_col(&self, color: Color, side: CastleSide) -> Option<u8> {
+        match (color, side) {
+            (Color::White, CastleSide::Kingside) => self.white_kingside,
+            (Color::White, CastleSide::Queenside) => self.white_queenside,
+            (Color::Black, CastleSide::Kingside) => self.black_kingside,
+            (Color::Black, CastleSide::Queenside) => self.black_queenside,
+        }
+    }
+
+    pub fn clear(&mut self, color: Color) {
+        match color {
+            Color::White => { self.white_kingside = None; self.white_queenside = None; }
+            Color::Black => { self.black_kingside = None; self.black_queenside = None; }
+        }
+    }
+
+    // Clears the right tied to a castling rook's home square, if `square` is one
+    pub fn clear_for_rook_square(&mut self, square: Square) {
+        let (kingside, queenside) = match square.row {
+            0 => (&mut self.white_kingside, &mut self.white_queenside),
+            7 => (&mut self.black_kingside, &mut self.black_queenside),
+            _ => return,
+        };
+        if *kingside == Some(square.col) {
+            *kingside = None;
+        }
+        if *queenside == Some(square.col) {
+            *queenside = None;
+        }
+    }
+}
+
+// Outcome of the position for the side to move.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameResult {
+    Ongoing,
+    Checkmate { winner: Color },
+    Stalemate,
+    FiftyMoveRule,
+    KingOfTheHill { winner: Color },
+    ThreeChecks { winner: Color },
+    KingExploded { winner: Color },
+    AllPiecesLost { winner: Color },  // Antichess: the side with nothing left wins
+    StalemateWin { winner: Color },   // Antichess: the side with no legal move wins
+}
+
+impl GameResult {
+    pub fn is_over(self) -> bool {
+        self != GameResult::Ongoing
+    }
+}
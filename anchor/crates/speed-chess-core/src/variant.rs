@@ -0,0 +1,72 @@
+// src/variant.rs
+use crate::types::{PieceKind, Square};
+
+pub const CHESS960_START_POSITIONS: u16 = 960;
+pub const STANDARD_START_POSITION: u16 = 518; // RNBQKBNR in the Scharnagl numbering
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Variant {
+    #[default]
+    Standard,
+    Chess960, // Fischer Random: shuffled back rank, castling moves encoded as king-takes-own-rook
+    KingOfTheHill, // A king reaching d4, e4, d5 or e5 wins
+    ThreeCheck, // Giving check for the third time wins
+    Crazyhouse, // Captured pieces change sides and may be dropped back onto the board
+    Atomic, // Captures explode the capturer and neighbouring non-pawns; exploding the enemy king wins
+    Antichess, // Captures are compulsory, kings are ordinary pieces and losing everything wins
+}
+
+impl Variant {
+    // Pieces a pawn may promote to; Antichess also allows a king.
+    pub fn promotion_pieces(self) -> &'static [PieceKind] {
+        const WITH_KING: [PieceKind; 5] = [PieceKind::Queen, PieceKind::Rook, PieceKind::Bishop, PieceKind::Knight, PieceKind::King];
+        match self {
+            Variant::Antichess => &WITH_KING,
+            _ => &PieceKind::PROMOTIONS,
+        }
+    }
+}
+
+// d4, e4, d5 and e5
+pub const HILL_SQUARES: [Square; 4] = [
+    Square { row: 3, col: 3 },
+    Square { row: 3, col: 4 },
+    Square { row: 4, col: 3 },
+    Square { row: 4, col: 4 },
+];
+pub const CHECKS_TO_WIN: u8 = 3;
+
+// Knight placements among the five squares left after bishops and queen, indexed by the
+// last digit of the Scharnagl number.
+const KNIGHT_PAIRS: [(usize, usize); 10] = [(0, 1), (0, 2), (0, 3), (0, 4), (1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)];
+
+// Back rank of Chess960 start position `index` (0..960, Scharnagl numbering), None if out of range.
+pub fn chess960_back_rank(index: u16) -> Option<[PieceKind; 8]> {
+    if index >= CHESS960_START_POSITIONS {
+        return None;
+    }
+    let mut rank: [Option<PieceKind>; 8] = [None; 8];
+    let mut n = index as usize;
+
+    rank[(n % 4) * 2 + 1] = Some(PieceKind::Bishop); // Light-squared bishop: b, d, f or h
+    n /= 4;
+    rank[(n % 4) * 2] = Some(PieceKind::Bishop); // Dark-squared bishop: a, c, e or g
+    n /= 4;
+    place_on_nth_empty(&mut rank, n % 6, PieceKind::Queen);
+    n /= 6;
+    let (first_knight, second_knight) = KNIGHT_PAIRS[n];
+    place_on_nth_empty(&mut rank, second_knight, PieceKind::Knight); // Later square first so the earlier index stays valid
+    place_on_nth_empty(&mut rank, first_knight, PieceKind::Knight);
+
+    // The three remaining squares take rook, king, rook so the king sits between the rooks
+    for kind in [PieceKind::Rook, PieceKind::King, PieceKind::Rook] {
+        place_on_nth_empty(&mut rank, 0, kind);
+    }
+    Some(rank.map(|square| square.expect("every square is filled")))
+}
+
+fn place_on_nth_empty(rank: &mut [Option<PieceKind>; 8], nth: usize, kind: PieceKind) {
+    if let Some(square) = rank.iter_mut().filter(|square| square.is_none()).nth(nth) {
+        *square = Some(kind);
+    }
+}
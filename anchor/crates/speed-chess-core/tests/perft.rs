@@ -0,0 +1,353 @@
+// Move generator check against published perft node counts
+// (https://www.chessprogramming.org/Perft_Results).
+use speed_chess_core::*;
+
+fn perft(position: &mut Position, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    let mut nodes = 0;
+    for mv in position.legal_moves() {
+        let undo = position.make_move(mv).expect("generated move must be legal");
+        nodes += perft(position, depth - 1);
+        position.unmake_move(&undo);
+    }
+    nodes
+}
+
+// Piece placement, side, castling and en passant fields of a FEN string
+fn position_from_fen(fen: &str) -> Position {
+    let fields: Vec<&str> = fen.split_whitespace().collect();
+    let mut board: Board = [[None; 8]; 8];
+    for (rank_index, rank) in fields[0].split('/').enumerate() {
+        let row = 7 - rank_index;
+        let mut col = 0;
+        for c in rank.chars() {
+            if let Some(skip) = c.to_digit(10) {
+                col += skip as usize;
+                continue;
+            }
+            let color = if c.is_ascii_uppercase() { Color::White } else { Color::Black };
+            let kind = match c.to_ascii_lowercase() {
+                'p' => PieceKind::Pawn,
+                'n' => PieceKind::Knight,
+                'b' => PieceKind::Bishop,
+                'r' => PieceKind::Rook,
+                'q' => PieceKind::Queen,
+                _ => PieceKind::King,
+            };
+            board[row][col] = Some(Piece::new(kind, color));
+            col += 1;
+        }
+    }
+    // Castling field: KQkq, or Shredder-FEN rook files (e.g. HFhf) for Chess960
+    let mut castling_rights = CastlingRights::NONE;
+    let mut variant = Variant::Standard;
+    for symbol in fields[2].chars().filter(|&symbol| symbol != '-') {
+        let color = if symbol.is_ascii_uppercase() { Color::White } else { Color::Black };
+        let king_col = (0..8).find(|&col| board[color.home_row() as usize][col] == Some(Piece::new(PieceKind::King, color))).unwrap() as u8;
+        let rook_col = match symbol.to_ascii_lowercase() {
+            'k' => 7,
+            'q' => 0,
+            file => {
+                variant = Variant::Chess960;
+                file as u8 - b'a'
+            }
+        };
+        let right = match (color, rook_col > king_col) {
+            (Color::White, true) => &mut castling_rights.white_kingside,
+            (Color::White, false) => &mut castling_rights.white_queenside,
+            (Color::Black, true) => &mut castling_rights.black_kingside,
+            (Color::Black, false) => &mut castling_rights.black_queenside,
+        };
+        *right = Some(rook_col);
+    }
+    let en_passant = fields[3].as_bytes();
+    Position {
+        variant,
+        board,
+        side_to_move: if fields[1] == "w" { Color::White } else { Color::Black },
+        castling_rights,
+        en_passant_target: (en_passant != b"-").then(|| Square::new(en_passant[1] - b'1', en_passant[0] - b'a')),
+        halfmove_clock: 0,
+        fullmove_number: 1,
+        checks_given: [0; 2],
+        pockets: [Pocket::default(); 2],
+        promoted: 0,
+    }
+}
+
+#[test]
+fn perft_starting_position() {
+    let mut position = Position::starting();
+    assert_eq!(perft(&mut position, 1), 20);
+    assert_eq!(perft(&mut position, 2), 400);
+    assert_eq!(perft(&mut position, 3), 8_902);
+    assert_eq!(position, Position::starting(), "make/unmake must restore the position");
+}
+
+#[test]
+fn perft_kiwipete_castling_and_en_passant() {
+    let mut position = position_from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -");
+    assert_eq!(perft(&mut position, 1), 48);
+    assert_eq!(perft(&mut position, 2), 2_039);
+    assert_eq!(perft(&mut position, 3), 97_862);
+}
+
+#[test]
+fn perft_endgame_pins_and_en_passant() {
+    let mut position = position_from_fen("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - -");
+    assert_eq!(perft(&mut position, 1), 14);
+    assert_eq!(perft(&mut position, 2), 191);
+    assert_eq!(perft(&mut position, 3), 2_812);
+    assert_eq!(perft(&mut position, 4), 43_238);
+}
+
+#[test]
+fn perft_promotions() {
+    let mut position = position_from_fen("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq -");
+    assert_eq!(perft(&mut position, 1), 6);
+    assert_eq!(perft(&mut position, 2), 264);
+    assert_eq!(perft(&mut position, 3), 9_467);
+}
+
+#[test]
+fn perft_chess960_positions() {
+    let mut position = position_from_fen("bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9");
+    assert_eq!(perft(&mut position, 1), 21);
+    assert_eq!(perft(&mut position, 2), 528);
+    assert_eq!(perft(&mut position, 3), 12_189);
+
+    let mut position = position_from_fen("2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9");
+    assert_eq!(perft(&mut position, 1), 21);
+    assert_eq!(perft(&mut position, 2), 807);
+    assert_eq!(perft(&mut position, 3), 18_002);
+}
+
+#[test]
+fn chess960_start_positions() {
+    assert_eq!(Position::chess960(STANDARD_START_POSITION).unwrap().board, Position::starting().board);
+    assert!(Position::chess960(CHESS960_START_POSITIONS).is_none());
+    for index in 0..CHESS960_START_POSITIONS {
+        let position = Position::chess960(index).unwrap();
+        let back_rank: Vec<PieceKind> = (0..8).map(|col| position.board[0][col].unwrap().kind).collect();
+        let files_of = |kind| (0..8).filter(|&col| back_rank[col] == kind).collect::<Vec<_>>();
+        let (bishops, rooks, king) = (files_of(PieceKind::Bishop), files_of(PieceKind::Rook), files_of(PieceKind::King)[0]);
+        assert_ne!(bishops[0] % 2, bishops[1] % 2, "bishops on opposite colours in {}", index);
+        assert!(rooks[0] < king && king < rooks[1], "king between the rooks in {}", index);
+    }
+}
+
+#[test]
+fn chess960_castling_encoding() {
+    // King on b1 and rook on a1: O-O-O is b1xa1 and lands the king on c1, rook on d1
+    let mut position = position_from_fen("4k3/8/8/8/8/8/8/RK5R w HA - 0 1");
+    let queenside = position.castling_move(CastleSide::Queenside).unwrap();
+    assert_eq!(queenside, Move::new(Square::new(0, 1), Square::new(0, 0)));
+    assert_eq!(position.castling_side(queenside), Some(CastleSide::Queenside));
+    assert!(position.is_legal(position.castling_move(CastleSide::Kingside).unwrap()));
+    let before = position;
+    let undo = position.make_move(queenside).unwrap();
+    assert_eq!(position.piece_at(Square::new(0, 2)), Some(Piece::new(PieceKind::King, Color::White)));
+    assert_eq!(position.piece_at(Square::new(0, 3)), Some(Piece::new(PieceKind::Rook, Color::White)));
+    assert_eq!(position.castling_rights.white_kingside, None);
+    position.unmake_move(&undo);
+    assert_eq!(position, before);
+
+    // The standard two-square king move is not castling in Chess960
+    assert_eq!(before.validate_move(Move::new(Square::new(0, 1), Square::new(0, 3))), Err(RulesError::IllegalPieceMovement));
+
+    // A rook on c8 covers c1, the queenside destination and a square the king crosses going kingside
+    let covered = position_from_fen("2r1k3/8/8/8/8/8/8/RK5R w HA - 0 1");
+    for side in [CastleSide::Queenside, CastleSide::Kingside] {
+        assert!(!covered.is_legal(covered.castling_move(side).unwrap()));
+    }
+}
+
+#[test]
+fn game_results() {
+    // Fool's mate
+    let mut position = Position::starting();
+    for (from, to) in [((1, 5), (2, 5)), ((6, 4), (4, 4)), ((1, 6), (3, 6)), ((7, 3), (3, 7))] {
+        position.make_move(Move::new(Square::new(from.0, from.1), Square::new(to.0, to.1))).unwrap();
+    }
+    assert_eq!(position.game_result(), GameResult::Checkmate { winner: Color::Black });
+
+    let stalemate = position_from_fen("7k/5Q2/6K1/8/8/8/8/8 b - -");
+    assert_eq!(stalemate.game_result(), GameResult::Stalemate);
+
+    let mut quiet = position_from_fen("7k/8/6K1/8/8/8/8/R7 w - -");
+    quiet.halfmove_clock = 100;
+    assert_eq!(quiet.game_result(), GameResult::FiftyMoveRule);
+}
+
+#[test]
+fn variant_results() {
+    let mut hill = position_from_fen("7k/8/8/8/8/4K3/8/8 w - -");
+    hill.variant = Variant::KingOfTheHill;
+    assert_eq!(hill.game_result(), GameResult::Ongoing);
+    hill.make_move(Move::new(Square::new(2, 4), Square::new(3, 4))).unwrap();
+    assert_eq!(hill.game_result(), GameResult::KingOfTheHill { winner: Color::White });
+
+    // The rook shuttles between checking files; only the third check ends the game
+    let mut three_check = position_from_fen("k7/8/8/8/8/8/8/1R4K1 w - -");
+    three_check.variant = Variant::ThreeCheck;
+    let checks = [((0, 1), (0, 0)), ((7, 0), (7, 1)), ((0, 0), (0, 1)), ((7, 1), (7, 0)), ((0, 1), (0, 0))];
+    let mut undos = Vec::new();
+    for (from, to) in checks {
+        assert_eq!(three_check.game_result(), GameResult::Ongoing);
+        undos.push(three_check.make_move(Move::new(Square::new(from.0, from.1), Square::new(to.0, to.1))).unwrap());
+    }
+    assert_eq!(three_check.checks_given, [3, 0]);
+    assert_eq!(three_check.game_result(), GameResult::ThreeChecks { winner: Color::White });
+
+    for undo in undos.iter().rev() {
+        three_check.unmake_move(undo);
+    }
+    assert_eq!(three_check.checks_given, [0, 0]);
+}
+
+#[test]
+fn rules_errors() {
+    let position = Position::starting();
+    let e2 = Square::new(1, 4);
+    assert_eq!(position.validate_move(Move::new(Square::new(3, 3), e2)), Err(RulesError::EmptySource));
+    assert_eq!(position.validate_move(Move::new(Square::new(6, 4), Square::new(5, 4))), Err(RulesError::NotYourPiece));
+    assert_eq!(position.validate_move(Move::new(Square::new(0, 3), e2)), Err(RulesError::CannotCaptureOwnPiece));
+    assert_eq!(position.validate_move(Move::new(e2, Square::new(4, 4))), Err(RulesError::IllegalPieceMovement));
+    assert_eq!(position.validate_move(Move::new(e2, Square::new(8, 4))), Err(RulesError::OutOfBounds));
+    assert_eq!(
+        position.validate_move(Move::with_promotion(e2, Square::new(3, 4), PieceKind::Queen)),
+        Err(RulesError::PromotionNotOnLastRank)
+    );
+
+    let pinned = position_from_fen("4r2k/8/8/8/8/8/4N3/4K3 w - -");
+    assert_eq!(pinned.validate_move(Move::new(e2, Square::new(3, 5))), Err(RulesError::LeavesKingInCheck));
+}
+
+// Walks the move tree checking that unmake_move restores the exact position, pockets included.
+fn assert_unmake_restores(position: &mut Position, depth: u32) {
+    if depth == 0 {
+        return;
+    }
+    for mv in position.legal_moves() {
+        let before = *position;
+        let undo = position.make_move(mv).expect("generated move must be legal");
+        assert_unmake_restores(position, depth - 1);
+        position.unmake_move(&undo);
+        assert_eq!(*position, before, "{:?}", mv);
+    }
+}
+
+#[test]
+fn crazyhouse_drops() {
+    // Back-rank mate unless Black has a piece in hand to interpose
+    let mut back_rank = position_from_fen("R6k/5ppp/8/8/8/8/8/6K1 b - -");
+    back_rank.variant = Variant::Crazyhouse;
+    assert_eq!(back_rank.game_result(), GameResult::Checkmate { winner: Color::White });
+    back_rank.pockets[Color::Black.index()].knights = 1;
+    assert_eq!(back_rank.game_result(), GameResult::Ongoing);
+    let blocks: Vec<Move> = (1..7).map(|col| Move::drop(PieceKind::Knight, Square::new(7, col))).collect();
+    assert_eq!(back_rank.legal_moves(), blocks);
+
+    let mut bare = position_from_fen("4k3/8/8/8/8/8/8/4K3 w - -");
+    assert_eq!(bare.validate_move(Move::drop(PieceKind::Knight, Square::new(3, 3))), Err(RulesError::DropNotAllowed));
+    bare.variant = Variant::Crazyhouse;
+    bare.pockets[Color::White.index()] = Pocket { pawns: 1, knights: 1, ..Pocket::default() };
+    assert_eq!(bare.validate_move(Move::drop(PieceKind::Pawn, Square::new(7, 0))), Err(RulesError::PawnDropOnBackRank));
+    assert_eq!(bare.validate_move(Move::drop(PieceKind::Knight, Square::new(7, 4))), Err(RulesError::DropOnOccupiedSquare));
+    assert_eq!(bare.validate_move(Move::drop(PieceKind::Queen, Square::new(3, 3))), Err(RulesError::PieceNotInPocket));
+    assert!(bare.make_move(Move::drop(PieceKind::Knight, Square::new(3, 3))).is_ok());
+    assert_eq!(bare.pockets[Color::White.index()], Pocket { pawns: 1, ..Pocket::default() });
+}
+
+#[test]
+fn crazyhouse_captured_promoted_piece_returns_as_pawn() {
+    let mut position = position_from_fen("4k3/P7/8/8/8/8/7K/r7 w - -");
+    position.variant = Variant::Crazyhouse;
+    let start = position;
+    let a8 = Square::new(7, 0);
+    let promote = position.make_move(Move::with_promotion(Square::new(6, 0), a8, PieceKind::Queen)).unwrap();
+    assert!(position.is_promoted(a8));
+    let capture = position.make_move(Move::new(Square::new(0, 0), a8)).unwrap();
+    assert!(!position.is_promoted(a8));
+    assert_eq!(position.pockets[Color::Black.index()], Pocket { pawns: 1, ..Pocket::default() });
+
+    position.unmake_move(&capture);
+    position.unmake_move(&promote);
+    assert_eq!(position, start);
+}
+
+#[test]
+fn crazyhouse_unmake_restores_pockets() {
+    // 1. e4 d5 2. exd5 Qxd5: each side holds a pawn
+    let mut position = Position { variant: Variant::Crazyhouse, ..Position::starting() };
+    for (from, to) in [((1, 4), (3, 4)), ((6, 3), (4, 3)), ((3, 4), (4, 3)), ((7, 3), (4, 3))] {
+        position.make_move(Move::new(Square::new(from.0, from.1), Square::new(to.0, to.1))).unwrap();
+    }
+    assert_eq!(position.pockets, [Pocket { pawns: 1, ..Pocket::default() }; 2]);
+    assert_unmake_restores(&mut position, 3);
+}
+
+// Variant start-position counts as published with the Fairy-Stockfish perft tests
+#[test]
+fn perft_atomic_and_antichess() {
+    let mut atomic = Position::with_variant(Variant::Atomic);
+    assert_eq!(perft(&mut atomic, 4), 197326);
+    let mut antichess = Position::with_variant(Variant::Antichess);
+    assert_eq!(perft(&mut antichess, 3), 8067);
+}
+
+#[test]
+fn atomic_explosions() {
+    // Qxh7 blows up the knight and rook on g8/h8 but not the g7 pawn
+    let mut position = position_from_fen("rnbqkbnr/ppppp1pp/8/5p1Q/8/4P3/PPPP1PPP/RNB1KBNR w KQkq -");
+    position.variant = Variant::Atomic;
+    let before = position;
+    let undo = position.make_move(Move::new(Square::new(4, 7), Square::new(6, 7))).unwrap();
+    assert_eq!(position.piece_at(Square::new(6, 7)), None, "the capturer explodes");
+    assert_eq!(position.piece_at(Square::new(7, 6)), None, "g8 knight explodes");
+    assert!(position.piece_at(Square::new(6, 6)).is_some(), "pawns survive explosions");
+    assert_eq!(position.game_result(), GameResult::Ongoing);
+    position.unmake_move(&undo);
+    assert_eq!(position, before);
+
+    let mut king_hunt = position_from_fen("4k3/5p2/8/7Q/8/8/8/4K3 w - -");
+    king_hunt.variant = Variant::Atomic;
+    king_hunt.make_move(Move::new(Square::new(4, 7), Square::new(6, 5))).unwrap();
+    assert_eq!(king_hunt.king_square(Color::Black), None);
+    assert_eq!(king_hunt.game_result(), GameResult::KingExploded { winner: Color::White });
+
+    // Kings cannot capture, and touching kings cannot give check
+    let mut kings = position_from_fen("8/8/8/3k4/4K3/8/8/4r3 w - -");
+    kings.variant = Variant::Atomic;
+    assert!(!kings.is_in_check(Color::White));
+    let mut capture = position_from_fen("8/8/8/8/3k4/4p3/4K3/8 w - -");
+    capture.variant = Variant::Atomic;
+    assert_eq!(capture.validate_move(Move::new(Square::new(1, 4), Square::new(2, 4))), Err(RulesError::IllegalPieceMovement));
+}
+
+#[test]
+fn antichess_rules() {
+    let mut position = Position::with_variant(Variant::Antichess);
+    for (from, to) in [((1, 4), (3, 4)), ((6, 3), (4, 3))] {
+        position.make_move(Move::new(Square::new(from.0, from.1), Square::new(to.0, to.1))).unwrap();
+    }
+    // exd5 is available, so everything else is illegal
+    assert_eq!(position.validate_move(Move::new(Square::new(1, 0), Square::new(2, 0))), Err(RulesError::CaptureRequired));
+    assert_eq!(position.legal_moves(), vec![Move::new(Square::new(3, 4), Square::new(4, 3))]);
+
+    // Kings can be captured and promoted to; losing everything wins
+    let mut endgame = position_from_fen("8/8/8/8/8/8/1p6/K7 w - -");
+    endgame.variant = Variant::Antichess;
+    assert_eq!(endgame.legal_moves(), vec![Move::new(Square::new(0, 0), Square::new(1, 1))]);
+    endgame.make_move(Move::new(Square::new(0, 0), Square::new(1, 1))).unwrap();
+    assert_eq!(endgame.game_result(), GameResult::AllPiecesLost { winner: Color::Black });
+    let mut promotion = position_from_fen("8/P7/8/8/8/8/8/7k w - -");
+    promotion.variant = Variant::Antichess;
+    assert!(promotion.is_legal(Move::with_promotion(Square::new(6, 0), Square::new(7, 0), PieceKind::King)));
+
+    // A side with no legal move wins
+    let blocked = Position { variant: Variant::Antichess, ..position_from_fen("8/8/8/8/8/p7/P7/8 w - -") };
+    assert_eq!(blocked.game_result(), GameResult::StalemateWin { winner: Color::White });
+}
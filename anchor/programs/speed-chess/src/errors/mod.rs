@@ -86,4 +86,128 @@ pub enum ChessError {
     PlatformTokenAccountError,
     #[msg("Game state is invalid for processing a payout (e.g., winner does not exist).")]
     InvalidGameStateForPayout,
+    #[msg("The join deadline must be in the future.")]
+    InvalidJoinDeadline,
+    #[msg("The join deadline for this match has passed.")]
+    JoinDeadlinePassed,
+    #[msg("This match has no join deadline and cannot expire.")]
+    NoJoinDeadline,
+    #[msg("The join deadline for this match has not passed yet.")]
+    JoinDeadlineNotReached,
+    #[msg("No rematch has been requested for this match.")]
+    NoRematchRequested,
+    #[msg("You cannot accept your own rematch request.")]
+    CannotAcceptOwnRematch,
+    #[msg("The requester's token account has not approved the rematch stake.")]
+    RematchStakeNotApproved,
+    #[msg("Takebacks are not enabled for this match.")]
+    TakebacksDisabled,
+    #[msg("There is no move of yours that can be taken back.")]
+    NoMoveToTakeBack,
+    #[msg("No takeback has been requested.")]
+    NoTakebackRequested,
+    #[msg("You cannot accept your own takeback request.")]
+    CannotAcceptOwnTakeback,
+    #[msg("The SlotHashes sysvar could not be read.")]
+    SlotHashesUnavailable,
+    #[msg("Piece drops are only allowed in crazyhouse.")]
+    DropNotAllowed,
+    #[msg("Invalid drop: The piece is not in your pocket.")]
+    InvalidDropPieceNotInPocket,
+    #[msg("Invalid drop: The target square is occupied.")]
+    InvalidDropOccupiedSquare,
+    #[msg("Invalid drop: Pawns cannot be dropped on the first or last rank.")]
+    InvalidDropPawnOnBackRank,
+    #[msg("Invalid move: A capture is available and must be played.")]
+    InvalidMoveCaptureRequired,
+    #[msg("Invalid handicap: Piece odds need the standard variant, time odds a shorter creator timeout.")]
+    InvalidHandicap,
+    #[msg("Rated matches must use the standard variant without takebacks or handicaps.")]
+    RatedMatchRestriction,
+    #[msg("Rated matches need both player profiles to settle.")]
+    PlayerProfileRequired,
+    #[msg("The match queue is full.")]
+    QueueFull,
+    #[msg("You are already waiting in this queue.")]
+    AlreadyInQueue,
+    #[msg("You are not waiting in this queue.")]
+    NotInQueue,
+    #[msg("An opponent is waiting: pass the new match accounts to be paired.")]
+    QueueMatchAccountsRequired,
+    #[msg("No compatible opponent is waiting, so no match accounts should be passed.")]
+    NoQueueOpponent,
+    #[msg("This lobby page is full.")]
+    LobbyPageFull,
+    #[msg("Lobby pages must be created in order.")]
+    InvalidLobbyPage,
+    #[msg("Only the match creator can cancel it.")]
+    NotMatchCreator,
+    #[msg("Tournament games are paid out by their tournament, not per match.")]
+    TournamentMatch,
+    #[msg("Invalid payout table: Shares must add up to the whole prize pool.")]
+    InvalidPayoutTable,
+    #[msg("The tournament is not open for registration.")]
+    TournamentNotInRegistration,
+    #[msg("The tournament is not in progress.")]
+    TournamentNotInProgress,
+    #[msg("The tournament has not finished yet.")]
+    TournamentNotCompleted,
+    #[msg("The tournament is full.")]
+    TournamentFull,
+    #[msg("You are already registered for this tournament.")]
+    AlreadyRegistered,
+    #[msg("Only the tournament organizer can do this.")]
+    NotTournamentOrganizer,
+    #[msg("A tournament needs at least two players.")]
+    NotEnoughTournamentPlayers,
+    #[msg("This game is not a pairing of the tournament's current round.")]
+    InvalidTournamentPairing,
+    #[msg("The result of this pairing has already been recorded.")]
+    TournamentResultAlreadyRecorded,
+    #[msg("Prize accounts must match the prize winners, in order.")]
+    PrizeAccountMismatch,
+    #[msg("Invalid tournament format: A Swiss tournament needs between 1 and 9 rounds, and an arena must end after it starts.")]
+    InvalidTournamentFormat,
+    #[msg("The arena has not started yet.")]
+    ArenaNotStarted,
+    #[msg("The arena has ended.")]
+    ArenaEnded,
+    #[msg("The arena is still running.")]
+    ArenaStillRunning,
+    #[msg("Fewer than two registered players are waiting for an arena game.")]
+    NoArenaOpponent,
+    #[msg("Berserk is only possible in an arena game, before your first move.")]
+    BerserkNotAllowed,
+    #[msg("The match is delegated to an ephemeral rollup; undelegate it first.")]
+    MatchDelegated,
+    #[msg("The match is not delegated to an ephemeral rollup.")]
+    MatchNotDelegated,
+    #[msg("Delegation accounts do not match the expected programs or addresses.")]
+    InvalidDelegationAccounts,
+    #[msg("The session token does not belong to this signer and match.")]
+    InvalidSessionToken,
+    #[msg("The session key has expired.")]
+    SessionExpired,
+    #[msg("A session must expire in the future, within 24 hours.")]
+    InvalidSessionExpiry,
+    #[msg("Premoves are for the player who is not on move; make the move instead.")]
+    PremoveOnYourTurn,
+    #[msg("A move batch must hold between 1 and MAX_BATCHED_MOVES moves.")]
+    InvalidMoveBatch,
+    #[msg("Both players must sign the move list with ed25519 instructions in the same transaction.")]
+    MissingMoveSignatures,
+    #[msg("Your opponent has already offered a rematch; accept it instead.")]
+    RematchAlreadyRequested,
+    #[msg("The tournament game's result has not been recorded yet.")]
+    TournamentResultNotRecorded,
+    #[msg("Chess960 matches need a nonzero start position commitment; other variants take none.")]
+    InvalidStartPositionCommitment,
+    #[msg("The Chess960 start position has not been revealed yet.")]
+    StartPositionNotRevealed,
+    #[msg("The secret does not match the start position commitment.")]
+    StartPositionSecretMismatch,
+    #[msg("This match has no start position waiting to be revealed.")]
+    NoStartPositionToReveal,
+    #[msg("Queued matches need a positive move timeout.")]
+    InvalidTimeout,
 }
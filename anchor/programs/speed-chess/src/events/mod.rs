@@ -13,12 +13,30 @@ pub struct MoveMadeEvent {
     pub to_row: u8,
     pub to_col: u8,
     pub promotion_piece: Option<PieceType>,
+    pub drop_piece: Option<PieceType>, // Crazyhouse drop onto (to_row, to_col); from_* equal to_*
+    pub move_time_seconds: u32, // Time the mover spent on this move
     pub board_fen: String,
     pub is_check: bool,
     pub is_checkmate: bool,
     pub is_stalemate: bool,
 }
 
+#[event]
+pub struct PremoveSetEvent { // The queued move is in ChessMatch::premove; its own MoveMadeEvent follows when played
+    pub match_id: String,
+    pub player: Pubkey,
+    pub player_color: PlayerColor,
+    pub cleared: bool,
+}
+
+#[event]
+pub struct MovesBatchAppliedEvent { // Emitted by make_moves after the MoveMadeEvent of each ply
+    pub match_id: String,
+    pub first_ply: u32,
+    pub moves_submitted: u16,
+    pub moves_applied: u16, // Fewer than submitted when a move ended the game
+}
+
 #[event]
 pub struct GameEndedEvent {
     pub match_id: String, // Changed to String
@@ -27,6 +45,204 @@ pub struct GameEndedEvent {
     pub reason: GameEndReason,
 }
 
+#[event]
+pub struct QueueJoinedEvent {
+    pub player: Pubkey,
+    pub betting_token_mint: Pubkey,
+    pub bet_amount: u64,
+    pub move_timeout_duration: i64,
+    pub rating: Option<u16>,
+    pub rating_window: Option<u16>,
+}
+
+#[event]
+pub struct QueueLeftEvent {
+    pub player: Pubkey,
+    pub betting_token_mint: Pubkey,
+    pub bet_amount: u64,
+    pub move_timeout_duration: i64,
+}
+
+#[event]
+pub struct QueueMatchedEvent { // The waiting player plays White
+    pub match_id: String,
+    pub white_player: Pubkey,
+    pub black_player: Pubkey,
+    pub betting_token_mint: Pubkey,
+    pub bet_amount_per_player: u64,
+    pub move_timeout_duration: i64,
+    pub white_clock_starts_at: i64, // White is not timed out before this; the queued player may have been away
+}
+
+#[event]
+pub struct TournamentCreatedEvent {
+    pub tournament_id: String,
+    pub organizer: Pubkey,
+    pub betting_token_mint: Pubkey,
+    pub entry_fee: u64,
+    pub move_timeout_duration: i64,
+    pub payout_table: Vec<u16>,
+    pub format: TournamentFormat,
+}
+
+#[event]
+pub struct TournamentRegisteredEvent {
+    pub tournament_id: String,
+    pub player: Pubkey,
+    pub rating: Option<u16>,
+    pub prize_pool: u64,
+}
+
+#[event]
+pub struct TournamentRoundStartedEvent {
+    pub tournament_id: String,
+    pub round: u8,
+    pub bracket: Vec<Pubkey>, // Pairs of slots; Pubkey::default() is a bye
+}
+
+#[event]
+pub struct TournamentMatchCreatedEvent {
+    pub tournament_id: String,
+    pub round: u8,
+    pub pairing: u8,
+    pub match_id: String,
+    pub white_player: Pubkey,
+    pub black_player: Pubkey,
+}
+
+#[event]
+pub struct TournamentResultRecordedEvent {
+    pub tournament_id: String,
+    pub round: u8,
+    pub match_id: String,
+    pub winner: Pubkey,
+    pub loser: Pubkey,
+}
+
+#[event]
+pub struct KnockoutArmageddonEvent { // The drawn pairing plays again; create_tournament_match creates the Armageddon game
+    pub tournament_id: String,
+    pub round: u8,
+    pub pairing: u8,
+    pub drawn_match_id: String,
+    pub armageddon_match_id: String,
+}
+
+#[event]
+pub struct TournamentMatchClosedEvent {
+    pub tournament_id: String,
+    pub match_id: String,
+    pub rent_payer: Pubkey,
+}
+
+#[event]
+pub struct SwissResultRecordedEvent {
+    pub tournament_id: String,
+    pub round: u8,
+    pub match_id: String,
+    pub white_player: Pubkey,
+    pub black_player: Pubkey,
+    pub result: GameStatus, // WhiteWins, BlackWins or Draw
+}
+
+#[event]
+pub struct SwissStandingsEvent { // Emitted when every game of a Swiss round is recorded
+    pub tournament_id: String,
+    pub round: u8,
+    pub standings: Vec<SwissStanding>,
+}
+
+#[event]
+pub struct ArenaMatchCreatedEvent {
+    pub tournament_id: String,
+    pub game: u16, // Arena game number
+    pub match_id: String,
+    pub white_player: Pubkey,
+    pub black_player: Pubkey,
+}
+
+#[event]
+pub struct BerserkEvent {
+    pub match_id: String,
+    pub player: Pubkey,
+    pub move_timeout: i64, // The player's halved move timeout
+}
+
+#[event]
+pub struct ArenaResultRecordedEvent {
+    pub tournament_id: String,
+    pub match_id: String,
+    pub white_player: Pubkey,
+    pub black_player: Pubkey,
+    pub result: GameStatus, // WhiteWins, BlackWins or Draw
+    pub white_points: u16,
+    pub black_points: u16,
+}
+
+#[event]
+pub struct ArenaStandingsEvent { // Emitted by finish_arena
+    pub tournament_id: String,
+    pub standings: Vec<ArenaStanding>,
+}
+
+#[event]
+pub struct TournamentCompletedEvent {
+    pub tournament_id: String,
+    pub champion: Pubkey, // Swiss and arena: first in the final standings
+}
+
+#[event]
+pub struct TournamentPrizesDistributedEvent {
+    pub tournament_id: String,
+    pub platform_fee: u64,
+    pub prizes: Vec<TournamentPrize>,
+}
+
+#[event]
+pub struct MatchDelegatedEvent {
+    pub match_id: String,
+    pub delegated_by: Pubkey,
+    pub validator: Option<Pubkey>,
+}
+
+#[event]
+pub struct MatchCommittedEvent { // Emitted inside the rollup by commit_match and undelegate_match
+    pub match_id: String,
+    pub game_status: GameStatus,
+    pub undelegate: bool,
+}
+
+#[event]
+pub struct SessionCreatedEvent {
+    pub match_id: String,
+    pub player: Pubkey,
+    pub session_signer: Pubkey,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct SessionRevokedEvent {
+    pub chess_match: Pubkey,
+    pub player: Pubkey,
+    pub session_signer: Pubkey,
+}
+
+#[event]
+pub struct RatingChangedEvent { // Emitted by process_match_settlement
+    pub match_id: String,
+    pub white: Pubkey,
+    pub black: Pubkey,
+    pub white_rating_before: u16,
+    pub white_rating_after: u16,
+    pub black_rating_before: u16,
+    pub black_rating_after: u16,
+    pub time_control: TimeControl, // Glicko-2 pool the game counted towards
+    pub white_glicko_before: GlickoRating, // Deviation includes any inactivity growth
+    pub white_glicko_after: GlickoRating,
+    pub black_glicko_before: GlickoRating,
+    pub black_glicko_after: GlickoRating,
+}
+
 #[event]
 pub struct PlayerJoinedEvent { // For join_match instruction
     pub match_id: String,    // Changed to String
@@ -34,6 +250,13 @@ pub struct PlayerJoinedEvent { // For join_match instruction
     pub player_two: Pubkey,
     pub betting_token_mint: Pubkey,
     pub bet_amount_per_player: u64,
+    pub variant: Variant, // Chess960 start positions follow in StartPositionRevealedEvent
+}
+
+#[event]
+pub struct StartPositionRevealedEvent {
+    pub match_id: String,
+    pub start_position_index: u16, // Scharnagl number of the Chess960 start position
 }
 
 
@@ -63,5 +286,58 @@ pub struct MatchCreatedEvent {
     pub bet_amount: u64,
     pub move_timeout_duration: i64, // Added this field
     pub platform_fee_basis_points: u16, // Added this field
+    pub join_deadline: Option<i64>,
+    pub takebacks_enabled: bool,
+    pub variant: Variant,
+    pub handicap: Handicap, // Given by the creator, who plays White
+    pub rated: bool,
 }
 
+#[event]
+pub struct MatchCancelledEvent {
+    pub match_id: String,
+    pub creator: Pubkey,
+    pub refunded_amount: u64,
+}
+
+#[event]
+pub struct MatchExpiredEvent {
+    pub match_id: String,
+    pub creator: Pubkey,
+    pub refunded_amount: u64,
+}
+
+
+#[event]
+pub struct RematchRequestedEvent {
+    pub match_id: String,
+    pub requester: Pubkey,
+    pub rematch_match_id: String,
+}
+
+#[event]
+pub struct RematchAcceptedEvent {
+    pub original_match_id: String,
+    pub match_id: String,
+    pub white_player: Pubkey,
+    pub black_player: Pubkey,
+    pub betting_token_mint: Pubkey,
+    pub bet_amount_per_player: u64,
+    pub variant: Variant,
+    pub start_position_index: u16,
+    pub rated: bool,
+}
+
+#[event]
+pub struct TakebackRequestedEvent {
+    pub match_id: String,
+    pub requester: Pubkey,
+    pub requester_color: PlayerColor,
+}
+
+#[event]
+pub struct TakebackAcceptedEvent {
+    pub match_id: String,
+    pub requester_color: PlayerColor,
+    pub plies_undone: u8,
+}
@@ -0,0 +1,158 @@
+// src/instructions/accept_rematch.rs
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct AcceptRematch<'info> {
+    #[account(
+        mut,
+        seeds = [b"chess_match", original_match.load()?.match_id_bytes()],
+        bump = original_match.load()?.bump,
+        constraint = original_match.load()?.rematch_requested_by().is_some() @ ChessError::NoRematchRequested,
+    )]
+    pub original_match: AccountLoader<'info, ChessMatch>,
+
+    #[account(
+        init,
+        payer = acceptor_signer,
+        space = 8 + ChessMatch::INIT_SPACE,
+        seeds = [b"chess_match", original_match.load()?.rematch_match_id().as_bytes()],
+        bump
+    )]
+    pub chess_match: AccountLoader<'info, ChessMatch>,
+
+    #[account(
+        init,
+        payer = acceptor_signer,
+        space = MoveHistory::space_for(0),
+        seeds = [b"move_history", original_match.load()?.rematch_match_id().as_bytes()],
+        bump
+    )]
+    pub move_history: Account<'info, MoveHistory>,
+
+    #[account(address = original_match.load()?.betting_token_mint @ ChessError::InvalidMint)]
+    pub betting_token_mint_account: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = acceptor_signer,
+        seeds = [b"match_escrow", original_match.load()?.rematch_match_id().as_bytes()],
+        bump,
+        token::mint = betting_token_mint_account,
+        token::authority = chess_match
+    )]
+    pub match_escrow_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub acceptor_signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = acceptor_token_account.owner == acceptor_signer.key() @ ChessError::InvalidOwner,
+        constraint = acceptor_token_account.mint == original_match.load()?.betting_token_mint @ ChessError::InvalidMint,
+    )]
+    pub acceptor_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = Some(requester_token_account.owner) == original_match.load()?.rematch_requested_by() @ ChessError::PlayerTokenAccountMismatch,
+        constraint = requester_token_account.mint == original_match.load()?.betting_token_mint @ ChessError::InvalidMint,
+    )]
+    pub requester_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AcceptRematch>) -> Result<()> {
+    let original_match = ctx.accounts.original_match.load()?;
+    let chess_match = &mut ctx.accounts.chess_match.load_init()?;
+    let acceptor_key = ctx.accounts.acceptor_signer.key();
+    let requester_key = original_match.rematch_requested_by().ok_or(error!(ChessError::NoRematchRequested))?;
+    let clock = Clock::get()?;
+
+    // 1. The acceptor must be the other player of the original match
+    require!(
+        acceptor_key == original_match.players[0] || acceptor_key == original_match.players[1],
+        ChessError::NotAPlayer
+    );
+    require!(acceptor_key != requester_key, ChessError::CannotAcceptOwnRematch);
+
+    let stake = original_match.bet_amount_player_one;
+    let requester_token_account = &ctx.accounts.requester_token_account;
+    require!(
+        requester_token_account.delegate == COption::Some(ctx.accounts.original_match.key()) &&
+        requester_token_account.delegated_amount >= stake,
+        ChessError::RematchStakeNotApproved
+    );
+
+    // 2. Set up the new match: same settings, colors swapped, already active
+    chess_match.start_rematch_of(&original_match, clock.unix_timestamp)?;
+    chess_match.bump = ctx.bumps.chess_match;
+
+    let move_history = &mut ctx.accounts.move_history;
+    move_history.match_id = chess_match.match_id();
+    move_history.bump = ctx.bumps.move_history;
+    move_history.moves = Vec::new();
+
+    // 3. The offer has been consumed. The original match is released first: it signs the
+    // requester's transfer below, and a CPI cannot read an account that is mutably borrowed.
+    let original_match_id = original_match.match_id();
+    let original_match_bump = original_match.bump;
+    drop(original_match);
+    ctx.accounts.original_match.load_mut()?.set_rematch_requested_by(None);
+
+    // 4. Escrow both stakes: the acceptor signs for their own, and the original match PDA
+    // spends the requester's stake through the allowance granted in request_rematch.
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.acceptor_token_account.to_account_info(),
+                to: ctx.accounts.match_escrow_token_account.to_account_info(),
+                authority: ctx.accounts.acceptor_signer.to_account_info(),
+            },
+        ),
+        stake,
+    )?;
+
+    let seeds: &[&[u8]] = &[
+        b"chess_match",
+        original_match_id.as_bytes(),
+        &[original_match_bump],
+    ];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.requester_token_account.to_account_info(),
+                to: ctx.accounts.match_escrow_token_account.to_account_info(),
+                authority: ctx.accounts.original_match.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        stake,
+    )?;
+
+    msg!("Rematch of {} accepted. New match: {}", original_match_id, chess_match.match_id());
+
+    emit!(RematchAcceptedEvent {
+        original_match_id,
+        match_id: chess_match.match_id(),
+        white_player: chess_match.players[0],
+        black_player: chess_match.players[1],
+        betting_token_mint: chess_match.betting_token_mint,
+        bet_amount_per_player: stake,
+        variant: chess_match.variant(),
+        start_position_index: chess_match.start_position_index,
+        rated: chess_match.rated(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+// src/instructions/accept_takeback.rs
+use anchor_lang::prelude::*;
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::state::*;
+use crate::utils::chess_logic;
+
+#[derive(Accounts)]
+pub struct AcceptTakeback<'info> {
+    #[account(
+        mut,
+        seeds = [b"chess_match", chess_match.load()?.match_id_bytes()],
+        bump = chess_match.load()?.bump,
+    )]
+    pub chess_match: AccountLoader<'info, ChessMatch>,
+
+    #[account(
+        mut,
+        seeds = [b"move_history", chess_match.load()?.match_id_bytes()],
+        bump = move_history.bump,
+    )]
+    pub move_history: Account<'info, MoveHistory>,
+
+    #[account(mut)]
+    pub player_signer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptTakeback>) -> Result<()> {
+    let chess_match = &mut ctx.accounts.chess_match.load_mut()?;
+    let player_key = ctx.accounts.player_signer.key();
+    let clock = Clock::get()?;
+
+    require!(chess_match.game_status() == GameStatus::Active, ChessError::GameNotActive);
+    let requester_color = chess_match.takeback_requested_by().ok_or(error!(ChessError::NoTakebackRequested))?;
+
+    // 1. Only the requester's opponent can accept
+    let accepter_color = if player_key == chess_match.players[0] {
+        PlayerColor::White
+    } else if player_key == chess_match.players[1] {
+        PlayerColor::Black
+    } else {
+        return err!(ChessError::NotAPlayer);
+    };
+    require!(accepter_color != requester_color, ChessError::CannotAcceptOwnTakeback);
+
+    // 2. Rewind until the requester is on move again
+    let plies = chess_logic::take_back(chess_match, &mut ctx.accounts.move_history, requester_color)?;
+
+    chess_match.set_takeback_requested_by(None);
+    chess_match.set_premove(None); // Queued against a position that no longer exists
+    chess_match.current_player_idx = if chess_match.current_turn() == PlayerColor::White { 0 } else { 1 };
+    chess_match.last_move_timestamp = clock.unix_timestamp; // Requester gets a fresh move clock
+
+    msg!("Takeback accepted in match {}: {} ply(s) undone", chess_match.match_id(), plies);
+
+    emit!(TakebackAcceptedEvent {
+        match_id: chess_match.match_id(),
+        requester_color,
+        plies_undone: plies as u8,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+// src/instructions/berserk.rs
+use anchor_lang::prelude::*;
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct Berserk<'info> {
+    #[account(
+        mut,
+        seeds = [b"chess_match", chess_match.load()?.match_id_bytes()],
+        bump = chess_match.load()?.bump,
+        constraint = chess_match.load()?.tournament() == Some(tournament.key()) @ ChessError::BerserkNotAllowed,
+        constraint = chess_match.load()?.game_status() == GameStatus::Active @ ChessError::GameNotActive,
+    )]
+    pub chess_match: AccountLoader<'info, ChessMatch>,
+
+    #[account(
+        constraint = matches!(tournament.format, TournamentFormat::Arena { .. }) @ ChessError::BerserkNotAllowed,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    pub player_signer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<Berserk>) -> Result<()> {
+    let chess_match = &mut ctx.accounts.chess_match.load_mut()?;
+    let player_key = ctx.accounts.player_signer.key();
+
+    let color = if player_key == chess_match.players[0] {
+        PlayerColor::White
+    } else if player_key == chess_match.players[1] {
+        PlayerColor::Black
+    } else {
+        return err!(ChessError::NotAPlayer);
+    };
+
+    // Only before the player's own first move: White until move 1 is played, Black until its reply
+    let has_moved = chess_match.fullmove_number > 1 || (color == PlayerColor::White && chess_match.current_turn() == PlayerColor::Black);
+    require!(!has_moved && !chess_match.berserk(color), ChessError::BerserkNotAllowed);
+    chess_match.set_berserk(color);
+
+    emit!(BerserkEvent {
+        match_id: chess_match.match_id(),
+        player: player_key,
+        move_timeout: chess_match.move_timeout_for(color),
+    });
+
+    Ok(())
+}
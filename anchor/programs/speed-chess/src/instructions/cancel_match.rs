@@ -0,0 +1,82 @@
+// src/instructions/cancel_match.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::state::*;
+use crate::utils::payout_logic;
+
+#[derive(Accounts)]
+pub struct CancelMatch<'info> {
+    #[account(
+        mut,
+        seeds = [b"chess_match", chess_match.load()?.match_id_bytes()],
+        bump = chess_match.load()?.bump,
+        constraint = chess_match.load()?.game_status() == GameStatus::WaitingForOpponent @ ChessError::MatchAlreadyFullOrActive,
+        constraint = chess_match.load()?.players[0] == creator.key() @ ChessError::NotMatchCreator,
+        close = creator,
+    )]
+    pub chess_match: AccountLoader<'info, ChessMatch>,
+
+    #[account(
+        mut,
+        seeds = [b"move_history", chess_match.load()?.match_id_bytes()],
+        bump = move_history.bump,
+        close = creator,
+    )]
+    pub move_history: Account<'info, MoveHistory>,
+
+    #[account(
+        mut,
+        seeds = [b"match_escrow", chess_match.load()?.match_id_bytes()],
+        bump,
+    )]
+    pub match_escrow_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"lobby", chess_match.load()?.lobby_page.to_le_bytes().as_ref()],
+        bump = lobby_page.bump,
+    )]
+    pub lobby_page: Account<'info, LobbyPage>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = creator_token_account.owner == creator.key() @ ChessError::PlayerTokenAccountMismatch,
+        constraint = creator_token_account.mint == chess_match.load()?.betting_token_mint @ ChessError::PlayerTokenAccountMismatch,
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<CancelMatch>) -> Result<()> {
+    let chess_match = &ctx.accounts.chess_match.load()?;
+
+    // 1. Take the challenge out of the lobby
+    ctx.accounts.lobby_page.remove_listing(&chess_match.match_id());
+
+    // 2. Refund the creator's stake and close the escrow
+    let refunded_amount = payout_logic::refund_and_close_escrow(
+        &ctx.accounts.chess_match,
+        &ctx.accounts.match_escrow_token_account,
+        &ctx.accounts.creator_token_account.to_account_info(),
+        &ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+
+    msg!("Match {} cancelled. Refunded {} to creator {}", chess_match.match_id(), refunded_amount, chess_match.players[0]);
+
+    // 3. Emit event (the chess_match account itself is closed by the `close` constraint)
+    emit!(MatchCancelledEvent {
+        match_id: chess_match.match_id(),
+        creator: chess_match.players[0],
+        refunded_amount,
+    });
+
+    Ok(())
+}
@@ -9,24 +9,24 @@ use crate::state::*;  // Ensure ChessMatch, GameStatus, PlayerColor, GameEndReas
 pub struct ClaimTimeoutWin<'info> {
     #[account(
         mut,
-        seeds = [b"chess_match", chess_match.match_id.as_bytes()], // Assumes chess_match.match_id is String
-        bump = chess_match.bump,
+        seeds = [b"chess_match", chess_match.load()?.match_id_bytes()], // Assumes chess_match.match_id is String
+        bump = chess_match.load()?.bump,
     )]
-    pub chess_match: Account<'info, ChessMatch>,
+    pub chess_match: AccountLoader<'info, ChessMatch>,
 
     #[account(mut)] // Signer is mutable due to transaction fees
     pub claimer_signer: Signer<'info>, // The player claiming the timeout win
 }
 
 pub fn handler(ctx: Context<ClaimTimeoutWin>) -> Result<()> {
-    let chess_match = &mut ctx.accounts.chess_match;
+    let chess_match = &mut ctx.accounts.chess_match.load_mut()?;
     let claimer_key = ctx.accounts.claimer_signer.key();
     let clock = Clock::get()?;
     let now = clock.unix_timestamp;
 
     // 1. Ensure game is active.
     require!(
-        chess_match.game_status == GameStatus::Active,
+        chess_match.game_status() == GameStatus::Active,
         ChessError::GameNotActive // Or "CannotClaimTimeoutForNonActiveGame"
     );
 
@@ -53,7 +53,7 @@ pub fn handler(ctx: Context<ClaimTimeoutWin>) -> Result<()> {
 
     // 3. Ensure it was the opponent's turn.
     require!(
-        chess_match.current_turn == opponent_color,
+        chess_match.current_turn() == opponent_color,
         ChessError::NotOpponentsTurnToClaimTimeout // New Error: "Cannot claim timeout if it's your turn"
     );
 
@@ -65,16 +65,16 @@ pub fn handler(ctx: Context<ClaimTimeoutWin>) -> Result<()> {
 
     let time_since_last_move = now.saturating_sub(chess_match.last_move_timestamp);
     require!(
-        time_since_last_move > chess_match.move_timeout_duration,
+        time_since_last_move > chess_match.move_timeout_for(opponent_color),
         ChessError::OpponentNotTimedOut // Or a more specific "TimeoutThresholdNotReached"
     );
 
     // 5. Opponent has timed out. Claimer wins.
-    chess_match.game_status = match claimer_color {
+    chess_match.set_game_status(match claimer_color {
         PlayerColor::White => GameStatus::WhiteWins,
         PlayerColor::Black => GameStatus::BlackWins,
-    };
-    chess_match.game_end_reason = Some(GameEndReason::Timeout);
+    });
+    chess_match.set_game_end_reason(Some(GameEndReason::Timeout));
     chess_match.last_move_timestamp = now; // Record time of game end due to timeout claim
 
     msg!("Player {:?} ({:?}) timed out. Player {:?} ({:?}) wins by timeout claim.", 
@@ -86,8 +86,8 @@ pub fn handler(ctx: Context<ClaimTimeoutWin>) -> Result<()> {
 
     // 6. Emit GameEndedEvent.
     emit!(GameEndedEvent {
-        match_id: chess_match.match_id.clone(), // Assuming match_id in ChessMatch is String
-        status: chess_match.game_status,
+        match_id: chess_match.match_id(), // Assuming match_id in ChessMatch is String
+        status: chess_match.game_status(),
         winner: Some(claimer_color), // The claimer wins
         reason: GameEndReason::Timeout,
     });
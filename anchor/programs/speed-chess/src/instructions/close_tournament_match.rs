@@ -0,0 +1,60 @@
+// src/instructions/close_tournament_match.rs
+use anchor_lang::prelude::*;
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CloseTournamentMatch<'info> {
+    #[account(
+        seeds = [b"tournament", tournament.tournament_id.as_bytes()],
+        bump = tournament.bump,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    #[account(
+        mut,
+        close = rent_payer, // Rent for the match account goes back to whoever created the game
+        seeds = [b"chess_match", chess_match.load()?.match_id_bytes()],
+        bump = chess_match.load()?.bump,
+        constraint = chess_match.load()?.tournament() == Some(tournament.key()) @ ChessError::InvalidTournamentPairing,
+    )]
+    pub chess_match: AccountLoader<'info, ChessMatch>,
+
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [b"move_history", chess_match.load()?.match_id_bytes()],
+        bump = move_history.bump,
+    )]
+    pub move_history: Account<'info, MoveHistory>,
+
+    #[account(mut, address = chess_match.load()?.rent_payer @ ChessError::InvalidOwner)]
+    pub rent_payer: SystemAccount<'info>,
+
+    // Anyone may close a finished game; the rent always goes to rent_payer
+    pub caller: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseTournamentMatch>) -> Result<()> {
+    let chess_match = ctx.accounts.chess_match.load()?;
+
+    // The result must have been counted first. Arena games still running when the arena
+    // closed are never counted, so those only need to have finished.
+    require!(
+        chess_match.result_recorded() ||
+        (ctx.accounts.tournament.status != TournamentStatus::InProgress && chess_match.is_concluded()),
+        ChessError::TournamentResultNotRecorded
+    );
+
+    msg!("Closing tournament game {}", chess_match.match_id());
+
+    emit!(TournamentMatchClosedEvent {
+        tournament_id: ctx.accounts.tournament.tournament_id.clone(),
+        match_id: chess_match.match_id(),
+        rent_payer: chess_match.rent_payer,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+// src/instructions/commit_match.rs
+use anchor_lang::prelude::*;
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::state::*;
+use crate::utils::{schedule_commit, MAGIC_CONTEXT_ID, MAGIC_PROGRAM_ID};
+
+// Runs inside the ephemeral rollup, where the delegated match accounts are writable.
+#[derive(Accounts)]
+pub struct CommitMatch<'info> {
+    #[account(
+        mut,
+        seeds = [b"chess_match", chess_match.load()?.match_id_bytes()],
+        bump = chess_match.load()?.bump,
+        constraint = chess_match.load()?.delegated() @ ChessError::MatchNotDelegated,
+    )]
+    pub chess_match: AccountLoader<'info, ChessMatch>,
+
+    #[account(
+        mut,
+        seeds = [b"move_history", chess_match.load()?.match_id_bytes()],
+        bump = move_history.bump,
+    )]
+    pub move_history: Account<'info, MoveHistory>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: MagicBlock context account that queues scheduled commits.
+    #[account(mut, address = MAGIC_CONTEXT_ID)]
+    pub magic_context: AccountInfo<'info>,
+
+    /// CHECK: MagicBlock magic program.
+    #[account(address = MAGIC_PROGRAM_ID)]
+    pub magic_program: AccountInfo<'info>,
+}
+
+// Checkpoints the live game on the base layer; anyone may commit, and the rollup also commits on
+// its own every DEFAULT_COMMIT_FREQUENCY_MS.
+pub fn handler(ctx: Context<CommitMatch>) -> Result<()> {
+    commit(ctx, false)
+}
+
+// Shared with undelegate_match: the accounts are written out before the magic program snapshots them.
+pub fn commit(ctx: Context<CommitMatch>, undelegate: bool) -> Result<()> {
+    if undelegate {
+        ctx.accounts.chess_match.load_mut()?.set_delegated(false);
+    }
+    ctx.accounts.chess_match.exit(&crate::ID)?;
+    ctx.accounts.move_history.exit(&crate::ID)?;
+
+    schedule_commit(
+        &ctx.accounts.payer.to_account_info(),
+        &[&ctx.accounts.chess_match.to_account_info(), &ctx.accounts.move_history.to_account_info()],
+        &ctx.accounts.magic_context,
+        &ctx.accounts.magic_program,
+        undelegate,
+    )?;
+
+    let chess_match = ctx.accounts.chess_match.load()?;
+    emit!(MatchCommittedEvent {
+        match_id: chess_match.match_id(),
+        game_status: chess_match.game_status(),
+        undelegate,
+    });
+
+    Ok(())
+}
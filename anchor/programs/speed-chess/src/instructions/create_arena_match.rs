@@ -0,0 +1,107 @@
+// src/instructions/create_arena_match.rs
+use anchor_lang::prelude::*;
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::state::*;
+use crate::utils::arena_pairing;
+
+#[derive(Accounts)]
+pub struct CreateArenaMatch<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.as_bytes()],
+        bump = tournament.bump,
+        constraint = tournament.status == TournamentStatus::InProgress @ ChessError::TournamentNotInProgress,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ChessMatch::INIT_SPACE,
+        seeds = [b"chess_match", tournament.arena_match_id(tournament.games_created).as_bytes()],
+        bump
+    )]
+    pub chess_match: AccountLoader<'info, ChessMatch>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = MoveHistory::space_for(0),
+        seeds = [b"move_history", tournament.arena_match_id(tournament.games_created).as_bytes()],
+        bump
+    )]
+    pub move_history: Account<'info, MoveHistory>,
+
+    // Anyone may pair idle players, typically one of them right after their last game
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateArenaMatch>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    let chess_match = &mut ctx.accounts.chess_match.load_init()?;
+    let clock = Clock::get()?;
+
+    // 1. New games only start inside the arena window
+    let TournamentFormat::Arena { ends_at, .. } = tournament.format else {
+        return err!(ChessError::InvalidTournamentPairing);
+    };
+    require!(clock.unix_timestamp < ends_at, ChessError::ArenaEnded);
+
+    // 2. Pair two idle players and mark them as playing this game
+    let (white, black) = arena_pairing(&tournament.players).ok_or(error!(ChessError::NoArenaOpponent))?;
+    let game = tournament.games_created;
+    tournament.players[white as usize].arena.current_game = Some(game);
+    tournament.players[black as usize].arena.current_game = Some(game);
+    tournament.games_created = game.checked_add(1).ok_or(ChessError::MathError)?;
+    let white_player = tournament.players[white as usize].player;
+    let black_player = tournament.players[black as usize].player;
+
+    // 3. The game is already active; the prize pool replaces per-game stakes
+    chess_match.set_match_id(&tournament.arena_match_id(game));
+    chess_match.players = [white_player, black_player];
+    chess_match.set_variant(Variant::Standard);
+    chess_match.start_position_index = STANDARD_START_POSITION;
+    chess_match.set_handicap(Handicap::None);
+    chess_match.reset_position();
+
+    chess_match.last_move_timestamp = clock.unix_timestamp; // White's clock starts now
+    chess_match.move_timeout_duration = tournament.move_timeout_duration;
+    chess_match.set_join_deadline(None);
+    chess_match.set_takebacks_enabled(false);
+    chess_match.set_rated(false); // Tournament games are never settled, so they leave profiles alone
+    chess_match.set_tournament(Some(tournament.key()));
+
+    chess_match.set_game_status(GameStatus::Active);
+    chess_match.set_game_end_reason(None);
+
+    chess_match.betting_token_mint = tournament.betting_token_mint;
+    chess_match.bet_amount_player_one = 0;
+    chess_match.bet_amount_player_two = 0;
+    chess_match.total_pot = 0;
+    chess_match.platform_fee_basis_points = 0;
+    chess_match.set_payout_processed(false);
+    chess_match.set_rematch_requested_by(None);
+    chess_match.rent_payer = ctx.accounts.payer.key();
+
+    chess_match.bump = ctx.bumps.chess_match;
+
+    let move_history = &mut ctx.accounts.move_history;
+    move_history.match_id = chess_match.match_id();
+    move_history.bump = ctx.bumps.move_history;
+    move_history.moves = Vec::new();
+
+    emit!(ArenaMatchCreatedEvent {
+        tournament_id: tournament.tournament_id.clone(),
+        game,
+        match_id: chess_match.match_id(),
+        white_player,
+        black_player,
+    });
+
+    Ok(())
+}
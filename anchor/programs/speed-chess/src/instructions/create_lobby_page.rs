@@ -0,0 +1,46 @@
+// src/instructions/create_lobby_page.rs
+use anchor_lang::prelude::*;
+
+use crate::errors::ChessError;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(page_index_arg: u32)]
+pub struct CreateLobbyPage<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + LobbyPage::INIT_SPACE,
+        seeds = [b"lobby", page_index_arg.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub lobby_page: Account<'info, LobbyPage>,
+
+    // Required for every page but the first, so pages stay contiguous
+    #[account(
+        seeds = [b"lobby", page_index_arg.saturating_sub(1).to_le_bytes().as_ref()],
+        bump = previous_page.bump,
+    )]
+    pub previous_page: Option<Account<'info, LobbyPage>>,
+
+    // Anyone may add a page once the existing ones fill up
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateLobbyPage>, page_index_arg: u32) -> Result<()> {
+    require!(
+        page_index_arg == 0 || ctx.accounts.previous_page.is_some(),
+        ChessError::InvalidLobbyPage
+    );
+
+    let lobby_page = &mut ctx.accounts.lobby_page;
+    lobby_page.page_index = page_index_arg;
+    lobby_page.bump = ctx.bumps.lobby_page;
+    lobby_page.listings = Vec::new();
+
+    msg!("Lobby page {} created", page_index_arg);
+    Ok(())
+}
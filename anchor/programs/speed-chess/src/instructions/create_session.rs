@@ -0,0 +1,85 @@
+// src/instructions/create_session.rs
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(session_signer_arg: Pubkey)]
+pub struct CreateSession<'info> {
+    #[account(
+        init,
+        payer = player_signer,
+        space = 8 + SessionToken::INIT_SPACE,
+        seeds = [b"session", chess_match.key().as_ref(), session_signer_arg.as_ref()],
+        bump
+    )]
+    pub session_token: Account<'info, SessionToken>,
+
+    #[account(
+        seeds = [b"chess_match", chess_match.load()?.match_id_bytes()],
+        bump = chess_match.load()?.bump,
+        constraint = (
+            chess_match.load()?.game_status() == GameStatus::WaitingForOpponent ||
+            chess_match.load()?.game_status() == GameStatus::Active
+        ) @ ChessError::GameNotActive,
+    )]
+    pub chess_match: AccountLoader<'info, ChessMatch>,
+
+    // The session key itself; it pays its own transaction fees and move history growth
+    #[account(mut, address = session_signer_arg @ ChessError::InvalidSessionToken)]
+    pub session_signer: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub player_signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Usually sent in the same transaction as initialize_match or join_match, so the player approves
+// the session once in their wallet.
+pub fn handler(ctx: Context<CreateSession>, session_signer_arg: Pubkey, expires_at_arg: i64, lamports_arg: u64) -> Result<()> {
+    let chess_match = &ctx.accounts.chess_match.load()?;
+    let player_key = ctx.accounts.player_signer.key();
+    let now = Clock::get()?.unix_timestamp;
+
+    // 1. A player of this match, delegating to a different key for a bounded time
+    require!(chess_match.players.contains(&player_key), ChessError::NotAPlayer);
+    require!(session_signer_arg != player_key, ChessError::InvalidSessionToken);
+    require!(
+        expires_at_arg > now && expires_at_arg - now <= MAX_SESSION_DURATION,
+        ChessError::InvalidSessionExpiry
+    );
+
+    let session_token = &mut ctx.accounts.session_token;
+    session_token.chess_match = ctx.accounts.chess_match.key();
+    session_token.player = player_key;
+    session_token.session_signer = session_signer_arg;
+    session_token.expires_at = expires_at_arg;
+    session_token.bump = ctx.bumps.session_token;
+
+    // 2. Fund the session key for fees
+    if lamports_arg > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.player_signer.to_account_info(),
+                    to: ctx.accounts.session_signer.to_account_info(),
+                },
+            ),
+            lamports_arg,
+        )?;
+    }
+
+    emit!(SessionCreatedEvent {
+        match_id: chess_match.match_id(),
+        player: player_key,
+        session_signer: session_signer_arg,
+        expires_at: expires_at_arg,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+// src/instructions/create_tournament.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::state::*;
+use crate::utils::{payout_table_is_valid, swiss_payout_table_is_valid};
+
+// Define allowed token mints
+const SEND_TOKEN_MINT_STR: &str = "SENDYLjLBaTgjyfXtPP2aHUt91WhNzX7iUfpThyApht"; // mock-SEND mint
+const WSOL_MINT_STR: &str = "WSiBAnrREwNLdGkDpXuqdKL4fJvAHeJhDfehmFdMdvw";     // mock-Wrapped SOL mint
+
+#[derive(Accounts)]
+#[instruction(tournament_id_arg: String)]
+pub struct CreateTournament<'info> {
+    #[account(
+        init,
+        payer = organizer,
+        space = 8 + Tournament::INIT_SPACE,
+        seeds = [b"tournament", tournament_id_arg.as_bytes()],
+        bump
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    #[account(
+        init,
+        payer = organizer,
+        seeds = [b"tournament_vault", tournament_id_arg.as_bytes()],
+        bump,
+        token::mint = betting_token_mint_account,
+        token::authority = tournament // The tournament PDA holds the prize pool
+    )]
+    pub tournament_vault: Account<'info, TokenAccount>,
+
+    pub betting_token_mint_account: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub organizer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateTournament>,
+    tournament_id_arg: String,
+    entry_fee_arg: u64,
+    move_timeout_duration_arg: i64,
+    platform_fee_basis_points_arg: u16,
+    payout_table_arg: Vec<u16>,
+    format_arg: TournamentFormat,
+) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+
+    // 1. Validate the settings
+    require!(
+        !tournament_id_arg.is_empty() && tournament_id_arg.len() <= MAX_TOURNAMENT_ID_LEN,
+        ChessError::InvalidMatchIdLength
+    );
+    let send_mint_pubkey = SEND_TOKEN_MINT_STR.parse::<Pubkey>().map_err(|_| error!(ChessError::InvalidPublicKeyString))?;
+    let wsol_mint_pubkey = WSOL_MINT_STR.parse::<Pubkey>().map_err(|_| error!(ChessError::InvalidPublicKeyString))?;
+    let mint_key = ctx.accounts.betting_token_mint_account.key();
+    require!(
+        mint_key == send_mint_pubkey || mint_key == wsol_mint_pubkey,
+        ChessError::UnsupportedBettingToken
+    );
+    require!(platform_fee_basis_points_arg <= 10000, ChessError::InvalidPlatformFee);
+    let payout_table_valid = match format_arg {
+        TournamentFormat::Knockout => payout_table_is_valid(&payout_table_arg),
+        TournamentFormat::Swiss { rounds } => {
+            require!((1..=MAX_SWISS_ROUNDS).contains(&rounds), ChessError::InvalidTournamentFormat);
+            swiss_payout_table_is_valid(&payout_table_arg)
+        }
+        TournamentFormat::Arena { starts_at, ends_at } => {
+            require!(starts_at < ends_at, ChessError::InvalidTournamentFormat);
+            swiss_payout_table_is_valid(&payout_table_arg)
+        }
+    };
+    require!(
+        payout_table_arg.len() <= MAX_PAYOUT_PLACES && payout_table_valid,
+        ChessError::InvalidPayoutTable
+    );
+
+    // 2. Open registration
+    tournament.tournament_id = tournament_id_arg;
+    tournament.organizer = ctx.accounts.organizer.key();
+    tournament.bump = ctx.bumps.tournament;
+    tournament.status = TournamentStatus::Registration;
+    tournament.format = format_arg;
+    tournament.betting_token_mint = mint_key;
+    tournament.entry_fee = entry_fee_arg;
+    tournament.prize_pool = 0;
+    tournament.platform_fee_basis_points = platform_fee_basis_points_arg;
+    tournament.payout_table = payout_table_arg;
+    tournament.move_timeout_duration = move_timeout_duration_arg;
+    tournament.players = Vec::new();
+    tournament.current_round = 0;
+    tournament.total_rounds = 0;
+    tournament.bracket = Vec::new();
+    tournament.round_winners = Vec::new();
+    tournament.results_pending = 0;
+    tournament.armageddon_pairings = 0;
+    tournament.games_created = 0;
+
+    emit!(TournamentCreatedEvent {
+        tournament_id: tournament.tournament_id.clone(),
+        organizer: tournament.organizer,
+        betting_token_mint: mint_key,
+        entry_fee: entry_fee_arg,
+        move_timeout_duration: move_timeout_duration_arg,
+        payout_table: tournament.payout_table.clone(),
+        format: format_arg,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+// src/instructions/create_tournament_match.rs
+use anchor_lang::prelude::*;
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(pairing_arg: u8)]
+pub struct CreateTournamentMatch<'info> {
+    #[account(
+        seeds = [b"tournament", tournament.tournament_id.as_bytes()],
+        bump = tournament.bump,
+        constraint = tournament.status == TournamentStatus::InProgress @ ChessError::TournamentNotInProgress,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ChessMatch::INIT_SPACE,
+        seeds = [b"chess_match", tournament.pairing_match_id(pairing_arg).as_bytes()],
+        bump
+    )]
+    pub chess_match: AccountLoader<'info, ChessMatch>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = MoveHistory::space_for(0),
+        seeds = [b"move_history", tournament.pairing_match_id(pairing_arg).as_bytes()],
+        bump
+    )]
+    pub move_history: Account<'info, MoveHistory>,
+
+    // Anyone may create the round's games, typically the organizer or one of the players
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateTournamentMatch>, pairing_arg: u8) -> Result<()> {
+    let tournament = &ctx.accounts.tournament;
+    let chess_match = &mut ctx.accounts.chess_match.load_init()?;
+    let clock = Clock::get()?;
+
+    // 1. Byes are advanced at the start of the round and need no game
+    let (white, black) = tournament.pairing(pairing_arg).ok_or(error!(ChessError::InvalidTournamentPairing))?;
+    require!(
+        white != Pubkey::default() && black != Pubkey::default(),
+        ChessError::InvalidTournamentPairing
+    );
+
+    // 2. The game is already active; the prize pool replaces per-game stakes.
+    // A knockout pairing replaying a draw gets Armageddon time odds: Black moves faster.
+    chess_match.set_match_id(&tournament.pairing_match_id(pairing_arg));
+    chess_match.players = [white, black];
+    chess_match.set_variant(Variant::Standard);
+    chess_match.start_position_index = STANDARD_START_POSITION;
+    chess_match.set_handicap(tournament.pairing_handicap(pairing_arg));
+    chess_match.set_handicap_color(PlayerColor::Black);
+    chess_match.reset_position();
+
+    chess_match.last_move_timestamp = clock.unix_timestamp; // White's clock starts now
+    chess_match.move_timeout_duration = tournament.move_timeout_duration;
+    chess_match.set_join_deadline(None);
+    chess_match.set_takebacks_enabled(false);
+    chess_match.set_rated(false); // Tournament games are never settled, so they leave profiles alone
+    chess_match.set_tournament(Some(tournament.key()));
+
+    chess_match.set_game_status(GameStatus::Active);
+    chess_match.set_game_end_reason(None);
+
+    chess_match.betting_token_mint = tournament.betting_token_mint;
+    chess_match.bet_amount_player_one = 0;
+    chess_match.bet_amount_player_two = 0;
+    chess_match.total_pot = 0;
+    chess_match.platform_fee_basis_points = 0;
+    chess_match.set_payout_processed(false);
+    chess_match.set_rematch_requested_by(None);
+    chess_match.rent_payer = ctx.accounts.payer.key();
+
+    chess_match.bump = ctx.bumps.chess_match;
+
+    let move_history = &mut ctx.accounts.move_history;
+    move_history.match_id = chess_match.match_id();
+    move_history.bump = ctx.bumps.move_history;
+    move_history.moves = Vec::new();
+
+    emit!(TournamentMatchCreatedEvent {
+        tournament_id: tournament.tournament_id.clone(),
+        round: tournament.current_round,
+        pairing: pairing_arg,
+        match_id: chess_match.match_id(),
+        white_player: white,
+        black_player: black,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+// src/instructions/delegate_match.rs
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::state::*;
+use crate::utils::{delegate_account, DelegateAccounts, BUFFER_SEED, DELEGATION_PROGRAM_ID};
+
+#[derive(Accounts)]
+#[instruction(match_id_arg: String)]
+pub struct DelegateMatch<'info> {
+    /// CHECK: ChessMatch PDA, loaded in the handler; it changes owner during the instruction,
+    /// so it cannot be an AccountLoader that Anchor writes back on exit.
+    #[account(mut, seeds = [b"chess_match", match_id_arg.as_bytes()], bump)]
+    pub chess_match: AccountInfo<'info>,
+
+    /// CHECK: MoveHistory PDA of the same match, delegated alongside it.
+    #[account(mut, seeds = [b"move_history", match_id_arg.as_bytes()], bump)]
+    pub move_history: AccountInfo<'info>,
+
+    /// CHECK: Temporary buffer for the match data, created and closed by this instruction.
+    #[account(mut, seeds = [BUFFER_SEED, chess_match.key().as_ref()], bump)]
+    pub chess_match_buffer: AccountInfo<'info>,
+
+    /// CHECK: Temporary buffer for the move history data.
+    #[account(mut, seeds = [BUFFER_SEED, move_history.key().as_ref()], bump)]
+    pub move_history_buffer: AccountInfo<'info>,
+
+    /// CHECK: Created by the delegation program ([b"delegation", chess_match]).
+    #[account(mut)]
+    pub chess_match_delegation_record: AccountInfo<'info>,
+
+    /// CHECK: Created by the delegation program ([b"delegation-metadata", chess_match]).
+    #[account(mut)]
+    pub chess_match_delegation_metadata: AccountInfo<'info>,
+
+    /// CHECK: Created by the delegation program ([b"delegation", move_history]).
+    #[account(mut)]
+    pub move_history_delegation_record: AccountInfo<'info>,
+
+    /// CHECK: Created by the delegation program ([b"delegation-metadata", move_history]).
+    #[account(mut)]
+    pub move_history_delegation_metadata: AccountInfo<'info>,
+
+    // Either player; pays for the delegation records and the move history's rollup headroom
+    #[account(mut)]
+    pub player_signer: Signer<'info>,
+
+    /// CHECK: This program, recorded as the owner of the delegated accounts.
+    #[account(address = crate::ID)]
+    pub owner_program: AccountInfo<'info>,
+
+    /// CHECK: MagicBlock delegation program.
+    #[account(address = DELEGATION_PROGRAM_ID)]
+    pub delegation_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<DelegateMatch>, match_id_arg: String, validator_arg: Option<Pubkey>) -> Result<()> {
+    let player_key = ctx.accounts.player_signer.key();
+
+    // 1. Only a started game can move to the rollup, at a player's request
+    {
+        let mut data = ctx.accounts.chess_match.try_borrow_mut_data()?;
+        require!(data.starts_with(ChessMatch::DISCRIMINATOR), anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch);
+        let chess_match: &mut ChessMatch = bytemuck::from_bytes_mut(&mut data[8..8 + ChessMatch::INIT_SPACE]);
+        require!(chess_match.game_status() == GameStatus::Active, ChessError::GameNotActive);
+        require!(chess_match.players.contains(&player_key), ChessError::NotAPlayer);
+        require!(!chess_match.delegated(), ChessError::MatchDelegated);
+        chess_match.set_delegated(true);
+    }
+
+    // 2. Fund the move history up front: make_move's realloc then needs no payer inside the rollup
+    let move_history = &ctx.accounts.move_history;
+    let rent_shortfall = Rent::get()?
+        .minimum_balance(MoveHistory::space_for(DELEGATED_MOVE_CAPACITY))
+        .saturating_sub(move_history.lamports());
+    if rent_shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.player_signer.to_account_info(),
+                    to: move_history.clone(),
+                },
+            ),
+            rent_shortfall,
+        )?;
+    }
+
+    // 3. Hand both accounts to the delegation program
+    let payer = ctx.accounts.player_signer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    delegate_account(
+        DelegateAccounts {
+            payer: &payer,
+            pda: &ctx.accounts.chess_match,
+            owner_program: &ctx.accounts.owner_program,
+            buffer: &ctx.accounts.chess_match_buffer,
+            delegation_record: &ctx.accounts.chess_match_delegation_record,
+            delegation_metadata: &ctx.accounts.chess_match_delegation_metadata,
+            delegation_program: &ctx.accounts.delegation_program,
+            system_program: &system_program,
+        },
+        &[b"chess_match", match_id_arg.as_bytes()],
+        validator_arg,
+    )?;
+    delegate_account(
+        DelegateAccounts {
+            payer: &payer,
+            pda: &ctx.accounts.move_history,
+            owner_program: &ctx.accounts.owner_program,
+            buffer: &ctx.accounts.move_history_buffer,
+            delegation_record: &ctx.accounts.move_history_delegation_record,
+            delegation_metadata: &ctx.accounts.move_history_delegation_metadata,
+            delegation_program: &ctx.accounts.delegation_program,
+            system_program: &system_program,
+        },
+        &[b"move_history", match_id_arg.as_bytes()],
+        validator_arg,
+    )?;
+
+    msg!("Match {} delegated to the ephemeral rollup", match_id_arg);
+
+    emit!(MatchDelegatedEvent {
+        match_id: match_id_arg,
+        delegated_by: player_key,
+        validator: validator_arg,
+    });
+
+    Ok(())
+}
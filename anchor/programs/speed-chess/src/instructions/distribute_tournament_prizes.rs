@@ -0,0 +1,109 @@
+// src/instructions/distribute_tournament_prizes.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::state::*;
+use crate::utils::{arena_prize_amounts, prize_amounts, swiss_prize_amounts};
+
+#[derive(Accounts)]
+pub struct DistributeTournamentPrizes<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.as_bytes()],
+        bump = tournament.bump,
+        constraint = tournament.status == TournamentStatus::Completed @ ChessError::TournamentNotCompleted,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament_vault", tournament.tournament_id.as_bytes()],
+        bump,
+    )]
+    pub tournament_vault: Account<'info, TokenAccount>,
+
+    // Platform's fee collection account
+    #[account(
+        mut,
+        constraint = platform_fee_ata.mint == tournament.betting_token_mint @ ChessError::PlatformTokenAccountError,
+    )]
+    pub platform_fee_ata: Account<'info, TokenAccount>,
+
+    // Anyone may trigger the payout once the final is recorded
+    pub caller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    // remaining_accounts: one token account per prize winner, in utils::prize_amounts order
+    // (champion first, then the other paid players in seed order), or for a Swiss tournament in
+    // utils::swiss_prize_amounts / utils::arena_prize_amounts order (final standings)
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, DistributeTournamentPrizes<'info>>) -> Result<()> {
+    let tournament = &ctx.accounts.tournament;
+
+    // 1. The platform fee comes out of the pool once, for the whole tournament
+    let fee = tournament.prize_pool
+        .checked_mul(tournament.platform_fee_basis_points.into())
+        .ok_or(ChessError::MathError)?
+        .checked_div(10000)
+        .ok_or(ChessError::MathError)?;
+    let pool = tournament.prize_pool.checked_sub(fee).ok_or(ChessError::MathError)?;
+
+    let prizes = match tournament.format {
+        TournamentFormat::Knockout => prize_amounts(&tournament.players, tournament.total_rounds, &tournament.payout_table, pool),
+        TournamentFormat::Swiss { .. } => swiss_prize_amounts(&tournament.players, &tournament.payout_table, pool),
+        TournamentFormat::Arena { .. } => arena_prize_amounts(&tournament.players, &tournament.payout_table, pool),
+    };
+    require!(ctx.remaining_accounts.len() == prizes.len(), ChessError::PrizeAccountMismatch);
+
+    let tournament_id_bytes = tournament.tournament_id.as_bytes();
+    let seeds: &[&[u8]] = &[
+        b"tournament",
+        tournament_id_bytes,
+        &[tournament.bump],
+    ];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+    let transfer_from_vault = |to: AccountInfo<'info>, amount: u64| -> Result<()> {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.tournament_vault.to_account_info(),
+                    to,
+                    authority: tournament.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )
+    };
+
+    if fee > 0 {
+        msg!("Transferring tournament platform fee: {}", fee);
+        transfer_from_vault(ctx.accounts.platform_fee_ata.to_account_info(), fee)?;
+    }
+
+    // 2. Pay each prize winner into their own token account
+    for (&(player, amount), account_info) in prizes.iter().zip(ctx.remaining_accounts.iter()) {
+        let token_account = Account::<TokenAccount>::try_from(account_info)?;
+        require!(
+            token_account.owner == player && token_account.mint == tournament.betting_token_mint,
+            ChessError::PrizeAccountMismatch
+        );
+        msg!("Paying {} to {}", amount, player);
+        transfer_from_vault(account_info.clone(), amount)?;
+    }
+
+    let tournament_id = tournament.tournament_id.clone();
+    ctx.accounts.tournament.status = TournamentStatus::PrizesDistributed;
+
+    emit!(TournamentPrizesDistributedEvent {
+        tournament_id,
+        platform_fee: fee,
+        prizes: prizes.iter().map(|&(player, amount)| TournamentPrize { player, amount }).collect(),
+    });
+
+    Ok(())
+}
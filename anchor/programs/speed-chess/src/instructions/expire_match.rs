@@ -0,0 +1,89 @@
+// src/instructions/expire_match.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::state::*;
+use crate::utils::payout_logic;
+
+#[derive(Accounts)]
+pub struct ExpireMatch<'info> {
+    #[account(
+        mut,
+        seeds = [b"chess_match", chess_match.load()?.match_id_bytes()],
+        bump = chess_match.load()?.bump,
+        constraint = chess_match.load()?.game_status() == GameStatus::WaitingForOpponent @ ChessError::MatchAlreadyFullOrActive,
+        close = creator, // Rent for the match account goes back to the creator
+    )]
+    pub chess_match: AccountLoader<'info, ChessMatch>,
+
+    #[account(
+        mut,
+        seeds = [b"move_history", chess_match.load()?.match_id_bytes()],
+        bump = move_history.bump,
+        close = creator,
+    )]
+    pub move_history: Account<'info, MoveHistory>,
+
+    #[account(
+        mut,
+        seeds = [b"match_escrow", chess_match.load()?.match_id_bytes()],
+        bump,
+    )]
+    pub match_escrow_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"lobby", chess_match.load()?.lobby_page.to_le_bytes().as_ref()],
+        bump = lobby_page.bump,
+    )]
+    pub lobby_page: Account<'info, LobbyPage>,
+
+    /// CHECK: Only receives lamports from the closed accounts; pinned to the match creator.
+    #[account(mut, address = chess_match.load()?.players[0] @ ChessError::NotAPlayer)]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = creator_token_account.owner == chess_match.load()?.players[0] @ ChessError::PlayerTokenAccountMismatch,
+        constraint = creator_token_account.mint == chess_match.load()?.betting_token_mint @ ChessError::PlayerTokenAccountMismatch,
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    // Anyone may expire a stale challenge; they only pay the transaction fee.
+    pub caller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<ExpireMatch>) -> Result<()> {
+    let chess_match = &ctx.accounts.chess_match.load()?;
+    let clock = Clock::get()?;
+
+    // 1. Only challenges created with a deadline can expire, and only once it has passed
+    chess_match.check_expirable_at(clock.unix_timestamp)?;
+
+    // 2. Take the challenge out of the lobby
+    ctx.accounts.lobby_page.remove_listing(&chess_match.match_id());
+
+    // 3. Refund the creator's stake and close the escrow
+    let refunded_amount = payout_logic::refund_and_close_escrow(
+        &ctx.accounts.chess_match,
+        &ctx.accounts.match_escrow_token_account,
+        &ctx.accounts.creator_token_account.to_account_info(),
+        &ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+
+    msg!("Match {} expired. Refunded {} to creator {}", chess_match.match_id(), refunded_amount, chess_match.players[0]);
+
+    // 4. Emit event (the chess_match account itself is closed by the `close` constraint)
+    emit!(MatchExpiredEvent {
+        match_id: chess_match.match_id(),
+        creator: chess_match.players[0],
+        refunded_amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+// src/instructions/finish_arena.rs
+use anchor_lang::prelude::*;
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::state::*;
+use crate::utils::arena_standings;
+
+#[derive(Accounts)]
+pub struct FinishArena<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.as_bytes()],
+        bump = tournament.bump,
+        constraint = tournament.status == TournamentStatus::InProgress @ ChessError::TournamentNotInProgress,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    // Anyone may close the arena once its window is over
+    pub caller: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<FinishArena>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    let TournamentFormat::Arena { ends_at, .. } = tournament.format else {
+        return err!(ChessError::TournamentNotInProgress);
+    };
+    require!(Clock::get()?.unix_timestamp >= ends_at, ChessError::ArenaStillRunning);
+
+    // Games still being played when the arena closes do not count
+    tournament.status = TournamentStatus::Completed;
+    let standings = arena_standings(&tournament.players);
+    let champion = standings.first().map(|standing| standing.player).unwrap_or_default();
+
+    msg!("Arena {} won by {}", tournament.tournament_id, champion);
+
+    emit!(ArenaStandingsEvent {
+        tournament_id: tournament.tournament_id.clone(),
+        standings,
+    });
+    emit!(TournamentCompletedEvent {
+        tournament_id: tournament.tournament_id.clone(),
+        champion,
+    });
+
+    Ok(())
+}
@@ -5,29 +5,31 @@ use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 use crate::errors::ChessError;
 use crate::events::*;
 use crate::state::*;      // Brings in ChessMatch, Enums, CastlingRights, MAX_MATCH_ID_LEN, etc.
-use crate::utils::*; // For initialize_chess_board (ensure this is in scope, e.g. pub use crate::utils::chess_logic)
-                        // If initialize_chess_board is directly in utils module: use crate::utils::initialize_chess_board;
 
 // Define allowed token mints
 const SEND_TOKEN_MINT_STR: &str = "SENDYLjLBaTgjyfXtPP2aHUt91WhNzX7iUfpThyApht"; // mock-SEND mint
 const WSOL_MINT_STR: &str = "WSiBAnrREwNLdGkDpXuqdKL4fJvAHeJhDfehmFdMdvw";     // mock-Wrapped SOL mint
 
 #[derive(Accounts)]
-#[instruction(
-    match_id_arg: String, 
-    bet_amount_arg: u64, 
-    move_timeout_duration_arg: i64, 
-    platform_fee_basis_points_arg: u16
-)]
+#[instruction(args: InitializeMatchArgs)]
 pub struct InitializeMatch<'info> {
     #[account(
         init,
         payer = player_signer,
         space = 8 + ChessMatch::INIT_SPACE, // Anchor adds 8 bytes for discriminator
-        seeds = [b"chess_match", match_id_arg.as_bytes()], // Use instruction arg for seed
+        seeds = [b"chess_match", args.match_id.as_bytes()], // Use instruction arg for seed
         bump
     )]
-    pub chess_match: Account<'info, ChessMatch>,
+    pub chess_match: AccountLoader<'info, ChessMatch>,
+
+    #[account(
+        init,
+        payer = player_signer,
+        space = MoveHistory::space_for(0), // Starts empty, grows with every move
+        seeds = [b"move_history", args.match_id.as_bytes()],
+        bump
+    )]
+    pub move_history: Account<'info, MoveHistory>,
 
     #[account(mut)]
     pub player_signer: Signer<'info>, // Renamed from 'player' for clarity
@@ -45,32 +47,55 @@ pub struct InitializeMatch<'info> {
     #[account(
         init,
         payer = player_signer,
-        seeds = [b"match_escrow", match_id_arg.as_bytes()], // Use instruction arg for seed
+        seeds = [b"match_escrow", args.match_id.as_bytes()], // Use instruction arg for seed
         bump,
         token::mint = betting_token_mint_account, // Use the validated mint account from context
         token::authority = chess_match // The chess_match PDA is the authority of this escrow account
     )]
     pub match_escrow_token_account: Account<'info, TokenAccount>, // Renamed from 'match_token_account'
 
+    // Lobby page the new challenge is listed on; any page with room will do
+    #[account(
+        mut,
+        seeds = [b"lobby", lobby_page.page_index.to_le_bytes().as_ref()],
+        bump = lobby_page.bump,
+    )]
+    pub lobby_page: Box<Account<'info, LobbyPage>>,
+
+    // Passed when the creator has a profile, to show their rating in the lobby
+    #[account(
+        seeds = [b"player_profile", player_signer.key().as_ref()],
+        bump = creator_profile.bump,
+    )]
+    pub creator_profile: Option<Account<'info, PlayerProfile>>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     // rent: Sysvar<'info, Rent>, // Removed, not strictly needed for `init` as payer covers rent.
 }
 
-pub fn handler(
-    ctx: Context<InitializeMatch>, 
-    match_id_arg: String, 
-    bet_amount_arg: u64,
-    move_timeout_duration_arg: i64,
-    platform_fee_basis_points_arg: u16,
-) -> Result<()> {
-    let chess_match_account = &mut ctx.accounts.chess_match;
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct InitializeMatchArgs {
+    pub match_id: String,
+    pub bet_amount: u64,
+    pub move_timeout_duration: i64,
+    pub platform_fee_basis_points: u16,
+    pub join_deadline: Option<i64>,  // Unix timestamp, None = joinable forever
+    pub takebacks_enabled: bool,
+    pub variant: Variant,            // Chess960 start position is drawn when the opponent joins
+    pub handicap: Handicap,          // Odds the creator gives (piece odds need the standard variant)
+    pub rated: bool,                 // Rated matches update player profiles at settlement
+    pub start_position_commitment: Option<[u8; 32]>, // Chess960 only: utils::start_position_commitment of the creator's secret
+}
+
+pub fn handler(ctx: Context<InitializeMatch>, args: InitializeMatchArgs) -> Result<()> {
+    let chess_match_account = &mut ctx.accounts.chess_match.load_init()?;
     let player_signer_account = &ctx.accounts.player_signer;
     let clock = Clock::get()?;
 
     // 1. Validate match_id length (important for PDA and state String)
     require!(
-        !match_id_arg.is_empty() && match_id_arg.len() <= MAX_MATCH_ID_LEN, 
+        !args.match_id.is_empty() && args.match_id.len() <= MAX_MATCH_ID_LEN, 
         ChessError::InvalidMatchIdLength
     );
 
@@ -88,51 +113,93 @@ pub fn handler(
     // This check should ideally consider token decimals.
     // Example: if 10_000_000 is for a 6-decimal token like SEND.
     if actual_betting_token_mint_key == send_mint_pubkey {
-        require!(bet_amount_arg == 10_000_000, ChessError::InvalidBetAmount); // e.g., 10 SEND
+        require!(args.bet_amount == 10_000_000, ChessError::InvalidBetAmount); // e.g., 10 SEND
     } else if actual_betting_token_mint_key == wsol_mint_pubkey {
         // For wSOL (9 decimals), a similar "unit" bet would be much larger.
         // For example, 0.1 wSOL = 100_000_000 lamports. Adjust this as per your game's design.
-        require!(bet_amount_arg == 100_000_000, ChessError::InvalidBetAmount); // e.g., 0.1 wSOL
+        require!(args.bet_amount == 100_000_000, ChessError::InvalidBetAmount); // e.g., 0.1 wSOL
     } else {
         // This branch should ideally not be reached due to the UnsupportedBettingToken check.
         return err!(ChessError::InvalidBetAmount); // Fallback, though logically covered.
     }
 
     // 4. Initialize ChessMatch account fields
-    chess_match_account.match_id = match_id_arg.clone(); // Use the validated instruction argument
+    chess_match_account.set_match_id(&args.match_id); // Use the validated instruction argument
     chess_match_account.players[0] = player_signer_account.key();
     chess_match_account.players[1] = Pubkey::default(); // Player 2 joins later
     chess_match_account.current_player_idx = 0; 
-    chess_match_account.current_turn = PlayerColor::White;
+    chess_match_account.set_current_turn(PlayerColor::White);
     
     chess_match_account.last_move_timestamp = clock.unix_timestamp; 
-    chess_match_account.move_timeout_duration = move_timeout_duration_arg;
-
-    chess_match_account.game_status = GameStatus::WaitingForOpponent;
-    chess_match_account.game_end_reason = None;
-
-    // Ensure chess_logic::initialize_chess_board is correctly imported/accessible
-    // If it's in `src/utils/chess_logic.rs`, then `use crate::utils::chess_logic;` at the top
-    // and then `chess_logic::initialize_chess_board()`.
-    // If `initialize_chess_board` itself is pub in `src/utils/mod.rs` and re-exported,
-    // then `crate::utils::initialize_chess_board()` might work.
-    // Your original file used `use crate::utils::initialize_chess_board;`
-    chess_match_account.board = chess_logic::initialize_chess_board();
-    chess_match_account.castling_rights = CastlingRights::default();
-    chess_match_account.en_passant_target = None;
-    chess_match_account.halfmove_clock = 0;
-    chess_match_account.fullmove_number = 1;
+    chess_match_account.move_timeout_duration = args.move_timeout_duration;
+
+    // Piece odds edit the standard setup; time odds must actually shorten the creator's clock
+    match args.handicap {
+        Handicap::None => {}
+        Handicap::TimeOdds { creator_move_timeout } => require!(
+            creator_move_timeout > 0 && creator_move_timeout < args.move_timeout_duration,
+            ChessError::InvalidHandicap
+        ),
+        _ => require!(args.variant == Variant::Standard, ChessError::InvalidHandicap),
+    }
+    chess_match_account.set_handicap(args.handicap);
+    chess_match_account.set_handicap_color(PlayerColor::White); // The creator plays White
+
+    chess_match_account.set_rated(args.rated);
+
+    // An open challenge may optionally stop being joinable after a deadline
+    if let Some(deadline) = args.join_deadline {
+        require!(deadline > clock.unix_timestamp, ChessError::InvalidJoinDeadline);
+    }
+    chess_match_account.set_join_deadline(args.join_deadline);
+
+    chess_match_account.set_game_status(GameStatus::WaitingForOpponent);
+    chess_match_account.set_game_end_reason(None);
+
+    // Chess960 matches keep the standard setup until the start position is drawn. The creator commits
+    // to a secret share now; join_match adds the joiner's share and reveal_start_position opens the secret.
+    require!(
+        matches!(args.start_position_commitment, Some(commitment) if commitment != [0; 32]) == (args.variant == Variant::Chess960),
+        ChessError::InvalidStartPositionCommitment
+    );
+    chess_match_account.set_start_position_commitment(args.start_position_commitment);
+    chess_match_account.set_variant(args.variant);
+    chess_match_account.start_position_index = STANDARD_START_POSITION;
+    chess_match_account.reset_position(); // Standard setup minus any odds pieces
+
+    chess_match_account.set_takebacks_enabled(args.takebacks_enabled);
+    chess_match_account.set_takeback_requested_by(None);
+    chess_match_account.check_rated_settings()?;
 
     chess_match_account.betting_token_mint = actual_betting_token_mint_key;
-    chess_match_account.bet_amount_player_one = bet_amount_arg;
+    chess_match_account.bet_amount_player_one = args.bet_amount;
     chess_match_account.bet_amount_player_two = 0; 
-    chess_match_account.total_pot = bet_amount_arg; 
+    chess_match_account.total_pot = args.bet_amount; 
     
-    require!(platform_fee_basis_points_arg <= 10000, ChessError::InvalidPlatformFee); // Max 100% fee
-    chess_match_account.platform_fee_basis_points = platform_fee_basis_points_arg;
+    require!(args.platform_fee_basis_points <= 10000, ChessError::InvalidPlatformFee); // Max 100% fee
+    chess_match_account.platform_fee_basis_points = args.platform_fee_basis_points;
     
     chess_match_account.bump = ctx.bumps.chess_match; // Store the bump for the chess_match PDA
 
+    let move_history = &mut ctx.accounts.move_history;
+    move_history.match_id = args.match_id.clone();
+    move_history.bump = ctx.bumps.move_history;
+    move_history.moves = Vec::new();
+
+    // List the challenge in the lobby so clients can find it without scanning program accounts
+    let lobby_page = &mut ctx.accounts.lobby_page;
+    lobby_page.add_listing(LobbyListing {
+        match_id: args.match_id.clone(),
+        creator: player_signer_account.key(),
+        creator_rating: ctx.accounts.creator_profile.as_ref().map(|profile| profile.rating),
+        betting_token_mint: actual_betting_token_mint_key,
+        bet_amount: args.bet_amount,
+        move_timeout_duration: args.move_timeout_duration,
+        variant: args.variant,
+        rated: args.rated,
+    })?;
+    chess_match_account.lobby_page = lobby_page.page_index;
+
     // 5. Transfer the bet from the player to the match escrow
     let cpi_accounts_transfer = Transfer {
         from: ctx.accounts.player_token_account.to_account_info(),
@@ -141,19 +208,24 @@ pub fn handler(
     };
     let cpi_program_transfer = ctx.accounts.token_program.to_account_info();
     let cpi_ctx_transfer = CpiContext::new(cpi_program_transfer, cpi_accounts_transfer);
-    token::transfer(cpi_ctx_transfer, bet_amount_arg)?;
+    token::transfer(cpi_ctx_transfer, args.bet_amount)?;
 
     // 6. Emit event
     emit!(MatchCreatedEvent {
-        match_id: chess_match_account.match_id.clone(),
+        match_id: chess_match_account.match_id(),
         creator: player_signer_account.key(),
         betting_token_mint: chess_match_account.betting_token_mint, // This is already a Pubkey
-        bet_amount: bet_amount_arg,
-        move_timeout_duration: move_timeout_duration_arg,
-        platform_fee_basis_points: platform_fee_basis_points_arg,
+        bet_amount: args.bet_amount,
+        move_timeout_duration: args.move_timeout_duration,
+        platform_fee_basis_points: args.platform_fee_basis_points,
+        join_deadline: args.join_deadline,
+        takebacks_enabled: args.takebacks_enabled,
+        variant: args.variant,
+        handicap: args.handicap,
+        rated: args.rated,
     });
 
-    msg!("Match created: {}", chess_match_account.match_id);
+    msg!("Match created: {}", chess_match_account.match_id());
     Ok(())
 }
 
@@ -1,10 +1,12 @@
 // src/instructions/join_match.rs
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
 use crate::errors::ChessError;
 use crate::events::*;
 use crate::state::*; // Make sure this brings in ChessMatch, PlayerColor, GameStatus, etc.
+use crate::utils::{recent_slot_hash_seed, seed_to_index};
 
 // Define allowed token mints (these are string literals, ensure they match your actual mint addresses)
 const SEND_TOKEN_MINT_STR: &str = "SENDYLjLBaTgjyfXtPP2aHUt91WhNzX7iUfpThyApht"; // mock-SEND mint
@@ -15,12 +17,12 @@ const WSOL_MINT_STR: &str = "WSiBAnrREwNLdGkDpXuqdKL4fJvAHeJhDfehmFdMdvw";     /
 pub struct JoinMatch<'info> {
     #[account(
         mut,
-        seeds = [b"chess_match", chess_match.match_id.as_bytes()], // Use the match_id from the account itself for seed
-        bump = chess_match.bump,
-        constraint = chess_match.game_status == GameStatus::WaitingForOpponent @ ChessError::MatchAlreadyFullOrActive, // Updated error
-        constraint = chess_match.players[1] == Pubkey::default() @ ChessError::MatchAlreadyFull, 
+        seeds = [b"chess_match", chess_match.load()?.match_id_bytes()], // Use the match_id from the account itself for seed
+        bump = chess_match.load()?.bump,
+        constraint = chess_match.load()?.game_status() == GameStatus::WaitingForOpponent @ ChessError::MatchAlreadyFullOrActive, // Updated error
+        constraint = chess_match.load()?.players[1] == Pubkey::default() @ ChessError::MatchAlreadyFull, 
     )]
-    pub chess_match: Account<'info, ChessMatch>,
+    pub chess_match: AccountLoader<'info, ChessMatch>,
 
     #[account(mut)]
     pub player_two_signer: Signer<'info>, // Renamed from player for clarity
@@ -29,29 +31,40 @@ pub struct JoinMatch<'info> {
         mut,
         constraint = player_token_account.owner == player_two_signer.key() @ ChessError::InvalidOwner,
         // This constraint correctly checks against the mint stored in chess_match
-        constraint = player_token_account.mint == chess_match.betting_token_mint @ ChessError::InvalidMintForJoin, // Specific error
+        constraint = player_token_account.mint == chess_match.load()?.betting_token_mint @ ChessError::InvalidMintForJoin, // Specific error
     )]
     pub player_token_account: Account<'info, TokenAccount>,
 
     #[account(
         mut,
-        seeds = [b"match_escrow", chess_match.match_id.as_bytes()],
-        // The bump for match_escrow_token_account might be different from chess_match.bump
+        seeds = [b"match_escrow", chess_match.load()?.match_id_bytes()],
+        // The bump for match_escrow_token_account might be different from chess_match.load()?.bump
         // It should be fetched or passed if this account was initialized with its own bump.
         // For simplicity, if it's always determinable or if you store it, fine. Otherwise, consider passing it.
         // Let's assume it's derivable or you have a way to get its bump. Often, PDAs used as token authorities
         // don't store their own bump in the token account data itself, the authority is just the PDA key.
         // The seeds constraint above is what matters for identifying the account.
-        bump // Assuming this bump is for the escrow PDA, which might need to be passed or stored if not derivable from chess_match.bump
+        bump // Assuming this bump is for the escrow PDA, which might need to be passed or stored if not derivable from chess_match.load()?.bump
     )]
     pub match_escrow_token_account: Account<'info, TokenAccount>,
 
+    #[account(
+        mut,
+        seeds = [b"lobby", chess_match.load()?.lobby_page.to_le_bytes().as_ref()],
+        bump = lobby_page.bump,
+    )]
+    pub lobby_page: Account<'info, LobbyPage>,
+
+    /// CHECK: SlotHashes sysvar, read raw (it is too large to deserialize) to seed the joiner's share of a Chess960 start position
+    #[account(address = slot_hashes::ID)]
+    pub recent_slot_hashes: UncheckedAccount<'info>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>, // Often needed for account initializations or rent payments by payer
 }
 
 pub fn handler(ctx: Context<JoinMatch>, bet_amount_arg: u64) -> Result<()> {
-    let chess_match = &mut ctx.accounts.chess_match;
+    let chess_match = &mut ctx.accounts.chess_match.load_mut()?;
     let player_two = &ctx.accounts.player_two_signer; // Use the renamed field
     let clock = Clock::get()?;
 
@@ -61,6 +74,9 @@ pub fn handler(ctx: Context<JoinMatch>, bet_amount_arg: u64) -> Result<()> {
         ChessError::CannotJoinOwnMatch // New Error
     );
 
+    // 1b. Stale challenges cannot be joined once their deadline has passed
+    chess_match.check_joinable_at(clock.unix_timestamp)?;
+
     // 2. Validate that the betting_token_mint stored in chess_match is one of the allowed types
     // This step ensures the integrity of the match's configured token.
     let send_mint_pubkey = SEND_TOKEN_MINT_STR.parse::<Pubkey>().map_err(|_| error!(ChessError::InvalidPublicKeyString))?;
@@ -96,13 +112,27 @@ pub fn handler(ctx: Context<JoinMatch>, bet_amount_arg: u64) -> Result<()> {
     let cpi_context_transfer = CpiContext::new(cpi_program_transfer, cpi_accounts_transfer);
     token::transfer(cpi_context_transfer, bet_amount_arg)?;
 
-    // 6. Update chess match state
+    // 6. Update chess match state; the challenge leaves the lobby
+    ctx.accounts.lobby_page.remove_listing(&chess_match.match_id());
     chess_match.players[1] = player_two.key(); // Assign player two
-    chess_match.game_status = GameStatus::Active; // Game is now active
+    chess_match.set_game_status(GameStatus::Active); // Game is now active
     chess_match.bet_amount_player_two = bet_amount_arg;
     chess_match.total_pot = chess_match.bet_amount_player_one
         .checked_add(bet_amount_arg)
         .ok_or(ChessError::MathError)?;
+
+    // Chess960: draw the joiner's share of the start position. The joiner can steer it by timing the
+    // join, but without the creator's committed secret that tells them nothing about the position.
+    // The board stays unset until reveal_start_position; the creator's clock runs from now, so
+    // withholding the reveal loses on time.
+    if chess_match.variant() == Variant::Chess960 {
+        let seed = recent_slot_hash_seed(
+            &ctx.accounts.recent_slot_hashes.to_account_info(),
+            &[chess_match.match_id_bytes(), chess_match.players[0].as_ref(), chess_match.players[1].as_ref()],
+        )?;
+        chess_match.start_position_index = seed_to_index(&seed, speed_chess_core::CHESS960_START_POSITIONS);
+        chess_match.last_move_timestamp = clock.unix_timestamp;
+    }
     
     // When player 2 joins, it's still player 1's (White's) turn.
     // The last_move_timestamp was set when P1 initialized the match, effectively starting P1's clock.
@@ -110,15 +140,16 @@ pub fn handler(ctx: Context<JoinMatch>, bet_amount_arg: u64) -> Result<()> {
     // If you want to reset player 1's clock upon player 2 joining:
     // chess_match.last_move_timestamp = clock.unix_timestamp;
 
-    msg!("Player {} joined match {}. Game is now active.", player_two.key(), chess_match.match_id);
+    msg!("Player {} joined match {}. Game is now active.", player_two.key(), chess_match.match_id());
 
     // 7. Emit PlayerJoinedEvent
     emit!(PlayerJoinedEvent {
-        match_id: chess_match.match_id.clone(),
+        match_id: chess_match.match_id(),
         player_one: chess_match.players[0],
         player_two: chess_match.players[1],
         betting_token_mint: chess_match.betting_token_mint,
         bet_amount_per_player: bet_amount_arg, // Both players bet the same amount
+        variant: chess_match.variant(),
     });
 
     Ok(())
@@ -0,0 +1,257 @@
+// src/instructions/join_queue.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::state::*;
+
+// Define allowed token mints
+const SEND_TOKEN_MINT_STR: &str = "SENDYLjLBaTgjyfXtPP2aHUt91WhNzX7iUfpThyApht"; // mock-SEND mint
+const WSOL_MINT_STR: &str = "WSiBAnrREwNLdGkDpXuqdKL4fJvAHeJhDfehmFdMdvw";     // mock-Wrapped SOL mint
+
+#[derive(Accounts)]
+#[instruction(bet_amount_arg: u64, move_timeout_duration_arg: i64)]
+pub struct JoinQueue<'info> {
+    #[account(
+        init_if_needed,
+        payer = player_signer,
+        space = 8 + MatchQueue::INIT_SPACE,
+        seeds = [
+            b"match_queue",
+            betting_token_mint_account.key().as_ref(),
+            &bet_amount_arg.to_le_bytes(),
+            &move_timeout_duration_arg.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub match_queue: Box<Account<'info, MatchQueue>>,
+
+    #[account(
+        init_if_needed,
+        payer = player_signer,
+        seeds = [b"queue_escrow", match_queue.key().as_ref()],
+        bump,
+        token::mint = betting_token_mint_account,
+        token::authority = match_queue // The queue PDA holds waiting players' stakes
+    )]
+    pub queue_escrow_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The player's PlayerProfile PDA, always passed so a rated player cannot hide their rating
+    /// from opponents' windows; read by PlayerProfile::rating_of, which allows it only to be uncreated.
+    #[account(seeds = [b"player_profile", player_signer.key().as_ref()], bump)]
+    pub player_profile: UncheckedAccount<'info>,
+
+    // The new match accounts: passed only when a compatible opponent is already waiting
+    #[account(
+        init,
+        payer = player_signer,
+        space = 8 + ChessMatch::INIT_SPACE,
+        seeds = [b"chess_match", match_queue.next_match_id().as_bytes()],
+        bump
+    )]
+    pub chess_match: Option<AccountLoader<'info, ChessMatch>>,
+
+    #[account(
+        init,
+        payer = player_signer,
+        space = MoveHistory::space_for(0),
+        seeds = [b"move_history", match_queue.next_match_id().as_bytes()],
+        bump
+    )]
+    pub move_history: Option<Account<'info, MoveHistory>>,
+
+    #[account(
+        init,
+        payer = player_signer,
+        seeds = [b"match_escrow", match_queue.next_match_id().as_bytes()],
+        bump,
+        token::mint = betting_token_mint_account,
+        token::authority = chess_match
+    )]
+    pub match_escrow_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub player_signer: Signer<'info>,
+
+    pub betting_token_mint_account: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = player_token_account.owner == player_signer.key() @ ChessError::InvalidOwner,
+        constraint = player_token_account.mint == betting_token_mint_account.key() @ ChessError::InvalidMint
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<JoinQueue>,
+    bet_amount_arg: u64,
+    move_timeout_duration_arg: i64,
+    rating_window_arg: Option<u16>,
+) -> Result<()> {
+    let match_queue = &mut ctx.accounts.match_queue;
+    let player_key = ctx.accounts.player_signer.key();
+    let mint_key = ctx.accounts.betting_token_mint_account.key();
+    let clock = Clock::get()?;
+
+    // 1. Same betting rules as initialize_match
+    let send_mint_pubkey = SEND_TOKEN_MINT_STR.parse::<Pubkey>().map_err(|_| error!(ChessError::InvalidPublicKeyString))?;
+    let wsol_mint_pubkey = WSOL_MINT_STR.parse::<Pubkey>().map_err(|_| error!(ChessError::InvalidPublicKeyString))?;
+    if mint_key == send_mint_pubkey {
+        require!(bet_amount_arg == 10_000_000, ChessError::InvalidBetAmount); // e.g., 10 SEND
+    } else if mint_key == wsol_mint_pubkey {
+        require!(bet_amount_arg == 100_000_000, ChessError::InvalidBetAmount); // e.g., 0.1 wSOL
+    } else {
+        return err!(ChessError::UnsupportedBettingToken);
+    }
+    MatchQueue::check_move_timeout(move_timeout_duration_arg)?;
+
+    // 2. A freshly created queue records its bucket
+    if match_queue.betting_token_mint == Pubkey::default() {
+        match_queue.betting_token_mint = mint_key;
+        match_queue.bet_amount = bet_amount_arg;
+        match_queue.move_timeout_duration = move_timeout_duration_arg;
+        match_queue.bump = ctx.bumps.match_queue;
+    }
+    require!(
+        !match_queue.entries.iter().any(|entry| entry.player == player_key),
+        ChessError::AlreadyInQueue
+    );
+
+    let entry = QueueEntry {
+        player: player_key,
+        rating: PlayerProfile::rating_of(&ctx.accounts.player_profile)?,
+        rating_window: rating_window_arg,
+        enqueued_at: clock.unix_timestamp,
+    };
+
+    // 3. Nobody compatible is waiting: escrow the stake in the queue and wait
+    let Some(opponent_index) = match_queue.find_opponent(&entry) else {
+        require!(
+            ctx.accounts.chess_match.is_none() && ctx.accounts.move_history.is_none() && ctx.accounts.match_escrow_token_account.is_none(),
+            ChessError::NoQueueOpponent
+        );
+        require!(match_queue.entries.len() < MAX_QUEUE_ENTRIES, ChessError::QueueFull);
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.player_token_account.to_account_info(),
+                    to: ctx.accounts.queue_escrow_token_account.to_account_info(),
+                    authority: ctx.accounts.player_signer.to_account_info(),
+                },
+            ),
+            bet_amount_arg,
+        )?;
+        match_queue.entries.push(entry);
+
+        emit!(QueueJoinedEvent {
+            player: player_key,
+            betting_token_mint: mint_key,
+            bet_amount: bet_amount_arg,
+            move_timeout_duration: move_timeout_duration_arg,
+            rating: entry.rating,
+            rating_window: rating_window_arg,
+        });
+        return Ok(());
+    };
+
+    // 4. Pair with the longest-waiting compatible player, who plays White
+    let (Some(chess_match_loader), Some(move_history), Some(match_escrow_token_account)) = (
+        ctx.accounts.chess_match.as_ref(),
+        ctx.accounts.move_history.as_mut(),
+        ctx.accounts.match_escrow_token_account.as_ref(),
+    ) else {
+        return err!(ChessError::QueueMatchAccountsRequired);
+    };
+    let opponent = match_queue.entries.remove(opponent_index);
+    let chess_match = &mut chess_match_loader.load_init()?;
+
+    chess_match.set_match_id(&match_queue.next_match_id());
+    chess_match.players = [opponent.player, player_key];
+    chess_match.set_variant(Variant::Standard);
+    chess_match.start_position_index = STANDARD_START_POSITION;
+    chess_match.set_handicap(Handicap::None);
+    chess_match.reset_position();
+
+    // White's clock starts after the grace period; make_move never counts the time before it
+    let white_clock_starts_at = clock.unix_timestamp.saturating_add(QUEUE_FIRST_MOVE_GRACE_SECONDS);
+    chess_match.last_move_timestamp = white_clock_starts_at;
+    chess_match.move_timeout_duration = move_timeout_duration_arg;
+    chess_match.set_join_deadline(None);
+    chess_match.set_takebacks_enabled(false);
+    chess_match.set_rated(true); // Queued games meet the rated rules: standard chess, no takebacks
+
+    chess_match.set_game_status(GameStatus::Active);
+    chess_match.set_game_end_reason(None);
+
+    chess_match.betting_token_mint = mint_key;
+    chess_match.bet_amount_player_one = bet_amount_arg;
+    chess_match.bet_amount_player_two = bet_amount_arg;
+    chess_match.total_pot = bet_amount_arg.checked_mul(2).ok_or(ChessError::MathError)?;
+    chess_match.platform_fee_basis_points = QUEUE_PLATFORM_FEE_BASIS_POINTS;
+    chess_match.set_payout_processed(false);
+    chess_match.set_rematch_requested_by(None);
+
+    chess_match.bump = ctx.bumps.chess_match.ok_or(ChessError::QueueMatchAccountsRequired)?;
+
+    move_history.match_id = chess_match.match_id();
+    move_history.bump = ctx.bumps.move_history.ok_or(ChessError::QueueMatchAccountsRequired)?;
+    move_history.moves = Vec::new();
+
+    // 5. Escrow both stakes: the joiner signs for their own, and the queue PDA releases the waiting player's
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.player_token_account.to_account_info(),
+                to: match_escrow_token_account.to_account_info(),
+                authority: ctx.accounts.player_signer.to_account_info(),
+            },
+        ),
+        bet_amount_arg,
+    )?;
+
+    let bet_amount_bytes = bet_amount_arg.to_le_bytes();
+    let move_timeout_bytes = move_timeout_duration_arg.to_le_bytes();
+    let seeds: &[&[u8]] = &[
+        b"match_queue",
+        mint_key.as_ref(),
+        &bet_amount_bytes,
+        &move_timeout_bytes,
+        &[match_queue.bump],
+    ];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.queue_escrow_token_account.to_account_info(),
+                to: match_escrow_token_account.to_account_info(),
+                authority: match_queue.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        bet_amount_arg,
+    )?;
+
+    match_queue.matches_created = match_queue.matches_created.saturating_add(1);
+
+    msg!("Queue paired {} with {}. New match: {}", opponent.player, player_key, chess_match.match_id());
+
+    emit!(QueueMatchedEvent {
+        match_id: chess_match.match_id(),
+        white_player: opponent.player,
+        black_player: player_key,
+        betting_token_mint: mint_key,
+        bet_amount_per_player: bet_amount_arg,
+        move_timeout_duration: move_timeout_duration_arg,
+        white_clock_starts_at,
+    });
+
+    Ok(())
+}
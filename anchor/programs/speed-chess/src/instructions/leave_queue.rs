@@ -0,0 +1,86 @@
+// src/instructions/leave_queue.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct LeaveQueue<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"match_queue",
+            match_queue.betting_token_mint.as_ref(),
+            &match_queue.bet_amount.to_le_bytes(),
+            &match_queue.move_timeout_duration.to_le_bytes(),
+        ],
+        bump = match_queue.bump,
+    )]
+    pub match_queue: Account<'info, MatchQueue>,
+
+    #[account(
+        mut,
+        seeds = [b"queue_escrow", match_queue.key().as_ref()],
+        bump,
+    )]
+    pub queue_escrow_token_account: Account<'info, TokenAccount>,
+
+    pub player_signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = player_token_account.owner == player_signer.key() @ ChessError::InvalidOwner,
+        constraint = player_token_account.mint == match_queue.betting_token_mint @ ChessError::InvalidMint
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<LeaveQueue>) -> Result<()> {
+    let match_queue = &mut ctx.accounts.match_queue;
+    let player_key = ctx.accounts.player_signer.key();
+
+    // 1. Drop the player's entry
+    let index = match_queue
+        .entries
+        .iter()
+        .position(|entry| entry.player == player_key)
+        .ok_or(error!(ChessError::NotInQueue))?;
+    match_queue.entries.remove(index);
+
+    // 2. Return the stake from the queue escrow, signed by the queue PDA
+    let bet_amount_bytes = match_queue.bet_amount.to_le_bytes();
+    let move_timeout_bytes = match_queue.move_timeout_duration.to_le_bytes();
+    let seeds: &[&[u8]] = &[
+        b"match_queue",
+        match_queue.betting_token_mint.as_ref(),
+        &bet_amount_bytes,
+        &move_timeout_bytes,
+        &[match_queue.bump],
+    ];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.queue_escrow_token_account.to_account_info(),
+                to: ctx.accounts.player_token_account.to_account_info(),
+                authority: match_queue.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        match_queue.bet_amount,
+    )?;
+
+    emit!(QueueLeftEvent {
+        player: player_key,
+        betting_token_mint: match_queue.betting_token_mint,
+        bet_amount: match_queue.bet_amount,
+        move_timeout_duration: match_queue.move_timeout_duration,
+    });
+
+    Ok(())
+}
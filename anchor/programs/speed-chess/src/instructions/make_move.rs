@@ -11,38 +11,59 @@ use crate::utils::chess_logic; // Your refactored chess_logic module
 pub struct MakeMove<'info> {
     #[account(
         mut,
-        seeds = [b"chess_match", chess_match.match_id.as_bytes()], // Assumes match_id in ChessMatch state is String
-        bump = chess_match.bump,
+        seeds = [b"chess_match", chess_match.load()?.match_id_bytes()], // Assumes match_id in ChessMatch state is String
+        bump = chess_match.load()?.bump,
     )]
-    pub chess_match: Account<'info, ChessMatch>,
+    pub chess_match: AccountLoader<'info, ChessMatch>,
+
+    #[account(
+        mut,
+        seeds = [b"move_history", chess_match.load()?.match_id_bytes()],
+        bump = move_history.bump,
+        realloc = MoveHistory::space_for(move_history.moves.len() + 1 + chess_match.load()?.premove().is_some() as usize), // Room for this move and a premove reply
+        realloc::payer = player,
+        realloc::zero = false,
+    )]
+    pub move_history: Account<'info, MoveHistory>,
 
     #[account(mut)]
-    pub player: Signer<'info>,
+    pub player: Signer<'info>, // The player's wallet, or their session key together with session_token
+
+    #[account(
+        seeds = [b"session", chess_match.key().as_ref(), player.key().as_ref()],
+        bump = session_token.bump,
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+
+    pub system_program: Program<'info, System>,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
 pub struct MakeMoveArgs {
     pub from_row: u8,
     pub from_col: u8,
     pub to_row: u8,
     pub to_col: u8,
     pub promotion: Option<PieceType>, // Ensure PieceType is correctly imported/namespaced
+    pub drop_piece: Option<PieceType>, // Crazyhouse: drop this pocket piece on (to_row, to_col); from_* and promotion are ignored
 }
 
 pub fn handler(ctx: Context<MakeMove>, args: MakeMoveArgs) -> Result<()> {
-    let chess_match = &mut ctx.accounts.chess_match;
-    let player_key = ctx.accounts.player.key();
     let clock = Clock::get()?;
     let now = clock.unix_timestamp;
+    let player_key = SessionToken::acting_player(
+        ctx.accounts.session_token.as_deref(),
+        &ctx.accounts.player.key(),
+        &ctx.accounts.chess_match.key(),
+        now,
+    )?;
+    let chess_match = &mut ctx.accounts.chess_match.load_mut()?;
 
-    // 1. Ensure game is active
-    require!(
-        chess_match.game_status == GameStatus::Active,
-        ChessError::GameNotActive
-    );
+    // 1. Ensure game is active and its start position settled
+    chess_match.check_accepts_moves()?;
 
     // 2. Determine player key for the current turn and verify signer
-    let expected_player_key_for_turn = if chess_match.current_turn == PlayerColor::White {
+    let expected_player_key_for_turn = if chess_match.current_turn() == PlayerColor::White {
         chess_match.players[0] // Assuming players[0] is White
     } else {
         chess_match.players[1] // Assuming players[1] is Black
@@ -53,21 +74,22 @@ pub fn handler(ctx: Context<MakeMove>, args: MakeMoveArgs) -> Result<()> {
         ChessError::NotYourTurn
     );
 
-    let player_color_making_move = chess_match.current_turn; // Color of the player making the move
+    let player_color_making_move = chess_match.current_turn(); // Color of the player making the move
+    let seconds_spent = now.saturating_sub(chess_match.last_move_timestamp).max(0);
 
-    // 3. Check move timeout (if move_timeout_duration is set > 0)
+    // 3. Check move timeout (if move_timeout_duration is set > 0; time odds shorten the creator's)
     if chess_match.move_timeout_duration > 0 {
-        if now.saturating_sub(chess_match.last_move_timestamp) > chess_match.move_timeout_duration {
-            chess_match.game_status = match player_color_making_move {
+        if now.saturating_sub(chess_match.last_move_timestamp) > chess_match.move_timeout_for(player_color_making_move) {
+            chess_match.set_game_status(match player_color_making_move {
                 PlayerColor::White => GameStatus::BlackWins,
                 PlayerColor::Black => GameStatus::WhiteWins,
-            };
-            chess_match.game_end_reason = Some(GameEndReason::Timeout);
+            });
+            chess_match.set_game_end_reason(Some(GameEndReason::Timeout));
             chess_match.last_move_timestamp = now; // Update timestamp for game end
 
             emit!(GameEndedEvent {
-                match_id: chess_match.match_id.clone(), // Assuming match_id in ChessMatch state is String
-                status: chess_match.game_status,
+                match_id: chess_match.match_id(), // Assuming match_id in ChessMatch state is String
+                status: chess_match.game_status(),
                 winner: Some(player_color_making_move.opponent()),
                 reason: GameEndReason::Timeout,
             });
@@ -78,19 +100,82 @@ pub fn handler(ctx: Context<MakeMove>, args: MakeMoveArgs) -> Result<()> {
 
     // 4. Call the core chess logic (Bounds checks are handled inside chess_logic)
     msg!("Calling validate_and_apply_move for player: {:?}", player_color_making_move);
-    msg!("Move: ({},{}) to ({},{}) promo: {:?}", args.from_row, args.from_col, args.to_row, args.to_col, args.promotion);
-    
+    msg!("Move: ({},{}) to ({},{}) promo: {:?} drop: {:?}", args.from_row, args.from_col, args.to_row, args.to_col, args.promotion, args.drop_piece);
+
+    // A drop goes through chess_logic and the history like any other ply: from == to, with the dropped piece in the promotion slot
+    let (from_row, from_col, promotion) = ply_source(&args);
     // CORRECTED CALL: Pass the mutable chess_match account directly
     let move_result = chess_logic::validate_and_apply_move(
         chess_match, // Pass the whole mutable ChessMatch state
-        args.from_row,
-        args.from_col,
+        from_row,
+        from_col,
         args.to_row,
         args.to_col,
         player_color_making_move, // Pass the color of the current player
-        args.promotion,
+        promotion,
     )?;
     msg!("Move result: {:?}", move_result);
+    // Making a move implicitly declines any pending takeback request
+    chess_match.set_takeback_requested_by(None);
+
+    let move_time_seconds = u32::try_from(seconds_spent).unwrap_or(u32::MAX);
+    let move_history = &mut ctx.accounts.move_history;
+    record_ply(chess_match, move_history, player_key, player_color_making_move, &args, move_result, move_time_seconds, now);
+
+    // 7. Answer with the opponent's premove, if it is still legal; it costs no clock time.
+    // An illegal premove is dropped without failing this move.
+    if move_result != MoveResult::Normal {
+        chess_match.set_premove(None);
+    } else if let Some((premove, premove_result)) = chess_logic::apply_premove(chess_match) {
+        msg!("Premove result: {:?}", premove_result);
+        let premove_args = MakeMoveArgs {
+            from_row: premove.from_row,
+            from_col: premove.from_col,
+            to_row: premove.to_row,
+            to_col: premove.to_col,
+            promotion: premove.promotion,
+            drop_piece: premove.drop_piece,
+        };
+        let premover = chess_match.players[premove.color as usize];
+        record_ply(chess_match, move_history, premover, premove.color, &premove_args, premove_result, 0, now);
+    }
+
+    Ok(())
+}
+
+// (from_row, from_col, promotion) as chess_logic and the history take them; a drop is from == to.
+pub fn ply_source(args: &MakeMoveArgs) -> (u8, u8, Option<PieceType>) {
+    match args.drop_piece {
+        Some(piece_type) => (args.to_row, args.to_col, Some(piece_type)),
+        None => (args.from_row, args.from_col, args.promotion),
+    }
+}
+
+// Records a ply already applied by chess_logic: appends it to the history, ends the game if it
+// decided it, and emits MoveMadeEvent.
+#[allow(clippy::too_many_arguments)]
+pub fn record_ply(
+    chess_match: &mut ChessMatch,
+    move_history: &mut MoveHistory,
+    player_key: Pubkey,
+    player_color_making_move: PlayerColor,
+    args: &MakeMoveArgs,
+    move_result: MoveResult,
+    move_time_seconds: u32,
+    now: i64,
+) {
+    let (from_row, from_col, promotion) = ply_source(args);
+
+    // Append the move to the on-chain history
+    move_history.push(&RecordedMove {
+        from_row,
+        from_col,
+        to_row: args.to_row,
+        to_col: args.to_col,
+        promotion,
+        clock_seconds: move_time_seconds,
+    });
+    chess_match.plies_played += 1;
     // chess_match is now updated by chess_logic::validate_and_apply_move for fields like:
     // board, castling_rights, en_passant_target, halfmove_clock, fullmove_number, current_turn.
 
@@ -99,73 +184,98 @@ pub fn handler(ctx: Context<MakeMove>, args: MakeMoveArgs) -> Result<()> {
         MoveResult::Normal => {
             // current_turn was already updated by chess_logic.
             // Update current_player_idx to match the new current_turn.
-            chess_match.current_player_idx = if chess_match.current_turn == PlayerColor::White { 0 } else { 1 };
+            chess_match.current_player_idx = if chess_match.current_turn() == PlayerColor::White { 0 } else { 1 };
             chess_match.last_move_timestamp = now;
         }
         MoveResult::Checkmate => {
             // player_color_making_move is the winner
-            chess_match.game_status = if player_color_making_move == PlayerColor::White {
+            chess_match.set_game_status(if player_color_making_move == PlayerColor::White {
                 GameStatus::WhiteWins
             } else {
                 GameStatus::BlackWins
-            };
-            chess_match.game_end_reason = Some(GameEndReason::Checkmate);
+            });
+            chess_match.set_game_end_reason(Some(GameEndReason::Checkmate));
             chess_match.last_move_timestamp = now; // Record time of game-ending move
 
             emit!(GameEndedEvent {
-                match_id: chess_match.match_id.clone(), // Assuming String
-                status: chess_match.game_status,
+                match_id: chess_match.match_id(), // Assuming String
+                status: chess_match.game_status(),
                 winner: Some(player_color_making_move),
                 reason: GameEndReason::Checkmate,
             });
         }
         MoveResult::Stalemate => {
-            chess_match.game_status = GameStatus::Draw;
+            chess_match.set_game_status(GameStatus::Draw);
             // chess_logic already updated halfmove_clock. Check it here for reason.
             if chess_match.halfmove_clock >= 100 {
-                 chess_match.game_end_reason = Some(GameEndReason::FiftyMoveRule);
+                 chess_match.set_game_end_reason(Some(GameEndReason::FiftyMoveRule));
             } else {
-                 chess_match.game_end_reason = Some(GameEndReason::Stalemate);
+                 chess_match.set_game_end_reason(Some(GameEndReason::Stalemate));
             }
             chess_match.last_move_timestamp = now; // Record time of game-ending move
 
             emit!(GameEndedEvent {
-                match_id: chess_match.match_id.clone(), // Assuming String
-                status: chess_match.game_status,
+                match_id: chess_match.match_id(), // Assuming String
+                status: chess_match.game_status(),
                 winner: None, // No winner in a draw
-                reason: chess_match.game_end_reason.unwrap(), // We just set it
+                reason: chess_match.game_end_reason().unwrap(), // We just set it
+            });
+        }
+        MoveResult::VariantWin { winner, reason } => {
+            chess_match.set_game_status(match winner {
+                PlayerColor::White => GameStatus::WhiteWins,
+                PlayerColor::Black => GameStatus::BlackWins,
+            });
+            chess_match.set_game_end_reason(Some(reason));
+            chess_match.last_move_timestamp = now; // Record time of game-ending move
+
+            emit!(GameEndedEvent {
+                match_id: chess_match.match_id(),
+                status: chess_match.game_status(),
+                winner: Some(winner),
+                reason,
             });
         }
     }
 
-    // 6. Emit MoveMadeEvent
-    let from_sq = format!("{}{}", (b'a' + args.from_col) as char, args.from_row + 1);
+    // 6. Emit MoveMadeEvent (squares from ply_source: a drop's from_* are ignored and may hold anything)
+    let from_sq = format!("{}{}", (b'a' + from_col) as char, from_row + 1);
     let to_sq = format!("{}{}", (b'a' + args.to_col) as char, args.to_row + 1);
     let promo_char_str = match args.promotion { // Renamed for clarity
         Some(PieceType::Queen) => "q", Some(PieceType::Rook) => "r",
         Some(PieceType::Bishop) => "b", Some(PieceType::Knight) => "n",
         _ => "",
     };
-    let algebraic_move_string = format!("{}{}{}", from_sq, to_sq, promo_char_str);
+    let algebraic_move_string = match args.drop_piece {
+        // UCI drop notation, e.g. "N@f3"
+        Some(piece_type) => {
+            let letter = match piece_type {
+                PieceType::Pawn => "P", PieceType::Knight => "N", PieceType::Bishop => "B",
+                PieceType::Rook => "R", PieceType::Queen => "Q", PieceType::King => "K",
+            };
+            format!("{}@{}", letter, to_sq)
+        }
+        None => format!("{}{}{}", from_sq, to_sq, promo_char_str),
+    };
 
     emit!(MoveMadeEvent {
-        match_id: chess_match.match_id.clone(), // Assuming String
+        match_id: chess_match.match_id(), // Assuming String
         player: player_key,
         player_color: player_color_making_move, // The color that just moved
         algebraic_move: algebraic_move_string,
-        from_row: args.from_row,
-        from_col: args.from_col,
+        from_row,
+        from_col,
         to_row: args.to_row,
         to_col: args.to_col,
-        promotion_piece: args.promotion,
+        promotion_piece: if args.drop_piece.is_some() { None } else { args.promotion },
+        drop_piece: args.drop_piece,
+        move_time_seconds,
         board_fen: String::from(""), // Placeholder
         // Check status for the *next* player (whose turn it is now, after chess_logic updated current_turn)
-        is_check: if chess_match.game_status == GameStatus::Active { 
-            chess_logic::is_king_in_check(&chess_match.board, chess_match.current_turn)
+        is_check: if chess_match.game_status() == GameStatus::Active { 
+            chess_logic::is_in_check(chess_match, chess_match.current_turn())
         } else { false },
         is_checkmate: move_result == MoveResult::Checkmate,
         is_stalemate: move_result == MoveResult::Stalemate,
     });
-
-    Ok(())
 }
@@ -0,0 +1,94 @@
+// src/instructions/make_moves.rs
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::instructions::make_move::{ply_source, record_ply, MakeMoveArgs};
+use crate::state::*;
+use crate::utils::{chess_logic, check_moves_signed, MAX_BATCHED_MOVES};
+
+#[derive(Accounts)]
+#[instruction(moves_arg: Vec<MakeMoveArgs>)]
+pub struct MakeMoves<'info> {
+    #[account(
+        mut,
+        seeds = [b"chess_match", chess_match.load()?.match_id_bytes()],
+        bump = chess_match.load()?.bump,
+    )]
+    pub chess_match: AccountLoader<'info, ChessMatch>,
+
+    #[account(
+        mut,
+        seeds = [b"move_history", chess_match.load()?.match_id_bytes()],
+        bump = move_history.bump,
+        realloc = MoveHistory::space_for(move_history.moves.len() + moves_arg.len()),
+        realloc::payer = payer,
+        realloc::zero = false,
+    )]
+    pub move_history: Account<'info, MoveHistory>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>, // Anyone may submit a list both players signed
+
+    /// CHECK: Instructions sysvar, searched for the players' ed25519 signatures over the move list
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Plays a list of moves both players signed (see utils::moves_message) with ed25519 program
+// instructions in the same transaction. For correspondence games and staked over-the-board games;
+// there is no per-move clock, so each ply is recorded with zero seconds and no timeout applies.
+pub fn handler(ctx: Context<MakeMoves>, moves_arg: Vec<MakeMoveArgs>) -> Result<()> {
+    let chess_match = &mut ctx.accounts.chess_match.load_mut()?;
+    let move_history = &mut ctx.accounts.move_history;
+    let now = Clock::get()?.unix_timestamp;
+
+    chess_match.check_accepts_moves()?;
+    require!(
+        !moves_arg.is_empty() && moves_arg.len() <= MAX_BATCHED_MOVES,
+        ChessError::InvalidMoveBatch
+    );
+
+    // 1. Both players signed exactly this list, starting at the current ply of this match
+    check_moves_signed(&ctx.accounts.instructions, &ctx.accounts.chess_match.key(), chess_match, &moves_arg)?;
+    let first_ply = u32::try_from(move_history.moves.len()).map_err(|_| error!(ChessError::MathError))?;
+
+    // 2. Play the moves in order; any illegal move fails the whole list, and moves after the
+    // game-ending one are ignored
+    chess_match.set_takeback_requested_by(None);
+    chess_match.set_premove(None);
+    let mut moves_applied: u16 = 0;
+    for args in &moves_arg {
+        let color = chess_match.current_turn();
+        let (from_row, from_col, promotion) = ply_source(args);
+        let move_result = chess_logic::validate_and_apply_move(
+            chess_match,
+            from_row,
+            from_col,
+            args.to_row,
+            args.to_col,
+            color,
+            promotion,
+        )?;
+        let player = chess_match.players[color as usize];
+        record_ply(chess_match, move_history, player, color, args, move_result, 0, now);
+        moves_applied += 1;
+        if chess_match.game_status() != GameStatus::Active {
+            break;
+        }
+    }
+
+    msg!("Applied {} of {} signed moves in match {}", moves_applied, moves_arg.len(), chess_match.match_id());
+
+    emit!(MovesBatchAppliedEvent {
+        match_id: chess_match.match_id(),
+        first_ply,
+        moves_submitted: moves_arg.len() as u16,
+        moves_applied,
+    });
+
+    Ok(())
+}
@@ -1,13 +1,67 @@
 pub mod initialize_match;
 pub mod join_match;
+pub mod reveal_start_position;
 pub mod make_move;
+pub mod make_moves;
 pub mod resign_game;
 pub mod claim_timeout_win;
 pub mod process_match_settlement;
+pub mod expire_match;
+pub mod request_rematch;
+pub mod accept_rematch;
+pub mod request_takeback;
+pub mod accept_takeback;
+pub mod join_queue;
+pub mod leave_queue;
+pub mod create_lobby_page;
+pub mod cancel_match;
+pub mod create_tournament;
+pub mod register_tournament;
+pub mod start_tournament;
+pub mod create_tournament_match;
+pub mod record_tournament_result;
+pub mod close_tournament_match;
+pub mod distribute_tournament_prizes;
+pub mod create_arena_match;
+pub mod berserk;
+pub mod finish_arena;
+pub mod delegate_match;
+pub mod commit_match;
+pub mod undelegate_match;
+pub mod create_session;
+pub mod revoke_session;
+pub mod set_premove;
 
 pub use initialize_match::*;
 pub use join_match::*;
+pub use reveal_start_position::*;
 pub use make_move::*;
+pub use make_moves::*;
 pub use resign_game::*;
 pub use claim_timeout_win::*;
 pub use process_match_settlement::*;
+pub use expire_match::*;
+pub use request_rematch::*;
+pub use accept_rematch::*;
+pub use request_takeback::*;
+pub use accept_takeback::*;
+pub use join_queue::*;
+pub use leave_queue::*;
+pub use create_lobby_page::*;
+pub use cancel_match::*;
+pub use create_tournament::*;
+pub use register_tournament::*;
+pub use start_tournament::*;
+pub use create_tournament_match::*;
+pub use record_tournament_result::*;
+pub use close_tournament_match::*;
+pub use distribute_tournament_prizes::*;
+pub use create_arena_match::*;
+pub use berserk::*;
+pub use finish_arena::*;
+pub use delegate_match::*;
+pub use commit_match::*;
+pub use undelegate_match::*;
+pub use create_session::*;
+pub use revoke_session::*;
+pub use set_premove::*;
@@ -3,29 +3,32 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount}; // TokenAccount for type, Token for Program
 
 use crate::errors::ChessError;
-use crate::state::{ChessMatch, GameStatus, PlayerColor}; // PlayerColor for winner check
-use crate::utils::payout_logic; // Import your payout functions
+use crate::events::RatingChangedEvent;
+use crate::state::{ChessMatch, GameStatus, GlickoRating, PlayerProfile, TimeControl};
+use crate::utils::{self, payout_logic}; // Import your payout functions
 
 #[derive(Accounts)]
 pub struct ProcessMatchSettlement<'info> {
     #[account(
         mut, // Mutable because we set payout_processed = true
-        seeds = [b"chess_match", chess_match.match_id.as_bytes()], // Assuming match_id is String
-        bump = chess_match.bump,
+        seeds = [b"chess_match", chess_match.load()?.match_id_bytes()], // Assuming match_id is String
+        bump = chess_match.load()?.bump,
         constraint = (
-            chess_match.game_status == GameStatus::WhiteWins ||
-            chess_match.game_status == GameStatus::BlackWins ||
-            chess_match.game_status == GameStatus::Draw
+            chess_match.load()?.game_status() == GameStatus::WhiteWins ||
+            chess_match.load()?.game_status() == GameStatus::BlackWins ||
+            chess_match.load()?.game_status() == GameStatus::Draw
         ) @ ChessError::GameNotConcluded,
-        constraint = !chess_match.payout_processed @ ChessError::PayoutAlreadyProcessed,
+        constraint = !chess_match.load()?.payout_processed() @ ChessError::PayoutAlreadyProcessed,
+        constraint = chess_match.load()?.tournament().is_none() @ ChessError::TournamentMatch,
+        constraint = !chess_match.load()?.delegated() @ ChessError::MatchDelegated,
     )]
-    pub chess_match: Account<'info, ChessMatch>,
+    pub chess_match: AccountLoader<'info, ChessMatch>,
 
     // The PDA escrow token account holding the bets.
     // We need both its Account<TokenAccount> for data (like owner) and its AccountInfo for CPI.
     #[account(
         mut,
-        seeds = [b"match_escrow", chess_match.match_id.as_bytes()], // Assuming match_id is String
+        seeds = [b"match_escrow", chess_match.load()?.match_id_bytes()], // Assuming match_id is String
         bump, // Anchor derives and verifies this bump
     )]
     pub match_escrow_token_account: Account<'info, TokenAccount>,
@@ -33,33 +36,55 @@ pub struct ProcessMatchSettlement<'info> {
     // Player 1's token account (ATA)
     #[account(
         mut,
-        constraint = player_one_ata.owner == chess_match.players[0] @ ChessError::PlayerTokenAccountMismatch,
-        constraint = player_one_ata.mint == chess_match.betting_token_mint @ ChessError::PlayerTokenAccountMismatch,
+        constraint = player_one_ata.owner == chess_match.load()?.players[0] @ ChessError::PlayerTokenAccountMismatch,
+        constraint = player_one_ata.mint == chess_match.load()?.betting_token_mint @ ChessError::PlayerTokenAccountMismatch,
     )]
     pub player_one_ata: Account<'info, TokenAccount>, // Player 1's Associated Token Account
 
     // Player 2's token account (ATA)
     #[account(
         mut,
-        constraint = player_two_ata.owner == chess_match.players[1] @ ChessError::PlayerTokenAccountMismatch,
-        constraint = player_two_ata.mint == chess_match.betting_token_mint @ ChessError::PlayerTokenAccountMismatch,
+        constraint = player_two_ata.owner == chess_match.load()?.players[1] @ ChessError::PlayerTokenAccountMismatch,
+        constraint = player_two_ata.mint == chess_match.load()?.betting_token_mint @ ChessError::PlayerTokenAccountMismatch,
     )]
     pub player_two_ata: Account<'info, TokenAccount>, // Player 2's Associated Token Account
     
     // Platform's fee collection account
     #[account(
         mut,
-        constraint = platform_fee_ata.mint == chess_match.betting_token_mint @ ChessError::PlatformTokenAccountError,
+        constraint = platform_fee_ata.mint == chess_match.load()?.betting_token_mint @ ChessError::PlatformTokenAccountError,
         // Owner of platform_fee_ata is not constrained here, assumed to be a known, correct address.
     )]
     pub platform_fee_ata: Account<'info, TokenAccount>, // Platform's Associated Token Account
 
+    // Rating profiles of both players, created here on their first rated game; omitted for casual matches
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", chess_match.load()?.players[0].as_ref()],
+        bump,
+    )]
+    pub white_profile: Option<Account<'info, PlayerProfile>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", chess_match.load()?.players[1].as_ref()],
+        bump,
+    )]
+    pub black_profile: Option<Account<'info, PlayerProfile>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>, // Anyone may crank settlement; pays rent for new profiles
+
     pub token_program: Program<'info, Token>,
-    // system_program: Program<'info, System>, // Not directly needed for this instruction
+    pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<ProcessMatchSettlement>) -> Result<()> {
-    let chess_match = &mut ctx.accounts.chess_match; // Note: mutable reference
+    let chess_match = &mut ctx.accounts.chess_match.load_mut()?; // Note: mutable reference
     
     // These are Account<TokenAccount> types from the context
     let match_escrow_data = &ctx.accounts.match_escrow_token_account;
@@ -76,15 +101,15 @@ pub fn handler(ctx: Context<ProcessMatchSettlement>) -> Result<()> {
     let token_program_info = &ctx.accounts.token_program;
     let current_program_id = ctx.program_id; // program_id is implicitly available via ctx.program_id
     
-    msg!("Processing settlement for match: {}", chess_match.match_id);
-    msg!("Game status: {:?}", chess_match.game_status);
+    msg!("Processing settlement for match: {}", chess_match.match_id());
+    msg!("Game status: {:?}", chess_match.game_status());
     msg!("Total pot: {}", chess_match.total_pot);
 
-    match chess_match.game_status {
+    match chess_match.game_status() {
         GameStatus::WhiteWins => {
             msg!("White wins. Payout to player 1: {}", chess_match.players[0]);
             payout_logic::process_payout(
-                chess_match,                     // &Account<'info, ChessMatch>
+                chess_match,                     // &ChessMatch
                 &match_escrow_info,              // &AccountInfo<'info>
                 &player_one_ata_info,            // &AccountInfo<'info> for winner
                 &platform_fee_ata_info,          // &AccountInfo<'info>
@@ -144,8 +169,101 @@ pub fn handler(ctx: Context<ProcessMatchSettlement>) -> Result<()> {
     }
 
     // Mark payout as processed to prevent double payouts
-    chess_match.payout_processed = true;
+    chess_match.set_payout_processed(true);
+
+    // Casual matches leave ratings untouched
+    if let Some((white_profile, black_profile)) =
+        chess_match.profiles_to_rate(ctx.accounts.white_profile.as_deref_mut(), ctx.accounts.black_profile.as_deref_mut())?
+    {
+        let now = Clock::get()?.unix_timestamp;
+        update_ratings(
+            chess_match,
+            now,
+            white_profile,
+            ctx.bumps.white_profile.unwrap_or_default(),
+            black_profile,
+            ctx.bumps.black_profile.unwrap_or_default(),
+        );
+    }
 
-    msg!("Settlement processed successfully for match: {}", chess_match.match_id);
+    msg!("Settlement processed successfully for match: {}", chess_match.match_id());
     Ok(())
 }
+
+// Applies the Elo update and the Glicko-2 update in the match's time-control pool
+// to both profiles, then emits RatingChangedEvent.
+fn update_ratings(
+    chess_match: &ChessMatch,
+    now: i64,
+    white_profile: &mut PlayerProfile,
+    white_bump: u8,
+    black_profile: &mut PlayerProfile,
+    black_bump: u8,
+) {
+    for (profile, player, bump) in [
+        (&mut *white_profile, chess_match.players[0], white_bump),
+        (&mut *black_profile, chess_match.players[1], black_bump),
+    ] {
+        if profile.player == Pubkey::default() { // Freshly created by init_if_needed
+            profile.player = player;
+            profile.bump = bump;
+            profile.rating = utils::DEFAULT_RATING;
+            profile.glicko = [GlickoRating::default(); TimeControl::COUNT];
+        }
+    }
+
+    let white_score = match chess_match.game_status() {
+        GameStatus::WhiteWins => utils::WIN_SCORE_MILLI,
+        GameStatus::BlackWins => utils::LOSS_SCORE_MILLI,
+        _ => utils::DRAW_SCORE_MILLI,
+    };
+    let black_score = utils::WIN_SCORE_MILLI - white_score;
+
+    // Both updates use the pre-game ratings
+    let white_before = white_profile.rating;
+    let black_before = black_profile.rating;
+    let white_after = utils::elo_update(white_before, white_profile.games_played, black_before, white_score);
+    let black_after = utils::elo_update(black_before, black_profile.games_played, white_before, black_score);
+    white_profile.record_game(white_after, white_score);
+    black_profile.record_game(black_after, black_score);
+
+    let time_control = TimeControl::from_move_timeout(chess_match.move_timeout_duration);
+    let pool = time_control.index();
+    let white_glicko_before = with_inactivity(&white_profile.glicko[pool], now);
+    let black_glicko_before = with_inactivity(&black_profile.glicko[pool], now);
+    let white_glicko_after = finish_glicko_game(utils::glicko_update(&white_glicko_before, &black_glicko_before, white_score), now);
+    let black_glicko_after = finish_glicko_game(utils::glicko_update(&black_glicko_before, &white_glicko_before, black_score), now);
+    white_profile.glicko[pool] = white_glicko_after;
+    black_profile.glicko[pool] = black_glicko_after;
+
+    msg!("Ratings: white {} -> {}, black {} -> {}", white_before, white_after, black_before, black_after);
+    emit!(RatingChangedEvent {
+        match_id: chess_match.match_id(),
+        white: chess_match.players[0],
+        black: chess_match.players[1],
+        white_rating_before: white_before,
+        white_rating_after: white_after,
+        black_rating_before: black_before,
+        black_rating_after: black_after,
+        time_control,
+        white_glicko_before,
+        white_glicko_after,
+        black_glicko_before,
+        black_glicko_after,
+    });
+}
+
+// Grows the deviation for the time since the player's last game in this pool.
+fn with_inactivity(glicko: &GlickoRating, now: i64) -> GlickoRating {
+    if glicko.last_game_timestamp == 0 {
+        return *glicko;
+    }
+    GlickoRating {
+        deviation: utils::deviation_after_inactivity(glicko.deviation, glicko.volatility, now - glicko.last_game_timestamp),
+        ..*glicko
+    }
+}
+
+fn finish_glicko_game(glicko: GlickoRating, now: i64) -> GlickoRating {
+    GlickoRating { games_played: glicko.games_played.saturating_add(1), last_game_timestamp: now, ..glicko }
+}
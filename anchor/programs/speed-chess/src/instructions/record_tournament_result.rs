@@ -0,0 +1,240 @@
+// src/instructions/record_tournament_result.rs
+use anchor_lang::prelude::*;
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::state::*;
+use crate::utils::{begin_swiss_round, record_arena_game, record_swiss_game, swiss_standings, winners_by_bye};
+
+#[derive(Accounts)]
+pub struct RecordTournamentResult<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.as_bytes()],
+        bump = tournament.bump,
+        constraint = tournament.status == TournamentStatus::InProgress @ ChessError::TournamentNotInProgress,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    #[account(
+        mut,
+        seeds = [b"chess_match", chess_match.load()?.match_id_bytes()],
+        bump = chess_match.load()?.bump,
+        constraint = chess_match.load()?.tournament() == Some(tournament.key()) @ ChessError::InvalidTournamentPairing,
+        constraint = (
+            chess_match.load()?.game_status() == GameStatus::WhiteWins ||
+            chess_match.load()?.game_status() == GameStatus::BlackWins ||
+            chess_match.load()?.game_status() == GameStatus::Draw
+        ) @ ChessError::GameNotConcluded,
+    )]
+    pub chess_match: AccountLoader<'info, ChessMatch>,
+
+    // Anyone may report a finished game; the result is read from the match itself
+    pub caller: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RecordTournamentResult>) -> Result<()> {
+    match ctx.accounts.tournament.format {
+        TournamentFormat::Swiss { .. } => return record_swiss_result(ctx),
+        TournamentFormat::Arena { .. } => return record_arena_result(ctx),
+        TournamentFormat::Knockout => {}
+    }
+    let tournament = &mut ctx.accounts.tournament;
+    let chess_match = &mut ctx.accounts.chess_match.load_mut()?;
+    chess_match.set_result_recorded(true); // The game can be closed from now on
+
+    // 1. Find the game's pairing in the current round (its Armageddon replay once the first game was drawn)
+    let round = tournament.current_round;
+    let pairing = (0..tournament.round_winners.len() as u8)
+        .find(|&pairing| tournament.pairing_match_id(pairing) == chess_match.match_id())
+        .ok_or(error!(ChessError::InvalidTournamentPairing))?;
+    require!(
+        tournament.round_winners[pairing as usize] == Pubkey::default(),
+        ChessError::TournamentResultAlreadyRecorded
+    );
+
+    // 2. A knockout needs a winner. A drawn game is replayed as Armageddon, where Black has less
+    // time and so takes the draw.
+    let armageddon = tournament.is_armageddon(pairing);
+    let (winner, loser) = match chess_match.game_status() {
+        GameStatus::WhiteWins => (chess_match.players[0], chess_match.players[1]),
+        GameStatus::Draw if !armageddon => {
+            tournament.set_armageddon(pairing);
+            let armageddon_match_id = tournament.armageddon_match_id(round, pairing);
+            msg!("Knockout game {} drawn; replayed as Armageddon {}", chess_match.match_id(), armageddon_match_id);
+            emit!(KnockoutArmageddonEvent {
+                tournament_id: tournament.tournament_id.clone(),
+                round,
+                pairing,
+                drawn_match_id: chess_match.match_id(),
+                armageddon_match_id,
+            });
+            return Ok(());
+        }
+        _ => (chess_match.players[1], chess_match.players[0]),
+    };
+    tournament.round_winners[pairing as usize] = winner;
+    if let Some(entry) = tournament.player_mut(&loser) {
+        entry.eliminated_in_round = round;
+    }
+
+    emit!(TournamentResultRecordedEvent {
+        tournament_id: tournament.tournament_id.clone(),
+        round,
+        match_id: chess_match.match_id(),
+        winner,
+        loser,
+    });
+
+    // 3. Once every pairing is decided, advance the winners or finish the tournament
+    if tournament.round_winners.iter().any(|player| *player == Pubkey::default()) {
+        return Ok(());
+    }
+    if tournament.round_winners.len() == 1 {
+        tournament.status = TournamentStatus::Completed;
+        msg!("Tournament {} won by {}", tournament.tournament_id, winner);
+        emit!(TournamentCompletedEvent {
+            tournament_id: tournament.tournament_id.clone(),
+            champion: winner,
+        });
+        return Ok(());
+    }
+    tournament.bracket = std::mem::take(&mut tournament.round_winners);
+    tournament.round_winners = winners_by_bye(&tournament.bracket);
+    tournament.armageddon_pairings = 0;
+    tournament.current_round += 1;
+
+    emit!(TournamentRoundStartedEvent {
+        tournament_id: tournament.tournament_id.clone(),
+        round: tournament.current_round,
+        bracket: tournament.bracket.clone(),
+    });
+
+    Ok(())
+}
+
+fn record_swiss_result(ctx: Context<RecordTournamentResult>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    let chess_match = &mut ctx.accounts.chess_match.load_mut()?;
+    chess_match.set_result_recorded(true); // The game can be closed from now on
+
+    // 1. Find the game's pairing in the current round; each player's history holds one entry per recorded round
+    let round = tournament.current_round;
+    (0..(tournament.bracket.len() / 2) as u8)
+        .find(|&pairing| tournament.round_match_id(round, pairing) == chess_match.match_id())
+        .ok_or(error!(ChessError::InvalidTournamentPairing))?;
+    let [white_player, black_player] = chess_match.players;
+    let white = tournament.player_index(&white_player).ok_or(error!(ChessError::InvalidTournamentPairing))?;
+    let black = tournament.player_index(&black_player).ok_or(error!(ChessError::InvalidTournamentPairing))?;
+    require!(
+        tournament.players[white].history.len() < usize::from(round),
+        ChessError::TournamentResultAlreadyRecorded
+    );
+
+    // 2. Score the game for both players, in half points
+    let (white_result, black_result) = match chess_match.game_status() {
+        GameStatus::WhiteWins => (2, 0),
+        GameStatus::BlackWins => (0, 2),
+        _ => (1, 1),
+    };
+    record_swiss_game(
+        &mut tournament.players[white],
+        SwissGame { opponent: black as u8, color: Some(PlayerColor::White), result: white_result },
+    );
+    record_swiss_game(
+        &mut tournament.players[black],
+        SwissGame { opponent: white as u8, color: Some(PlayerColor::Black), result: black_result },
+    );
+    tournament.results_pending = tournament.results_pending.saturating_sub(1);
+
+    emit!(SwissResultRecordedEvent {
+        tournament_id: tournament.tournament_id.clone(),
+        round,
+        match_id: chess_match.match_id(),
+        white_player,
+        black_player,
+        result: chess_match.game_status(),
+    });
+
+    // 3. Once the round is complete, publish the standings and pair the next round or finish
+    if tournament.results_pending > 0 {
+        return Ok(());
+    }
+    let standings = swiss_standings(&tournament.players);
+    let leader = standings[0].player;
+    emit!(SwissStandingsEvent {
+        tournament_id: tournament.tournament_id.clone(),
+        round,
+        standings,
+    });
+    if round == tournament.total_rounds {
+        tournament.status = TournamentStatus::Completed;
+        msg!("Tournament {} won by {}", tournament.tournament_id, leader);
+        emit!(TournamentCompletedEvent {
+            tournament_id: tournament.tournament_id.clone(),
+            champion: leader,
+        });
+        return Ok(());
+    }
+    tournament.current_round += 1;
+    begin_swiss_round(tournament);
+
+    emit!(TournamentRoundStartedEvent {
+        tournament_id: tournament.tournament_id.clone(),
+        round: tournament.current_round,
+        bracket: tournament.bracket.clone(),
+    });
+
+    Ok(())
+}
+
+fn record_arena_result(ctx: Context<RecordTournamentResult>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    let chess_match = &mut ctx.accounts.chess_match.load_mut()?;
+    chess_match.set_result_recorded(true); // The game can be closed from now on
+
+    // 1. The game must be the one both players are still marked as playing
+    let [white_player, black_player] = chess_match.players;
+    let white = tournament.player_index(&white_player).ok_or(error!(ChessError::InvalidTournamentPairing))?;
+    let black = tournament.player_index(&black_player).ok_or(error!(ChessError::InvalidTournamentPairing))?;
+    let game = tournament.players[white].arena.current_game.ok_or(error!(ChessError::TournamentResultAlreadyRecorded))?;
+    require!(
+        tournament.arena_match_id(game) == chess_match.match_id(),
+        ChessError::TournamentResultAlreadyRecorded
+    );
+
+    // 2. Score both sides (streaks and berserk bonuses included) and free them for their next pairing
+    let (white_result, black_result) = match chess_match.game_status() {
+        GameStatus::WhiteWins => (2, 0),
+        GameStatus::BlackWins => (0, 2),
+        _ => (1, 1),
+    };
+    let white_points = record_arena_game(
+        &mut tournament.players[white],
+        black as u8,
+        PlayerColor::White,
+        white_result,
+        chess_match.berserk(PlayerColor::White),
+        chess_match.fullmove_number,
+    );
+    let black_points = record_arena_game(
+        &mut tournament.players[black],
+        white as u8,
+        PlayerColor::Black,
+        black_result,
+        chess_match.berserk(PlayerColor::Black),
+        chess_match.fullmove_number,
+    );
+
+    emit!(ArenaResultRecordedEvent {
+        tournament_id: tournament.tournament_id.clone(),
+        match_id: chess_match.match_id(),
+        white_player,
+        black_player,
+        result: chess_match.game_status(),
+        white_points,
+        black_points,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+// src/instructions/register_tournament.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct RegisterTournament<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.as_bytes()],
+        bump = tournament.bump,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament_vault", tournament.tournament_id.as_bytes()],
+        bump,
+    )]
+    pub tournament_vault: Account<'info, TokenAccount>,
+
+    // Passed when the player has a profile, so they are seeded by rating
+    #[account(
+        seeds = [b"player_profile", player_signer.key().as_ref()],
+        bump = player_profile.bump,
+    )]
+    pub player_profile: Option<Account<'info, PlayerProfile>>,
+
+    pub player_signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = player_token_account.owner == player_signer.key() @ ChessError::InvalidOwner,
+        constraint = player_token_account.mint == tournament.betting_token_mint @ ChessError::InvalidMint
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<RegisterTournament>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    let player_key = ctx.accounts.player_signer.key();
+
+    // Arenas also take late entries while they run
+    let registration_open = match tournament.format {
+        TournamentFormat::Arena { ends_at, .. } => {
+            tournament.status == TournamentStatus::Registration ||
+            (tournament.status == TournamentStatus::InProgress && Clock::get()?.unix_timestamp < ends_at)
+        }
+        _ => tournament.status == TournamentStatus::Registration,
+    };
+    require!(registration_open, ChessError::TournamentNotInRegistration);
+    require!(tournament.players.len() < MAX_TOURNAMENT_PLAYERS, ChessError::TournamentFull);
+    require!(
+        !tournament.players.iter().any(|entry| entry.player == player_key),
+        ChessError::AlreadyRegistered
+    );
+
+    // The entry fee goes straight into the prize pool
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.player_token_account.to_account_info(),
+                to: ctx.accounts.tournament_vault.to_account_info(),
+                authority: ctx.accounts.player_signer.to_account_info(),
+            },
+        ),
+        tournament.entry_fee,
+    )?;
+    tournament.prize_pool = tournament.prize_pool.checked_add(tournament.entry_fee).ok_or(ChessError::MathError)?;
+
+    let rating = ctx.accounts.player_profile.as_ref().map(|profile| profile.rating);
+    tournament.players.push(TournamentPlayer {
+        player: player_key,
+        rating,
+        eliminated_in_round: 0,
+        score: 0,
+        history: Vec::new(),
+        arena: ArenaPlayer::default(),
+    });
+
+    emit!(TournamentRegisteredEvent {
+        tournament_id: tournament.tournament_id.clone(),
+        player: player_key,
+        rating,
+        prize_pool: tournament.prize_pool,
+    });
+
+    Ok(())
+}
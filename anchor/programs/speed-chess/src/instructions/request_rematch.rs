@@ -0,0 +1,71 @@
+// src/instructions/request_rematch.rs
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Approve, Token, TokenAccount};
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct RequestRematch<'info> {
+    #[account(
+        mut,
+        seeds = [b"chess_match", chess_match.load()?.match_id_bytes()],
+        bump = chess_match.load()?.bump,
+        constraint = (
+            chess_match.load()?.game_status() == GameStatus::WhiteWins ||
+            chess_match.load()?.game_status() == GameStatus::BlackWins ||
+            chess_match.load()?.game_status() == GameStatus::Draw
+        ) @ ChessError::GameNotConcluded,
+        constraint = chess_match.load()?.tournament().is_none() @ ChessError::TournamentMatch,
+    )]
+    pub chess_match: AccountLoader<'info, ChessMatch>,
+
+    #[account(mut)]
+    pub requester_signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = requester_token_account.owner == requester_signer.key() @ ChessError::InvalidOwner,
+        constraint = requester_token_account.mint == chess_match.load()?.betting_token_mint @ ChessError::InvalidMint,
+    )]
+    pub requester_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<RequestRematch>) -> Result<()> {
+    let requester_key = ctx.accounts.requester_signer.key();
+    let stake = {
+        let chess_match = ctx.accounts.chess_match.load()?;
+
+        // 1. Only the two players of the concluded match can offer a rematch, one offer at a time
+        chess_match.check_rematch_offer(requester_key)?;
+        chess_match.bet_amount_player_one
+    }; // Released before the CPI below, which reads the match account
+
+    // 2. Approve the concluded match PDA as delegate for the rematch stake.
+    // The stake stays in the requester's wallet until the opponent accepts, at which point
+    // accept_rematch moves both stakes into the new escrow in the same transaction.
+    let cpi_accounts_approve = Approve {
+        to: ctx.accounts.requester_token_account.to_account_info(),
+        delegate: ctx.accounts.chess_match.to_account_info(),
+        authority: ctx.accounts.requester_signer.to_account_info(),
+    };
+    let cpi_ctx_approve = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts_approve);
+    token::approve(cpi_ctx_approve, stake)?;
+
+    // 3. Record the offer
+    let chess_match = &mut ctx.accounts.chess_match.load_mut()?;
+    chess_match.set_rematch_requested_by(Some(requester_key));
+
+    msg!("Player {} requested a rematch of match {}", requester_key, chess_match.match_id());
+
+    emit!(RematchRequestedEvent {
+        match_id: chess_match.match_id(),
+        requester: requester_key,
+        rematch_match_id: chess_match.rematch_match_id(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+// src/instructions/request_takeback.rs
+use anchor_lang::prelude::*;
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct RequestTakeback<'info> {
+    #[account(
+        mut,
+        seeds = [b"chess_match", chess_match.load()?.match_id_bytes()],
+        bump = chess_match.load()?.bump,
+    )]
+    pub chess_match: AccountLoader<'info, ChessMatch>,
+
+    #[account(mut)]
+    pub player_signer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RequestTakeback>) -> Result<()> {
+    let chess_match = &mut ctx.accounts.chess_match.load_mut()?;
+    let player_key = ctx.accounts.player_signer.key();
+
+    // 1. Takebacks must have been enabled when the match was created
+    require!(chess_match.game_status() == GameStatus::Active, ChessError::GameNotActive);
+    require!(chess_match.takebacks_enabled(), ChessError::TakebacksDisabled);
+
+    // 2. Identify the requester
+    let requester_color = if player_key == chess_match.players[0] {
+        PlayerColor::White
+    } else if player_key == chess_match.players[1] {
+        PlayerColor::Black
+    } else {
+        return err!(ChessError::NotAPlayer);
+    };
+
+    // 3. The requester's last move (and any reply to it) must still be in the undo history
+    require!(chess_match.can_take_back(requester_color), ChessError::NoMoveToTakeBack);
+
+    chess_match.set_takeback_requested_by(Some(requester_color));
+
+    msg!("Player {:?} ({:?}) requested a takeback in match {}", player_key, requester_color, chess_match.match_id());
+
+    emit!(TakebackRequestedEvent {
+        match_id: chess_match.match_id(),
+        requester: player_key,
+        requester_color,
+    });
+
+    Ok(())
+}
@@ -9,24 +9,35 @@ use crate::state::*;  // Ensure ChessMatch, GameStatus, PlayerColor, GameEndReas
 pub struct ResignGame<'info> {
     #[account(
         mut,
-        seeds = [b"chess_match", chess_match.match_id.as_bytes()], // Assumes chess_match.match_id is String
-        bump = chess_match.bump, // Use the stored bump for the PDA
+        seeds = [b"chess_match", chess_match.load()?.match_id_bytes()], // Assumes chess_match.match_id is String
+        bump = chess_match.load()?.bump, // Use the stored bump for the PDA
     )]
-    pub chess_match: Account<'info, ChessMatch>,
+    pub chess_match: AccountLoader<'info, ChessMatch>,
 
     #[account(mut)] // Signer is mutable due to transaction fees
-    pub player_signer: Signer<'info>, // Renamed from 'player' for clarity
+    pub player_signer: Signer<'info>, // The player's wallet, or their session key together with session_token
+
+    #[account(
+        seeds = [b"session", chess_match.key().as_ref(), player_signer.key().as_ref()],
+        bump = session_token.bump,
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
 }
 
 pub fn handler(ctx: Context<ResignGame>) -> Result<()> {
-    let chess_match = &mut ctx.accounts.chess_match;
-    let player_key = ctx.accounts.player_signer.key();
     let clock = Clock::get()?;
+    let player_key = SessionToken::acting_player(
+        ctx.accounts.session_token.as_deref(),
+        &ctx.accounts.player_signer.key(),
+        &ctx.accounts.chess_match.key(),
+        clock.unix_timestamp,
+    )?;
+    let chess_match = &mut ctx.accounts.chess_match.load_mut()?;
 
     // 1. Ensure game is active to allow resignation.
     // Resigning from "WaitingForOpponent" could be an "abort_match" instruction with different logic (e.g., refund P1).
     require!(
-        chess_match.game_status == GameStatus::Active,
+        chess_match.game_status() == GameStatus::Active,
         ChessError::GameNotActive // Or a more specific "CannotResignNonActiveGame"
     );
 
@@ -50,11 +61,11 @@ pub fn handler(ctx: Context<ResignGame>) -> Result<()> {
     }
 
     // 3. Update game status - opponent wins due to resignation.
-    chess_match.game_status = match winner_color {
+    chess_match.set_game_status(match winner_color {
         PlayerColor::White => GameStatus::WhiteWins,
         PlayerColor::Black => GameStatus::BlackWins,
-    };
-    chess_match.game_end_reason = Some(GameEndReason::Resignation);
+    });
+    chess_match.set_game_end_reason(Some(GameEndReason::Resignation));
     chess_match.last_move_timestamp = clock.unix_timestamp; // Record time of game end
 
     msg!("Player {:?} ({:?}) resigned. Player {:?} wins.", 
@@ -65,8 +76,8 @@ pub fn handler(ctx: Context<ResignGame>) -> Result<()> {
 
     // 4. Emit GameEndedEvent.
     emit!(GameEndedEvent {
-        match_id: chess_match.match_id.clone(), // Assuming match_id in ChessMatch is String
-        status: chess_match.game_status,
+        match_id: chess_match.match_id(), // Assuming match_id in ChessMatch is String
+        status: chess_match.game_status(),
         winner: Some(winner_color),
         reason: GameEndReason::Resignation,
     });
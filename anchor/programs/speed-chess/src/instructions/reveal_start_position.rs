@@ -0,0 +1,32 @@
+// src/instructions/reveal_start_position.rs
+use anchor_lang::prelude::*;
+
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct RevealStartPosition<'info> {
+    #[account(
+        mut,
+        seeds = [b"chess_match", chess_match.load()?.match_id_bytes()],
+        bump = chess_match.load()?.bump,
+    )]
+    pub chess_match: AccountLoader<'info, ChessMatch>,
+
+    // Usually the creator; knowing the secret is what counts
+    pub caller: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RevealStartPosition>, secret_arg: [u8; 32]) -> Result<()> {
+    let chess_match = &mut ctx.accounts.chess_match.load_mut()?;
+    chess_match.reveal_start_position(&secret_arg)?;
+
+    msg!("Chess960 start position: {}", chess_match.start_position_index);
+
+    emit!(StartPositionRevealedEvent {
+        match_id: chess_match.match_id(),
+        start_position_index: chess_match.start_position_index,
+    });
+
+    Ok(())
+}
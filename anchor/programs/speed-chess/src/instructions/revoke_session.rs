@@ -0,0 +1,34 @@
+// src/instructions/revoke_session.rs
+use anchor_lang::prelude::*;
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct RevokeSession<'info> {
+    #[account(
+        mut,
+        seeds = [b"session", session_token.chess_match.as_ref(), session_token.session_signer.as_ref()],
+        bump = session_token.bump,
+        constraint = session_token.player == player_signer.key() @ ChessError::NotAPlayer,
+        close = player_signer,
+    )]
+    pub session_token: Account<'info, SessionToken>,
+
+    #[account(mut)]
+    pub player_signer: Signer<'info>,
+}
+
+// Ends a session early (a lost device, or the game is over) and returns the rent to the player.
+pub fn handler(ctx: Context<RevokeSession>) -> Result<()> {
+    let session_token = &ctx.accounts.session_token;
+
+    emit!(SessionRevokedEvent {
+        chess_match: session_token.chess_match,
+        player: session_token.player,
+        session_signer: session_token.session_signer,
+    });
+
+    Ok(())
+}
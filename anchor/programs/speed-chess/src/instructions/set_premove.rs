@@ -0,0 +1,77 @@
+// src/instructions/set_premove.rs
+use anchor_lang::prelude::*;
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::instructions::MakeMoveArgs;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetPremove<'info> {
+    #[account(
+        mut,
+        seeds = [b"chess_match", chess_match.load()?.match_id_bytes()],
+        bump = chess_match.load()?.bump,
+    )]
+    pub chess_match: AccountLoader<'info, ChessMatch>,
+
+    pub player_signer: Signer<'info>, // The player's wallet, or their session key together with session_token
+
+    #[account(
+        seeds = [b"session", chess_match.key().as_ref(), player_signer.key().as_ref()],
+        bump = session_token.bump,
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+}
+
+// Queues (or, with None, clears) the one premove of the player who is not on move. It replaces any
+// earlier premove and is only checked for legality once the opponent has moved.
+pub fn handler(ctx: Context<SetPremove>, premove_arg: Option<MakeMoveArgs>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let player_key = SessionToken::acting_player(
+        ctx.accounts.session_token.as_deref(),
+        &ctx.accounts.player_signer.key(),
+        &ctx.accounts.chess_match.key(),
+        now,
+    )?;
+    let chess_match = &mut ctx.accounts.chess_match.load_mut()?;
+
+    chess_match.check_accepts_moves()?;
+    let player_color = if player_key == chess_match.players[0] {
+        PlayerColor::White
+    } else if player_key == chess_match.players[1] {
+        PlayerColor::Black
+    } else {
+        return err!(ChessError::NotAPlayer);
+    };
+    require!(player_color != chess_match.current_turn(), ChessError::PremoveOnYourTurn);
+
+    let premove = match premove_arg {
+        Some(args) => {
+            require!(
+                [args.from_row, args.from_col, args.to_row, args.to_col].iter().all(|&coord| coord < 8),
+                ChessError::InvalidMoveOutOfBounds
+            );
+            Some(Premove {
+                color: player_color,
+                from_row: args.from_row,
+                from_col: args.from_col,
+                to_row: args.to_row,
+                to_col: args.to_col,
+                promotion: args.promotion,
+                drop_piece: args.drop_piece,
+            })
+        }
+        None => None,
+    };
+    chess_match.set_premove(premove);
+
+    emit!(PremoveSetEvent {
+        match_id: chess_match.match_id(),
+        player: player_key,
+        player_color,
+        cleared: premove.is_none(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+// src/instructions/start_tournament.rs
+use anchor_lang::prelude::*;
+
+use crate::errors::ChessError;
+use crate::events::*;
+use crate::state::*;
+use crate::utils::{begin_swiss_round, first_round_bracket, sort_by_seed, winners_by_bye};
+
+#[derive(Accounts)]
+pub struct StartTournament<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament.tournament_id.as_bytes()],
+        bump = tournament.bump,
+        has_one = organizer @ ChessError::NotTournamentOrganizer,
+        constraint = tournament.status == TournamentStatus::Registration @ ChessError::TournamentNotInRegistration,
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    pub organizer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<StartTournament>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    require!(tournament.players.len() >= 2, ChessError::NotEnoughTournamentPlayers);
+
+    // Seed by rating and build the first round: top seeds get any knockout byes,
+    // while a Swiss round pairs the top half against the bottom half
+    sort_by_seed(&mut tournament.players);
+    match tournament.format {
+        TournamentFormat::Knockout => {
+            let seeded: Vec<Pubkey> = tournament.players.iter().map(|entry| entry.player).collect();
+            tournament.bracket = first_round_bracket(&seeded);
+            tournament.round_winners = winners_by_bye(&tournament.bracket);
+            tournament.total_rounds = tournament.bracket.len().trailing_zeros() as u8;
+        }
+        TournamentFormat::Swiss { rounds } => {
+            begin_swiss_round(tournament);
+            tournament.total_rounds = rounds;
+        }
+        // Arena games are created one at a time by create_arena_match once the window opens
+        TournamentFormat::Arena { starts_at, .. } => {
+            require!(Clock::get()?.unix_timestamp >= starts_at, ChessError::ArenaNotStarted);
+        }
+    }
+    tournament.current_round = 1;
+    tournament.status = TournamentStatus::InProgress;
+
+    msg!("Tournament {} started with {} players", tournament.tournament_id, tournament.players.len());
+
+    emit!(TournamentRoundStartedEvent {
+        tournament_id: tournament.tournament_id.clone(),
+        round: tournament.current_round,
+        bracket: tournament.bracket.clone(),
+    });
+
+    Ok(())
+}
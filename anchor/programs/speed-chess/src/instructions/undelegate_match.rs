@@ -0,0 +1,50 @@
+// src/instructions/undelegate_match.rs
+use anchor_lang::prelude::*;
+
+use crate::errors::ChessError;
+use crate::state::*;
+use crate::utils::{restore_undelegated_account, DELEGATION_PROGRAM_ID};
+
+use super::commit_match::{commit, CommitMatch};
+
+// Runs inside the ephemeral rollup: commits the final state and returns the match accounts to the
+// base layer, where process_match_settlement can then pay out. Players may undelegate at any time;
+// anyone may once the game is over.
+pub fn handler(ctx: Context<CommitMatch>) -> Result<()> {
+    {
+        let chess_match = ctx.accounts.chess_match.load()?;
+        require!(
+            chess_match.game_status() != GameStatus::Active || chess_match.players.contains(&ctx.accounts.payer.key()),
+            ChessError::NotAPlayer
+        );
+    }
+    commit(ctx, true)
+}
+
+#[derive(Accounts)]
+pub struct RestoreUndelegatedAccount<'info> {
+    /// CHECK: The account being returned; re-created under this program by the handler.
+    #[account(mut)]
+    pub delegated_account: AccountInfo<'info>,
+
+    /// CHECK: Delegation program buffer holding the committed data; must sign and be owned by it.
+    #[account(owner = DELEGATION_PROGRAM_ID)]
+    pub buffer: AccountInfo<'info>,
+
+    /// CHECK: Funds any rent top-up for the re-created account.
+    #[account(mut)]
+    pub payer: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Called by the delegation program (not by clients) to finish undelegating a match account.
+pub fn restore_handler(ctx: Context<RestoreUndelegatedAccount>, account_seeds: Vec<Vec<u8>>) -> Result<()> {
+    restore_undelegated_account(
+        &ctx.accounts.delegated_account,
+        &ctx.accounts.buffer,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program.to_account_info(),
+        &account_seeds,
+    )
+}
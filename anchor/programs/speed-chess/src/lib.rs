@@ -7,9 +7,13 @@ pub mod events;
 pub mod instructions; // Assumes src/instructions/mod.rs exists and re-exports all instruction modules
 pub mod state;
 pub mod utils;     // Assumes src/utils/mod.rs exists and re-exports chess_logic and payout_logic
+#[cfg(feature = "pgn")]
+pub mod pgn;       // Off-chain PGN rendering, enable with the "pgn" feature
 
 // Make all items from instructions module available (structs like InitializeMatch, MakeMoveArgs, etc.)
 use instructions::*; 
+use state::TournamentFormat; // Instruction argument types
+use utils::UNDELEGATE_CALLBACK_DISCRIMINATOR;
 
 
 
@@ -20,22 +24,83 @@ pub mod speed_chess {
     use super::*; // Brings in InitializeMatch, JoinMatch, MakeMove, ResignGame, ClaimTimeoutWin, ProcessMatchSettlement, MakeMoveArgs from instructions::*
 
     // Initialize a new chess match with betting enabled
-    pub fn initialize_match(
-        ctx: Context<InitializeMatch>,
-        match_id_arg: String,          // Changed from match_id
-        bet_amount_arg: u64,           // Changed from bet_amount
-        move_timeout_duration_arg: i64,// Added
-        platform_fee_basis_points_arg: u16, // Added
+    pub fn initialize_match(ctx: Context<InitializeMatch>, args: InitializeMatchArgs) -> Result<()> {
+        instructions::initialize_match::handler(ctx, args)
+    }
+
+    // Add the next page to the open-challenge lobby
+    pub fn create_lobby_page(ctx: Context<CreateLobbyPage>, page_index_arg: u32) -> Result<()> {
+        instructions::create_lobby_page::handler(ctx, page_index_arg)
+    }
+
+    // Open a knockout or Swiss tournament; the payout table gives each place's share in basis points
+    pub fn create_tournament(
+        ctx: Context<CreateTournament>,
+        tournament_id_arg: String,
+        entry_fee_arg: u64,
+        move_timeout_duration_arg: i64,
+        platform_fee_basis_points_arg: u16, // Taken once from the prize pool
+        payout_table_arg: Vec<u16>,         // [champion, finalist, each semifinalist, each quarterfinalist] or Swiss places
+        format_arg: TournamentFormat,
     ) -> Result<()> {
-        instructions::initialize_match::handler(
-            ctx, 
-            match_id_arg, 
-            bet_amount_arg, 
-            move_timeout_duration_arg, 
-            platform_fee_basis_points_arg
+        instructions::create_tournament::handler(
+            ctx,
+            tournament_id_arg,
+            entry_fee_arg,
+            move_timeout_duration_arg,
+            platform_fee_basis_points_arg,
+            payout_table_arg,
+            format_arg,
         )
     }
 
+    // Register for a tournament, paying the entry fee into its prize pool
+    pub fn register_tournament(ctx: Context<RegisterTournament>) -> Result<()> {
+        instructions::register_tournament::handler(ctx)
+    }
+
+    // Close registration, seed the players and build the first round (organizer only)
+    pub fn start_tournament(ctx: Context<StartTournament>) -> Result<()> {
+        instructions::start_tournament::handler(ctx)
+    }
+
+    // Create the game for one pairing of the tournament's current round
+    pub fn create_tournament_match(ctx: Context<CreateTournamentMatch>, pairing_arg: u8) -> Result<()> {
+        instructions::create_tournament_match::handler(ctx, pairing_arg)
+    }
+
+    // Read a concluded tournament game: advance its winner, or score it in a Swiss round
+    pub fn record_tournament_result(ctx: Context<RecordTournamentResult>) -> Result<()> {
+        instructions::record_tournament_result::handler(ctx)
+    }
+
+    // Close a tournament game once its result is recorded, refunding the rent to whoever created it
+    pub fn close_tournament_match(ctx: Context<CloseTournamentMatch>) -> Result<()> {
+        instructions::close_tournament_match::handler(ctx)
+    }
+
+    // Pair the two best-placed idle players of a running arena in a new game
+    pub fn create_arena_match(ctx: Context<CreateArenaMatch>) -> Result<()> {
+        instructions::create_arena_match::handler(ctx)
+    }
+
+    // Halve your own move timeout in an arena game, before your first move, for a bonus point on a win
+    pub fn berserk(ctx: Context<Berserk>) -> Result<()> {
+        instructions::berserk::handler(ctx)
+    }
+
+    // Close an arena once its window has passed and publish the final standings
+    pub fn finish_arena(ctx: Context<FinishArena>) -> Result<()> {
+        instructions::finish_arena::handler(ctx)
+    }
+
+    // Pay out the prize pool of a finished tournament
+    pub fn distribute_tournament_prizes<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeTournamentPrizes<'info>>,
+    ) -> Result<()> {
+        instructions::distribute_tournament_prizes::handler(ctx)
+    }
+
     // Allow a player to join an existing match
     pub fn join_match(
         ctx: Context<JoinMatch>, 
@@ -44,6 +109,46 @@ pub mod speed_chess {
         instructions::join_match::handler(ctx, bet_amount_arg)
     }
 
+    // Chess960: open the creator's committed secret after the join, fixing the start position
+    pub fn reveal_start_position(ctx: Context<RevealStartPosition>, secret_arg: [u8; 32]) -> Result<()> {
+        instructions::reveal_start_position::handler(ctx, secret_arg)
+    }
+
+    // Wait in the (mint, stake, move timeout) queue, or get paired with the longest-waiting compatible player
+    pub fn join_queue(
+        ctx: Context<JoinQueue>,
+        bet_amount_arg: u64,
+        move_timeout_duration_arg: i64,
+        rating_window_arg: Option<u16>, // Largest accepted Elo gap when both players have profiles
+    ) -> Result<()> {
+        instructions::join_queue::handler(ctx, bet_amount_arg, move_timeout_duration_arg, rating_window_arg)
+    }
+
+    // Leave a match queue, returning the escrowed stake
+    pub fn leave_queue(ctx: Context<LeaveQueue>) -> Result<()> {
+        instructions::leave_queue::handler(ctx)
+    }
+
+    // Expire an open challenge whose join deadline has passed, refunding the creator
+    pub fn expire_match(ctx: Context<ExpireMatch>) -> Result<()> {
+        instructions::expire_match::handler(ctx)
+    }
+
+    // Withdraw an open challenge nobody has joined yet, refunding the creator
+    pub fn cancel_match(ctx: Context<CancelMatch>) -> Result<()> {
+        instructions::cancel_match::handler(ctx)
+    }
+
+    // Offer a color-swapped rematch at the same stake and time control after a game has concluded
+    pub fn request_rematch(ctx: Context<RequestRematch>) -> Result<()> {
+        instructions::request_rematch::handler(ctx)
+    }
+
+    // Accept a rematch offer, creating the new match with both stakes escrowed
+    pub fn accept_rematch(ctx: Context<AcceptRematch>) -> Result<()> {
+        instructions::accept_rematch::handler(ctx)
+    }
+
     // Make a chess move
     // The MakeMoveArgs struct should be defined in make_move.rs and made public,
     // then re-exported by src/instructions/mod.rs to be usable here via instructions::*
@@ -54,6 +159,26 @@ pub mod speed_chess {
         instructions::make_move::handler(ctx, args)
     }
 
+    // Play a list of moves both players signed with ed25519 instructions in the same transaction
+    pub fn make_moves(ctx: Context<MakeMoves>, moves_arg: Vec<MakeMoveArgs>) -> Result<()> {
+        instructions::make_moves::handler(ctx, moves_arg)
+    }
+
+    // Queue a move to be played the moment the opponent moves (None clears it); zero clock time if still legal
+    pub fn set_premove(ctx: Context<SetPremove>, premove_arg: Option<MakeMoveArgs>) -> Result<()> {
+        instructions::set_premove::handler(ctx, premove_arg)
+    }
+
+    // Ask the opponent to take back your last move (matches created with takebacks enabled)
+    pub fn request_takeback(ctx: Context<RequestTakeback>) -> Result<()> {
+        instructions::request_takeback::handler(ctx)
+    }
+
+    // Accept the opponent's takeback request, rewinding the board
+    pub fn accept_takeback(ctx: Context<AcceptTakeback>) -> Result<()> {
+        instructions::accept_takeback::handler(ctx)
+    }
+
     // Resign from the game, opponent wins
     pub fn resign_game(ctx: Context<ResignGame>) -> Result<()> {
         instructions::resign_game::handler(ctx)
@@ -64,6 +189,42 @@ pub mod speed_chess {
         instructions::claim_timeout_win::handler(ctx)
     }
 
+    // Move an active match and its move history into a MagicBlock ephemeral rollup (either player)
+    pub fn delegate_match(ctx: Context<DelegateMatch>, match_id_arg: String, validator_arg: Option<Pubkey>) -> Result<()> {
+        instructions::delegate_match::handler(ctx, match_id_arg, validator_arg)
+    }
+
+    // Inside the rollup: checkpoint the match state on the base layer
+    pub fn commit_match(ctx: Context<CommitMatch>) -> Result<()> {
+        instructions::commit_match::handler(ctx)
+    }
+
+    // Inside the rollup: commit the final state and return the match to the base layer for settlement
+    pub fn undelegate_match(ctx: Context<CommitMatch>) -> Result<()> {
+        instructions::undelegate_match::handler(ctx)
+    }
+
+    // Delegation program callback that re-creates an undelegated match account
+    #[instruction(discriminator = &UNDELEGATE_CALLBACK_DISCRIMINATOR)]
+    pub fn restore_undelegated_account(ctx: Context<RestoreUndelegatedAccount>, account_seeds: Vec<Vec<u8>>) -> Result<()> {
+        instructions::undelegate_match::restore_handler(ctx, account_seeds)
+    }
+
+    // Authorize a session key to make moves, premove and resign for the signing player in one match
+    pub fn create_session(
+        ctx: Context<CreateSession>,
+        session_signer_arg: Pubkey,
+        expires_at_arg: i64, // Unix timestamp, at most 24 hours ahead
+        lamports_arg: u64,   // Sent to the session key for its transaction fees
+    ) -> Result<()> {
+        instructions::create_session::handler(ctx, session_signer_arg, expires_at_arg, lamports_arg)
+    }
+
+    // Close a session key's token before it expires
+    pub fn revoke_session(ctx: Context<RevokeSession>) -> Result<()> {
+        instructions::revoke_session::handler(ctx)
+    }
+
     // Process the settlement of a concluded match (payouts/refunds)
     pub fn process_match_settlement(ctx: Context<ProcessMatchSettlement>) -> Result<()> {
         instructions::process_match_settlement::handler(ctx)
@@ -0,0 +1,236 @@
+// src/pgn/export.rs
+use anchor_lang::prelude::*;
+
+use crate::events::{GameEndedEvent, MoveMadeEvent};
+use crate::state::{
+    ChessMatch, GameEndReason, GameStatus, Handicap, PlayerColor, RecordedMove, Variant, STANDARD_START_POSITION,
+};
+use super::{apply_move_with_san, fen_from_chess_match, PgnMove};
+
+const MAX_LINE_LEN: usize = 79; // PGN export format keeps movetext lines under 80 characters
+
+// Everything needed to render a finished (or ongoing) match as PGN.
+#[derive(Clone, Debug)]
+pub struct PgnGame {
+    pub match_id: String,
+    pub event: String,
+    pub site: String,
+    pub date: Option<String>,  // "YYYY.MM.DD"; unknown dates render as "????.??.??"
+    pub round: Option<String>,
+    pub white: Pubkey,
+    pub black: Pubkey,
+    pub move_timeout_duration: i64,
+    pub variant: Variant,
+    pub start_position_index: u16, // Chess960 start position (PlayerJoinedEvent / ChessMatch)
+    pub handicap: Handicap,        // MatchCreatedEvent / ChessMatch
+    pub handicap_color: PlayerColor,
+    pub status: GameStatus,
+    pub end_reason: Option<GameEndReason>,
+    pub moves: Vec<PgnMove>,
+}
+
+impl PgnGame {
+    fn new(match_id: String, white: Pubkey, black: Pubkey, move_timeout_duration: i64) -> Self {
+        Self {
+            match_id,
+            event: "Speed Chess Match".to_string(),
+            site: "Solana".to_string(),
+            date: None,
+            round: None,
+            white,
+            black,
+            move_timeout_duration,
+            variant: Variant::Standard,
+            start_position_index: STANDARD_START_POSITION,
+            handicap: Handicap::None,
+            handicap_color: PlayerColor::White,
+            status: GameStatus::Active,
+            end_reason: None,
+            moves: Vec::new(),
+        }
+    }
+
+    // Initial position of the game: the standard setup (minus any odds pieces) or its Chess960 start position.
+    fn start_state(&self) -> ChessMatch {
+        let mut game_state = ChessMatch::default();
+        game_state.start_position_index = self.start_position_index;
+        game_state.set_variant(self.variant);
+        game_state.set_handicap(self.handicap);
+        game_state.set_handicap_color(self.handicap_color);
+        game_state.reset_position();
+        game_state
+    }
+
+    // Builds a game from the match account and its decoded MoveHistory.
+    pub fn from_match(chess_match: &ChessMatch, moves: &[RecordedMove]) -> Self {
+        let mut game = Self::new(
+            chess_match.match_id(),
+            chess_match.players[0],
+            chess_match.players[1],
+            chess_match.move_timeout_duration,
+        );
+        game.variant = chess_match.variant();
+        game.start_position_index = chess_match.start_position_index;
+        game.handicap = chess_match.handicap();
+        game.handicap_color = chess_match.handicap_color();
+        game.status = chess_match.game_status();
+        game.end_reason = chess_match.game_end_reason();
+        game.moves = moves.iter().copied().map(PgnMove::from).collect();
+        game
+    }
+
+    // Builds a game from the program's event log: every MoveMadeEvent in order plus the final GameEndedEvent.
+    // Chess960 games also need variant and start_position_index set from the StartPositionRevealedEvent.
+    // Odds games also need handicap and handicap_color (White, the creator) from the MatchCreatedEvent.
+    pub fn from_events(
+        white: Pubkey,
+        black: Pubkey,
+        move_timeout_duration: i64,
+        moves: &[MoveMadeEvent],
+        game_ended: Option<&GameEndedEvent>,
+    ) -> Self {
+        let match_id = game_ended
+            .map(|event| event.match_id.clone())
+            .or_else(|| moves.first().map(|event| event.match_id.clone()))
+            .unwrap_or_default();
+        let mut game = Self::new(match_id, white, black, move_timeout_duration);
+        if let Some(event) = game_ended {
+            game.status = event.status;
+            game.end_reason = Some(event.reason);
+        }
+        game.moves = moves.iter().map(PgnMove::from).collect();
+        game
+    }
+
+    pub fn result_tag(&self) -> &'static str {
+        match self.status {
+            GameStatus::WhiteWins => "1-0",
+            GameStatus::BlackWins => "0-1",
+            GameStatus::Draw => "1/2-1/2",
+            GameStatus::WaitingForOpponent | GameStatus::Active => "*",
+        }
+    }
+
+    // Per-move timeouts are a one-move time control period: "1/<seconds>".
+    pub fn time_control_tag(&self) -> String {
+        if self.move_timeout_duration > 0 {
+            format!("1/{}", self.move_timeout_duration)
+        } else {
+            "-".to_string()
+        }
+    }
+
+    fn termination_tag(&self) -> Option<&'static str> {
+        match self.end_reason? {
+            GameEndReason::Timeout => Some("time forfeit"),
+            GameEndReason::Checkmate
+            | GameEndReason::Stalemate
+            | GameEndReason::Resignation
+            | GameEndReason::FiftyMoveRule
+            | GameEndReason::KingOfTheHill
+            | GameEndReason::ThreeCheck
+            | GameEndReason::KingExploded
+            | GameEndReason::AllPiecesLost
+            | GameEndReason::StalemateWin => Some("normal"),
+        }
+    }
+
+    fn tag_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut tags = vec![
+            ("Event", self.event.clone()),
+            ("Site", self.site.clone()),
+            ("Date", self.date.clone().unwrap_or_else(|| "????.??.??".to_string())),
+            ("Round", self.round.clone().unwrap_or_else(|| "-".to_string())),
+            ("White", self.white.to_string()),
+            ("Black", self.black.to_string()),
+            ("Result", self.result_tag().to_string()),
+            ("TimeControl", self.time_control_tag()),
+        ];
+        if let Some(termination) = self.termination_tag() {
+            tags.push(("Termination", termination.to_string()));
+        }
+        match self.variant {
+            // Piece odds games start from a non-standard position
+            Variant::Standard if self.handicap.removed_square(self.handicap_color).is_some() => {
+                tags.push(("SetUp", "1".to_string()));
+                tags.push(("FEN", fen_from_chess_match(&self.start_state())));
+            }
+            Variant::Standard => {}
+            Variant::Chess960 => {
+                tags.push(("Variant", "Chess960".to_string()));
+                tags.push(("SetUp", "1".to_string()));
+                tags.push(("FEN", fen_from_chess_match(&self.start_state())));
+            }
+            Variant::KingOfTheHill => tags.push(("Variant", "King of the Hill".to_string())),
+            Variant::ThreeCheck => tags.push(("Variant", "Three-check".to_string())),
+            Variant::Crazyhouse => tags.push(("Variant", "Crazyhouse".to_string())),
+            Variant::Atomic => tags.push(("Variant", "Atomic".to_string())),
+            Variant::Antichess => tags.push(("Variant", "Antichess".to_string())),
+        }
+        if !self.match_id.is_empty() {
+            tags.push(("MatchId", self.match_id.clone()));
+        }
+        tags
+    }
+
+    // Renders the game, replaying every move through chess_logic to produce SAN.
+    pub fn to_pgn(&self) -> Result<String> {
+        let mut pgn = String::new();
+        for (name, value) in self.tag_pairs() {
+            pgn.push_str(&format!("[{} \"{}\"]\n", name, escape_tag_value(&value)));
+        }
+        pgn.push('\n');
+
+        let mut game_state = self.start_state();
+
+        let mut tokens = Vec::with_capacity(self.moves.len() * 3 + 1);
+        for (ply, pgn_move) in self.moves.iter().enumerate() {
+            if ply % 2 == 0 {
+                tokens.push(format!("{}.", game_state.fullmove_number));
+            }
+            let (san, _) = apply_move_with_san(
+                &mut game_state,
+                pgn_move.from_row,
+                pgn_move.from_col,
+                pgn_move.to_row,
+                pgn_move.to_col,
+                pgn_move.promotion,
+            )?;
+            tokens.push(san);
+            if let Some(seconds) = pgn_move.clock_seconds {
+                tokens.push(format!("{{[%emt {}]}}", format_clock(seconds)));
+            }
+        }
+        tokens.push(self.result_tag().to_string());
+
+        pgn.push_str(&wrap_tokens(&tokens));
+        pgn.push('\n');
+        Ok(pgn)
+    }
+}
+
+// "H:MM:SS", as used by the %clk / %emt comment commands.
+pub fn format_clock(seconds: u32) -> String {
+    format!("{}:{:02}:{:02}", seconds / 3600, (seconds / 60) % 60, seconds % 60)
+}
+
+fn escape_tag_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn wrap_tokens(tokens: &[String]) -> String {
+    let mut text = String::new();
+    let mut line_len = 0;
+    for token in tokens {
+        if line_len > 0 && line_len + 1 + token.len() > MAX_LINE_LEN {
+            text.push('\n');
+            line_len = 0;
+        } else if line_len > 0 {
+            text.push(' ');
+            line_len += 1;
+        }
+        text.push_str(token);
+        line_len += token.len();
+    }
+    text
+}
@@ -0,0 +1,178 @@
+// src/pgn/fen.rs
+use crate::state::{CastlingRights, ChessMatch, EnPassantSquare, Piece, PieceType, PlayerColor, Variant};
+use super::{piece_letter, square_name, PgnParseError};
+
+// Builds a game state from a FEN string, e.g. the value of a PGN [FEN] tag.
+// Castling may be given as KQkq, as X-FEN (KQkq meaning the outermost rook) or as
+// Shredder-FEN rook files (e.g. HAha); rook files put the game into Chess960 mode.
+pub fn chess_match_from_fen(fen: &str) -> Result<ChessMatch, PgnParseError> {
+    let invalid = || PgnParseError::InvalidFen(fen.to_string());
+    let fields: Vec<&str> = fen.split_whitespace().collect();
+    if fields.len() < 4 {
+        return Err(invalid());
+    }
+
+    let mut game_state = ChessMatch::default();
+    game_state.reset_position();
+    game_state.set_board(&[[None; 8]; 8]);
+
+    // 1. Piece placement, rank 8 first
+    let ranks: Vec<&str> = fields[0].split('/').collect();
+    if ranks.len() != 8 {
+        return Err(invalid());
+    }
+    for (rank_idx, rank) in ranks.iter().enumerate() {
+        let row = 7 - rank_idx;
+        let mut col = 0usize;
+        for symbol in rank.chars() {
+            if let Some(empty) = symbol.to_digit(10) {
+                col += empty as usize;
+                continue;
+            }
+            if col > 7 {
+                return Err(invalid());
+            }
+            let color = if symbol.is_ascii_uppercase() { PlayerColor::White } else { PlayerColor::Black };
+            let piece_type = piece_type_from_letter(symbol.to_ascii_uppercase()).ok_or_else(invalid)?;
+            game_state.set_piece_at(row, col, Some(Piece { piece_type, color }));
+            col += 1;
+        }
+        if col != 8 {
+            return Err(invalid());
+        }
+    }
+
+    // 2. Side to move
+    let side_to_move = match fields[1] {
+        "w" => PlayerColor::White,
+        "b" => PlayerColor::Black,
+        _ => return Err(invalid()),
+    };
+    game_state.set_current_turn(side_to_move);
+    game_state.current_player_idx = if side_to_move == PlayerColor::White { 0 } else { 1 };
+
+    // 3. Castling availability, stored as rook files
+    let mut rights = CastlingRights { white_kingside: None, white_queenside: None, black_kingside: None, black_queenside: None };
+    let mut shredder_files = false;
+    if fields[2] != "-" {
+        for symbol in fields[2].chars() {
+            let color = if symbol.is_ascii_uppercase() { PlayerColor::White } else { PlayerColor::Black };
+            let home_row = if color == PlayerColor::White { 0 } else { 7 };
+            let is_own = |col: u8, piece_type: PieceType| game_state.piece_at(home_row, col as usize) == Some(Piece { piece_type, color });
+            let king_col = (0..8u8).find(|&col| is_own(col, PieceType::King)).ok_or_else(invalid)?;
+            let rook_col = match symbol.to_ascii_uppercase() {
+                'K' => (king_col + 1..8).rev().find(|&col| is_own(col, PieceType::Rook)).ok_or_else(invalid)?,
+                'Q' => (0..king_col).find(|&col| is_own(col, PieceType::Rook)).ok_or_else(invalid)?,
+                file @ 'A'..='H' => {
+                    shredder_files = true;
+                    file as u8 - b'A'
+                }
+                _ => return Err(invalid()),
+            };
+            let right = match (color, rook_col > king_col) {
+                (PlayerColor::White, true) => &mut rights.white_kingside,
+                (PlayerColor::White, false) => &mut rights.white_queenside,
+                (PlayerColor::Black, true) => &mut rights.black_kingside,
+                (PlayerColor::Black, false) => &mut rights.black_queenside,
+            };
+            *right = Some(rook_col);
+        }
+    }
+    game_state.set_castling_rights(rights);
+    if shredder_files {
+        game_state.set_variant(Variant::Chess960);
+    }
+
+    // 4. En passant target square
+    game_state.set_en_passant_target(if fields[3] == "-" {
+        None
+    } else {
+        let (row, col) = parse_square(fields[3]).ok_or_else(invalid)?;
+        Some(EnPassantSquare { row, col })
+    });
+
+    // 5./6. Clocks are optional in some exporters
+    if let Some(halfmove) = fields.get(4) {
+        game_state.halfmove_clock = halfmove.parse::<u16>().map_err(|_| invalid())?.min(u8::MAX as u16) as u8;
+    }
+    if let Some(fullmove) = fields.get(5) {
+        game_state.fullmove_number = fullmove.parse().map_err(|_| invalid())?;
+    }
+
+    Ok(game_state)
+}
+
+// FEN of a game state. Chess960 games write castling rights as Shredder-FEN rook files.
+pub fn fen_from_chess_match(game_state: &ChessMatch) -> String {
+    let mut placement = Vec::with_capacity(8);
+    for row in (0..8).rev() {
+        let mut rank = String::new();
+        let mut empty = 0;
+        for square in game_state.board()[row] {
+            match square {
+                None => empty += 1,
+                Some(piece) => {
+                    if empty > 0 {
+                        rank.push_str(&empty.to_string());
+                        empty = 0;
+                    }
+                    let letter = match piece.piece_type {
+                        PieceType::Pawn => "P",
+                        other => piece_letter(other),
+                    };
+                    rank.push_str(&if piece.color == PlayerColor::White { letter.to_string() } else { letter.to_ascii_lowercase() });
+                }
+            }
+        }
+        if empty > 0 {
+            rank.push_str(&empty.to_string());
+        }
+        placement.push(rank);
+    }
+
+    let side = if game_state.current_turn() == PlayerColor::White { "w" } else { "b" };
+
+    let rights = game_state.castling_rights();
+    let mut castling = String::new();
+    for (right, standard_letter, white) in [
+        (rights.white_kingside, 'K', true),
+        (rights.white_queenside, 'Q', true),
+        (rights.black_kingside, 'k', false),
+        (rights.black_queenside, 'q', false),
+    ] {
+        if let Some(col) = right {
+            let letter = if game_state.variant() == Variant::Chess960 { (b'A' + col) as char } else { standard_letter };
+            castling.push(if white { letter } else { letter.to_ascii_lowercase() });
+        }
+    }
+    if castling.is_empty() {
+        castling.push('-');
+    }
+
+    let en_passant = game_state.en_passant_target().map_or("-".to_string(), |square| square_name(square.row, square.col));
+    format!(
+        "{} {} {} {} {} {}",
+        placement.join("/"), side, castling, en_passant, game_state.halfmove_clock, game_state.fullmove_number
+    )
+}
+
+pub fn piece_type_from_letter(letter: char) -> Option<PieceType> {
+    match letter {
+        'P' => Some(PieceType::Pawn),
+        'N' => Some(PieceType::Knight),
+        'B' => Some(PieceType::Bishop),
+        'R' => Some(PieceType::Rook),
+        'Q' => Some(PieceType::Queen),
+        'K' => Some(PieceType::King),
+        _ => None,
+    }
+}
+
+// "e4" -> (3, 4)
+pub fn parse_square(name: &str) -> Option<(u8, u8)> {
+    let bytes = name.as_bytes();
+    if bytes.len() != 2 || !(b'a'..=b'h').contains(&bytes[0]) || !(b'1'..=b'8').contains(&bytes[1]) {
+        return None;
+    }
+    Some((bytes[1] - b'1', bytes[0] - b'a'))
+}
@@ -0,0 +1,374 @@
+// src/pgn/import.rs
+use std::fmt;
+
+use anchor_lang::prelude::*;
+
+use crate::errors::ChessError;
+use speed_chess_core::CastleSide;
+
+use crate::state::{ChessMatch, MoveResult, PieceType, PlayerColor, Variant};
+use crate::utils::chess_logic;
+use super::{apply_move_with_san, chess_match_from_fen, parse_square, piece_type_from_letter, PgnMove};
+
+// A game as read from PGN text: its tag pairs and the SAN of each ply in order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParsedPgn {
+    pub tags: Vec<(String, String)>,
+    pub moves: Vec<String>,
+    pub result: Option<String>,
+}
+
+impl ParsedPgn {
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags.iter().find(|(tag_name, _)| tag_name == name).map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PgnParseError {
+    MalformedTag(String),
+    UnterminatedComment,
+    UnterminatedVariation,
+    UnbalancedDelimiter(char), // A `)` or `}` with nothing open to close
+    InvalidFen(String),
+}
+
+impl fmt::Display for PgnParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PgnParseError::MalformedTag(line) => write!(f, "malformed tag pair: {}", line),
+            PgnParseError::UnterminatedComment => write!(f, "unterminated {{comment}}"),
+            PgnParseError::UnterminatedVariation => write!(f, "unterminated (variation)"),
+            PgnParseError::UnbalancedDelimiter(delimiter) => write!(f, "unbalanced '{}'", delimiter),
+            PgnParseError::InvalidFen(fen) => write!(f, "invalid FEN: {}", fen),
+        }
+    }
+}
+
+// Why a replay stopped. `ply` counts from 0 at the start position.
+#[derive(Debug, PartialEq)]
+pub enum ReplayError {
+    Parse(PgnParseError),
+    UnrecognisedMove { ply: usize, san: String },
+    AmbiguousMove { ply: usize, san: String },
+    MoveAfterGameEnd { ply: usize, san: String },
+    // chess_logic rejected the move; `error` is the exact ChessError it raised.
+    IllegalMove { ply: usize, san: String, error: Error },
+}
+
+impl From<PgnParseError> for ReplayError {
+    fn from(error: PgnParseError) -> Self {
+        ReplayError::Parse(error)
+    }
+}
+
+// The position reached by a successful replay and the moves that led to it.
+#[derive(Clone, Debug)]
+pub struct ReplayOutcome {
+    pub game_state: ChessMatch,
+    pub moves: Vec<PgnMove>,
+    pub final_move_result: Option<MoveResult>,
+}
+
+// Parses every game in a PGN file.
+pub fn parse_pgn_games(text: &str) -> std::result::Result<Vec<ParsedPgn>, PgnParseError> {
+    let mut games = Vec::new();
+    let mut current = ParsedPgn::default();
+    let mut in_movetext = false;
+    let mut chars = text.chars().peekable();
+    let mut at_line_start = true; // `%` escapes a line only in its first column
+
+    while let Some(&c) = chars.peek() {
+        let line_start = std::mem::replace(&mut at_line_start, false);
+        match c {
+            '[' => {
+                if in_movetext {
+                    games.push(std::mem::take(&mut current));
+                    in_movetext = false;
+                }
+                let line: String = chars.by_ref().take_while(|&ch| ch != '\n').collect();
+                current.tags.push(parse_tag_pair(line.trim())?);
+                at_line_start = true;
+            }
+            '{' => {
+                chars.next();
+                if !chars.by_ref().any(|ch| ch == '}') {
+                    return Err(PgnParseError::UnterminatedComment);
+                }
+            }
+            ';' => {
+                chars.by_ref().take_while(|&ch| ch != '\n').for_each(drop);
+                at_line_start = true;
+            }
+            '%' if line_start => {
+                chars.by_ref().take_while(|&ch| ch != '\n').for_each(drop);
+                at_line_start = true;
+            }
+            ')' | '}' => return Err(PgnParseError::UnbalancedDelimiter(c)),
+            '(' => {
+                let mut depth = 0usize;
+                loop {
+                    match chars.next() {
+                        Some('(') => depth += 1,
+                        Some(')') => {
+                            depth -= 1;
+                            if depth == 0 { break; }
+                        }
+                        Some('{') => {
+                            if !chars.by_ref().any(|ch| ch == '}') {
+                                return Err(PgnParseError::UnterminatedComment);
+                            }
+                        }
+                        Some(_) => {}
+                        None => return Err(PgnParseError::UnterminatedVariation),
+                    }
+                }
+            }
+            c if c.is_whitespace() => {
+                chars.next();
+                at_line_start = c == '\n';
+            }
+            _ => {
+                let mut token = String::new();
+                while let Some(&ch) = chars.peek() {
+                    if ch.is_whitespace() || "{}();[".contains(ch) {
+                        break;
+                    }
+                    token.push(ch);
+                    chars.next();
+                }
+                in_movetext = true;
+                match token.as_str() {
+                    "1-0" | "0-1" | "1/2-1/2" | "*" => current.result = Some(token),
+                    _ if token.starts_with('$') => {} // Numeric annotation glyph
+                    _ => {
+                        // Strip a leading move number ("12.", "12...", "12.e4")
+                        let san = if token.starts_with(|ch: char| ch.is_ascii_digit()) && token.contains('.') {
+                            token.trim_start_matches(|ch: char| ch.is_ascii_digit() || ch == '.')
+                        } else {
+                            token.as_str()
+                        };
+                        if !san.is_empty() {
+                            current.moves.push(san.to_string());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if in_movetext || !current.tags.is_empty() {
+        games.push(current);
+    }
+    Ok(games)
+}
+
+// Parses a PGN holding a single game.
+pub fn parse_pgn(text: &str) -> std::result::Result<ParsedPgn, PgnParseError> {
+    Ok(parse_pgn_games(text)?.into_iter().next().unwrap_or_default())
+}
+
+fn parse_tag_pair(line: &str) -> std::result::Result<(String, String), PgnParseError> {
+    let malformed = || PgnParseError::MalformedTag(line.to_string());
+    let inner = line.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')).ok_or_else(malformed)?.trim();
+    let (name, quoted) = inner.split_once(char::is_whitespace).ok_or_else(malformed)?;
+    let value = quoted.trim().strip_prefix('"').and_then(|rest| rest.strip_suffix('"')).ok_or_else(malformed)?;
+    Ok((name.to_string(), value.replace("\\\"", "\"").replace("\\\\", "\\")))
+}
+
+// Replays a parsed game through chess_logic from the standard start position, or from
+// its [FEN] tag if present, stopping at the first move the rules engine rejects.
+// A [Variant] tag naming a supported variant (Chess960, Crazyhouse, Atomic, ...) switches to its rules.
+pub fn replay_pgn(pgn: &ParsedPgn) -> std::result::Result<ReplayOutcome, ReplayError> {
+    let mut game_state = match pgn.tag("FEN") {
+        Some(fen) => chess_match_from_fen(fen)?,
+        None => {
+            let mut game_state = ChessMatch::default();
+            game_state.reset_position();
+            game_state
+        }
+    };
+    if let Some(variant) = pgn.tag("Variant").and_then(variant_from_tag) {
+        game_state.set_variant(variant);
+    }
+
+    let mut moves = Vec::with_capacity(pgn.moves.len());
+    let mut final_move_result = None;
+    for (ply, san) in pgn.moves.iter().enumerate() {
+        if matches!(final_move_result, Some(MoveResult::Checkmate | MoveResult::Stalemate | MoveResult::VariantWin { .. })) {
+            return Err(ReplayError::MoveAfterGameEnd { ply, san: san.clone() });
+        }
+        let pgn_move = resolve_san(&game_state, ply, san)?;
+        let (_, move_result) = apply_move_with_san(
+            &mut game_state,
+            pgn_move.from_row,
+            pgn_move.from_col,
+            pgn_move.to_row,
+            pgn_move.to_col,
+            pgn_move.promotion,
+        )
+        .map_err(|error| ReplayError::IllegalMove { ply, san: san.clone(), error })?;
+        moves.push(pgn_move);
+        final_move_result = Some(move_result);
+    }
+
+    Ok(ReplayOutcome { game_state, moves, final_move_result })
+}
+
+// Parses and replays a single-game PGN.
+pub fn replay_pgn_text(text: &str) -> std::result::Result<ReplayOutcome, ReplayError> {
+    replay_pgn(&parse_pgn(text)?)
+}
+
+// Variant named by a [Variant] tag, ignoring case, spaces and hyphens ("King of the Hill", "Three-check").
+// Unknown names return None so the game replays under standard rules.
+fn variant_from_tag(value: &str) -> Option<Variant> {
+    let name: String = value.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>().to_ascii_lowercase();
+    match name.as_str() {
+        "chess960" | "fischerandom" => Some(Variant::Chess960),
+        "kingofthehill" => Some(Variant::KingOfTheHill),
+        "threecheck" => Some(Variant::ThreeCheck),
+        "crazyhouse" => Some(Variant::Crazyhouse),
+        "atomic" => Some(Variant::Atomic),
+        "antichess" | "giveaway" => Some(Variant::Antichess),
+        _ => None,
+    }
+}
+
+struct SanMove {
+    castle: Option<CastleSide>, // Target squares depend on the position, see resolve_san
+    drop: bool,                 // Crazyhouse "N@f3": piece_type comes from the pocket
+    piece_type: PieceType,
+    to_row: u8,
+    to_col: u8,
+    from_col: Option<u8>,
+    from_row: Option<u8>,
+    promotion: Option<PieceType>,
+}
+
+fn parse_san(san: &str, mover: PlayerColor) -> Option<SanMove> {
+    let san = san.trim_end_matches(['+', '#', '!', '?']);
+    let home_row = if mover == PlayerColor::White { 0 } else { 7 };
+    let castle = match san {
+        "O-O" | "0-0" => Some(CastleSide::Kingside),
+        "O-O-O" | "0-0-0" => Some(CastleSide::Queenside),
+        _ => None,
+    };
+    if let Some(side) = castle {
+        return Some(SanMove { castle, drop: false, piece_type: PieceType::King, to_row: home_row, to_col: side.king_to_col(), from_col: None, from_row: Some(home_row), promotion: None });
+    }
+
+    // Drops: "N@f3", with the pawn letter optional ("P@e6" or "@e6")
+    if let Some((letter, square)) = san.split_once('@') {
+        let piece_type = if letter.is_empty() { PieceType::Pawn } else { piece_type_from_letter(letter.chars().next()?)? };
+        let (to_row, to_col) = parse_square(square)?;
+        return Some(SanMove { castle: None, drop: true, piece_type, to_row, to_col, from_col: None, from_row: None, promotion: None });
+    }
+
+    // Promotion suffix: "e8=Q" (or the older "e8Q")
+    let (body, promotion) = match san.split_once('=') {
+        Some((body, piece)) => (body, Some(piece_type_from_letter(piece.chars().next()?)?)),
+        None => match san.chars().last() {
+            Some(last) if last.is_ascii_uppercase() && san.len() > 2 => (&san[..san.len() - 1], Some(piece_type_from_letter(last)?)),
+            _ => (san, None),
+        },
+    };
+
+    let (piece_type, rest) = match body.chars().next()? {
+        letter if letter.is_ascii_uppercase() => (piece_type_from_letter(letter)?, &body[1..]),
+        _ => (PieceType::Pawn, body),
+    };
+    let rest: String = rest.chars().filter(|&ch| ch != 'x' && ch != '-').collect();
+    if rest.len() < 2 {
+        return None;
+    }
+    let (to_row, to_col) = parse_square(&rest[rest.len() - 2..])?;
+
+    let mut from_col = None;
+    let mut from_row = None;
+    for ch in rest[..rest.len() - 2].chars() {
+        match ch {
+            'a'..='h' => from_col = Some(ch as u8 - b'a'),
+            '1'..='8' => from_row = Some(ch as u8 - b'1'),
+            _ => return None,
+        }
+    }
+    if piece_type == PieceType::Pawn && from_col.is_none() {
+        from_col = Some(to_col); // Pawn pushes stay on their file
+    }
+
+    Some(SanMove { castle: None, drop: false, piece_type, to_row, to_col, from_col, from_row, promotion })
+}
+
+// Maps SAN onto board coordinates. When no candidate is legal, the move is handed to
+// chess_logic anyway so the caller sees the exact ChessError it triggers.
+fn resolve_san(game_state: &ChessMatch, ply: usize, san: &str) -> std::result::Result<PgnMove, ReplayError> {
+    let mover = game_state.current_turn();
+    let parsed = parse_san(san, mover).ok_or_else(|| ReplayError::UnrecognisedMove { ply, san: san.to_string() })?;
+
+    // Castling: the encoding comes from the position (king onto rook in Chess960). Without the
+    // right, fall back to the standard two-file king move and let chess_logic reject it.
+    if let Some(side) = parsed.castle {
+        let (from_row, from_col, to_row, to_col) = chess_logic::castling_move(game_state, side)
+            .unwrap_or((parsed.to_row, 4, parsed.to_row, parsed.to_col));
+        return Ok(PgnMove { from_row, from_col, to_row, to_col, promotion: None, clock_seconds: None });
+    }
+    // Drops are encoded as from == to with the dropped piece in the promotion slot
+    if parsed.drop {
+        let (to_row, to_col) = (parsed.to_row, parsed.to_col);
+        return Ok(PgnMove { from_row: to_row, from_col: to_col, to_row, to_col, promotion: Some(parsed.piece_type), clock_seconds: None });
+    }
+
+    let mut candidates = Vec::new();
+    for row in 0..8u8 {
+        for col in 0..8u8 {
+            let matches_piece = game_state.piece_at(row as usize, col as usize)
+                .is_some_and(|piece| piece.color == mover && piece.piece_type == parsed.piece_type);
+            if matches_piece && parsed.from_col.is_none_or(|c| c == col) && parsed.from_row.is_none_or(|r| r == row) {
+                candidates.push((row, col));
+            }
+        }
+    }
+
+    let to_move = |(from_row, from_col): (u8, u8)| PgnMove {
+        from_row,
+        from_col,
+        to_row: parsed.to_row,
+        to_col: parsed.to_col,
+        promotion: parsed.promotion,
+        clock_seconds: None,
+    };
+
+    let legal: Vec<(u8, u8)> = candidates
+        .iter()
+        .copied()
+        .filter(|&(row, col)| chess_logic::is_legal_move(game_state, row, col, parsed.to_row, parsed.to_col))
+        .collect();
+    match legal.len() {
+        1 => return Ok(to_move(legal[0])),
+        0 => {}
+        _ => return Err(ReplayError::AmbiguousMove { ply, san: san.to_string() }),
+    }
+
+    // Nothing legal: prefer the candidate whose rejection is most specific (e.g. a pinned piece
+    // reports InvalidMoveLeavesKingInCheck rather than a geometry error).
+    let generic: Error = ChessError::InvalidMoveIllegalPieceMovement.into();
+    let mut first_error = None;
+    for &candidate in &candidates {
+        let mut probe = *game_state;
+        let pgn_move = to_move(candidate);
+        if let Err(error) = chess_logic::validate_and_apply_move(
+            &mut probe, pgn_move.from_row, pgn_move.from_col, pgn_move.to_row, pgn_move.to_col, mover, pgn_move.promotion,
+        ) {
+            if error != generic {
+                return Err(ReplayError::IllegalMove { ply, san: san.to_string(), error });
+            }
+            first_error.get_or_insert(error);
+        }
+    }
+    Err(ReplayError::IllegalMove {
+        ply,
+        san: san.to_string(),
+        error: first_error.unwrap_or(generic),
+    })
+}
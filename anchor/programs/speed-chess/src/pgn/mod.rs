@@ -0,0 +1,62 @@
+// src/pgn/mod.rs
+// Off-chain helpers for Portable Game Notation: rendering games from on-chain data and
+// replaying PGN through chess_logic. Compiled only with the "pgn" feature.
+pub mod san;
+pub mod export;
+pub mod fen;
+pub mod import;
+
+pub use san::*;
+pub use export::*;
+pub use fen::*;
+pub use import::*;
+
+use crate::events::MoveMadeEvent;
+use crate::state::{PieceType, RecordedMove};
+
+// A move as PGN tooling sees it: board coordinates plus an optional clock reading.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PgnMove {
+    pub from_row: u8,
+    pub from_col: u8,
+    pub to_row: u8,
+    pub to_col: u8,
+    pub promotion: Option<PieceType>,
+    pub clock_seconds: Option<u32>, // Seconds the mover spent on this move, if known
+}
+
+impl From<RecordedMove> for PgnMove {
+    fn from(recorded_move: RecordedMove) -> Self {
+        Self {
+            from_row: recorded_move.from_row,
+            from_col: recorded_move.from_col,
+            to_row: recorded_move.to_row,
+            to_col: recorded_move.to_col,
+            promotion: recorded_move.promotion,
+            clock_seconds: Some(recorded_move.clock_seconds),
+        }
+    }
+}
+
+impl From<&MoveMadeEvent> for PgnMove {
+    fn from(event: &MoveMadeEvent) -> Self {
+        // A drop goes in the promotion slot with from == to, as in the move history
+        let (from_row, from_col, promotion) = match event.drop_piece {
+            Some(piece_type) => (event.to_row, event.to_col, Some(piece_type)),
+            None => (event.from_row, event.from_col, event.promotion_piece),
+        };
+        Self {
+            from_row,
+            from_col,
+            to_row: event.to_row,
+            to_col: event.to_col,
+            promotion,
+            clock_seconds: Some(event.move_time_seconds),
+        }
+    }
+}
+
+// Algebraic name of a square, e.g. (0, 4) -> "e1".
+pub fn square_name(row: u8, col: u8) -> String {
+    format!("{}{}", (b'a' + col) as char, row + 1)
+}
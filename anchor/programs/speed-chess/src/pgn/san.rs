@@ -0,0 +1,130 @@
+// src/pgn/san.rs
+use anchor_lang::prelude::*;
+use speed_chess_core::CastleSide;
+
+use crate::state::{ChessMatch, MoveResult, PieceType, PlayerColor};
+use crate::utils::chess_logic;
+use super::square_name;
+
+pub fn piece_letter(piece_type: PieceType) -> &'static str {
+    match piece_type {
+        PieceType::Pawn => "",
+        PieceType::Knight => "N",
+        PieceType::Bishop => "B",
+        PieceType::Rook => "R",
+        PieceType::Queen => "Q",
+        PieceType::King => "K",
+    }
+}
+
+// Plays a move for the side to move and returns it in Standard Algebraic Notation.
+// The move is validated by chess_logic, so any ChessError it raises is passed through.
+pub fn apply_move_with_san(
+    game_state: &mut ChessMatch,
+    from_row: u8,
+    from_col: u8,
+    to_row: u8,
+    to_col: u8,
+    promotion: Option<PieceType>,
+) -> Result<(String, MoveResult)> {
+    let san_body = san_without_suffix(game_state, from_row, from_col, to_row, to_col, promotion);
+
+    let mover = game_state.current_turn();
+    let move_result = chess_logic::validate_and_apply_move(
+        game_state, from_row, from_col, to_row, to_col, mover, promotion,
+    )?;
+
+    let suffix = if move_result == MoveResult::Checkmate {
+        "#"
+    } else if chess_logic::is_in_check(game_state, game_state.current_turn()) {
+        "+"
+    } else {
+        ""
+    };
+    Ok((format!("{}{}", san_body, suffix), move_result))
+}
+
+// SAN for a move in the current position, without the check/mate suffix.
+// Illegal moves still get a best-effort rendering; callers validate separately.
+// Crazyhouse drops (from == to) render as e.g. "N@f3" or "P@e6".
+fn san_without_suffix(
+    game_state: &ChessMatch,
+    from_row: u8,
+    from_col: u8,
+    to_row: u8,
+    to_col: u8,
+    promotion: Option<PieceType>,
+) -> String {
+    if let (true, Some(dropped)) = ((from_row, from_col) == (to_row, to_col), promotion) {
+        let letter = if dropped == PieceType::Pawn { "P" } else { piece_letter(dropped) };
+        return format!("{}@{}", letter, square_name(to_row, to_col));
+    }
+    let piece = match game_state.board().get(from_row as usize).and_then(|row| row.get(from_col as usize)).copied().flatten() {
+        Some(piece) => piece,
+        None => return format!("{}{}", square_name(from_row, from_col), square_name(to_row, to_col)),
+    };
+    let target = square_name(to_row, to_col);
+
+    if piece.piece_type == PieceType::King {
+        match chess_logic::castling_side(game_state, from_row, from_col, to_row, to_col) {
+            Some(CastleSide::Kingside) => return "O-O".to_string(),
+            Some(CastleSide::Queenside) => return "O-O-O".to_string(),
+            None => {}
+        }
+    }
+
+    let is_en_passant = piece.piece_type == PieceType::Pawn
+        && from_col != to_col
+        && game_state.en_passant_target().is_some_and(|ep| ep.row == to_row && ep.col == to_col);
+    let is_capture = game_state.piece_at(to_row as usize, to_col as usize).is_some() || is_en_passant;
+
+    if piece.piece_type == PieceType::Pawn {
+        let mut san = String::new();
+        if is_capture {
+            san.push((b'a' + from_col) as char);
+            san.push('x');
+        }
+        san.push_str(&target);
+        let last_rank = if piece.color == PlayerColor::White { 7 } else { 0 };
+        if to_row == last_rank {
+            san.push('=');
+            san.push_str(piece_letter(promotion.unwrap_or(PieceType::Queen)));
+        }
+        return san;
+    }
+
+    // Disambiguate against other pieces of the same kind that can also reach the target
+    let mut needs_disambiguation = false;
+    let mut shares_file = false;
+    let mut shares_rank = false;
+    for row in 0..8u8 {
+        for col in 0..8u8 {
+            if (row, col) == (from_row, from_col) {
+                continue;
+            }
+            if game_state.piece_at(row as usize, col as usize) == Some(piece)
+                && chess_logic::is_legal_move(game_state, row, col, to_row, to_col)
+            {
+                needs_disambiguation = true;
+                shares_file |= col == from_col;
+                shares_rank |= row == from_row;
+            }
+        }
+    }
+
+    let mut san = piece_letter(piece.piece_type).to_string();
+    if needs_disambiguation {
+        if !shares_file {
+            san.push((b'a' + from_col) as char);
+        } else if !shares_rank {
+            san.push((b'1' + from_row) as char);
+        } else {
+            san.push_str(&square_name(from_row, from_col));
+        }
+    }
+    if is_capture {
+        san.push('x');
+    }
+    san.push_str(&target);
+    san
+}
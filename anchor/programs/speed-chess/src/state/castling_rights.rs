@@ -1,21 +1,23 @@
 // src/state/castling_rights.rs
 use anchor_lang::prelude::*;
 
+// File of the rook each side may still castle with (None once the right is lost).
+// Storing files rather than flags covers Chess960, where rooks need not start on a/h.
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
 pub struct CastlingRights {
-    pub white_kingside: bool,
-    pub white_queenside: bool,
-    pub black_kingside: bool,
-    pub black_queenside: bool,
+    pub white_kingside: Option<u8>,
+    pub white_queenside: Option<u8>,
+    pub black_kingside: Option<u8>,
+    pub black_queenside: Option<u8>,
 }
 
 impl Default for CastlingRights {
     fn default() -> Self {
         Self {
-            white_kingside: true,
-            white_queenside: true,
-            black_kingside: true,
-            black_queenside: true,
+            white_kingside: Some(7),
+            white_queenside: Some(0),
+            black_kingside: Some(7),
+            black_queenside: Some(0),
         }
     }
 }
@@ -24,4 +26,15 @@ impl CastlingRights {
     pub fn new() -> Self {
         Self::default()
     }
+
+    // One byte per right in field order, holding the rook's file + 1 (0 once the right is lost).
+    pub fn pack(&self) -> [u8; 4] {
+        [self.white_kingside, self.white_queenside, self.black_kingside, self.black_queenside]
+            .map(|file| file.map_or(0, |file| file + 1))
+    }
+
+    pub fn unpack(packed: [u8; 4]) -> Self {
+        let [white_kingside, white_queenside, black_kingside, black_queenside] = packed.map(|byte| byte.checked_sub(1));
+        Self { white_kingside, white_queenside, black_kingside, black_queenside }
+    }
 }
@@ -1,38 +1,470 @@
 // src/state/chess_match.rs
+use crate::errors::ChessError;
 use crate::state::*;
+use crate::utils::{chess_logic, seed_to_index, start_position_commitment};
+use speed_chess_core::CHESS960_START_POSITIONS;
+use anchor_lang::solana_program::hash::hashv;
 use anchor_lang::prelude::*;
 
 pub const MAX_PLAYERS: usize = 2;
 pub const MAX_MATCH_ID_LEN: usize = 32; // Define a max length for the string match_id.
+pub const STANDARD_START_POSITION: u16 = speed_chess_core::STANDARD_START_POSITION; // Scharnagl number of RNBQKBNR
 
-#[account]
-#[derive(InitSpace, Debug)]
-pub struct ChessMatch {
-    #[max_len(MAX_MATCH_ID_LEN)] // REQUIRED for String with InitSpace
-    pub match_id: String, 
-    pub players: [Pubkey; MAX_PLAYERS], // players[0] is White, players[1] is Black (by convention)
-    pub current_player_idx: u8,
-    pub current_turn: PlayerColor,
+// Flag bits of ChessMatch::flags
+const FLAG_BLACK_TO_MOVE: u16 = 1 << 0;
+const FLAG_HANDICAP_BY_BLACK: u16 = 1 << 1;
+const FLAG_HAS_JOIN_DEADLINE: u16 = 1 << 2;
+const FLAG_RATED: u16 = 1 << 3;
+const FLAG_WHITE_BERSERK: u16 = 1 << 4;
+const FLAG_BLACK_BERSERK: u16 = 1 << 5;
+const FLAG_DELEGATED: u16 = 1 << 6;
+const FLAG_TAKEBACKS_ENABLED: u16 = 1 << 7;
+const FLAG_PAYOUT_PROCESSED: u16 = 1 << 8;
+const FLAG_RESULT_RECORDED: u16 = 1 << 9;
 
+// Zero-copy: make_move reads and writes the account data in place instead of deserializing and
+// re-serializing it. Plain numbers are public fields; everything packed (the board, enums, options
+// and flags) goes through the accessors below. Fields are ordered by alignment so repr(C) adds no
+// padding. An optional byte holds its value + 1, with 0 for None.
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct ChessMatch {
     pub last_move_timestamp: i64, // Timestamp of the last successful move or game start
     pub move_timeout_duration: i64, // Duration in seconds for a single move timeout
-
-    pub game_status: GameStatus,
-    pub game_end_reason: Option<GameEndReason>,
-
-    pub board: [[Option<Piece>; 8]; 8], //
-    pub castling_rights: CastlingRights,
-    pub en_passant_target: Option<EnPassantSquare>, // **** CORRECTED TYPE ****
-    pub halfmove_clock: u8,
-    pub fullmove_number: u16,
-
-    pub betting_token_mint: Pubkey,
+    handicap_move_timeout: i64, // Handicap::TimeOdds creator_move_timeout, 0 otherwise
+    join_deadline: i64, // Unix timestamp after which an open challenge can no longer be joined, if FLAG_HAS_JOIN_DEADLINE
+    pub promoted_squares: u64, // Crazyhouse: bit row * 8 + col is set for pieces that were promoted pawns
     pub bet_amount_player_one: u64,
     pub bet_amount_player_two: u64, // Will be 0 initially
     pub total_pot: u64,
+    pub plies_played: u64, // Every ply ever applied; takebacks do not rewind it, so make_moves signatures bind to it
+
+    pub lobby_page: u32, // LobbyPage listing this match while it waits for an opponent
+    pub start_position_index: u16, // Scharnagl number of the start position (518 is the standard setup)
+    pub fullmove_number: u16,
     pub platform_fee_basis_points: u16, // Renamed from platform_fee_bps for consistency
-    pub payout_processed: bool,         // <-- NEW FLAG
+    flags: u16,
+
+    pub players: [Pubkey; MAX_PLAYERS], // players[0] is White, players[1] is Black (by convention)
+    pub betting_token_mint: Pubkey,
+    tournament: Pubkey, // Tournament that created this game (it has no stakes or escrow of its own), or default
+    rematch_requested_by: Pubkey, // Player who offered a color-swapped rematch after the game, or default
+    pub rent_payer: Pubkey, // Tournament games: who paid for this account and the move history, refunded on close
+    match_id: [u8; MAX_MATCH_ID_LEN], // UTF-8, match_id_len bytes used
+    match_id_len: u8,
+    board: [u8; 32], // Nibble per square (see pack_piece), square row * 8 + col, low nibble first
+    start_position_commitment: [u8; 32], // Chess960: creator's commitment until reveal_start_position, zeros after
+    castling_rights: [u8; 4], // CastlingRights::pack
+    en_passant_target: u8, // EnPassantSquare::pack
+    pub halfmove_clock: u8,
+    pub checks_given: [u8; 2], // Three-Check: checks delivered by White and Black
+    pub pockets: [Pocket; 2], // Crazyhouse: pieces in hand for White and Black
+    pub current_player_idx: u8,
+    game_status: u8,
+    game_end_reason: u8,
+    variant: u8,
+    handicap: u8, // Handicap::to_code kind
+    takeback_requested_by: u8,
+    premove: [u8; 4], // Premove::pack; queued by the side not on move, tried right after the opponent's next move
+    pub undo_history: [PackedUndoRecord; MAX_UNDO_PLIES], // undo_history[0] is the most recent ply
 
     pub bump: u8,
-    // Removed player_one_time_remaining, player_two_time_remaining to simplify, using per-move timeout
+}
+
+impl Default for ChessMatch {
+    fn default() -> Self {
+        bytemuck::Zeroable::zeroed()
+    }
+}
+
+impl anchor_lang::Space for ChessMatch {
+    const INIT_SPACE: usize = std::mem::size_of::<Self>();
+}
+
+impl ChessMatch {
+    fn flag(&self, bit: u16) -> bool {
+        self.flags & bit != 0
+    }
+
+    fn set_flag(&mut self, bit: u16, on: bool) {
+        if on { self.flags |= bit } else { self.flags &= !bit }
+    }
+
+    pub fn match_id(&self) -> String {
+        String::from_utf8_lossy(self.match_id_bytes()).into_owned()
+    }
+
+    // The match id as PDA seed.
+    pub fn match_id_bytes(&self) -> &[u8] {
+        &self.match_id[..self.match_id_len as usize]
+    }
+
+    // `match_id` must be at most MAX_MATCH_ID_LEN bytes (checked by the instructions that create matches).
+    pub fn set_match_id(&mut self, match_id: &str) {
+        let bytes = match_id.as_bytes();
+        self.match_id = [0; MAX_MATCH_ID_LEN];
+        self.match_id[..bytes.len()].copy_from_slice(bytes);
+        self.match_id_len = bytes.len() as u8;
+    }
+
+    pub fn current_turn(&self) -> PlayerColor {
+        if self.flag(FLAG_BLACK_TO_MOVE) { PlayerColor::Black } else { PlayerColor::White }
+    }
+
+    pub fn set_current_turn(&mut self, color: PlayerColor) {
+        self.set_flag(FLAG_BLACK_TO_MOVE, color == PlayerColor::Black);
+    }
+
+    pub fn handicap(&self) -> Handicap {
+        Handicap::from_code(self.handicap, self.handicap_move_timeout)
+    }
+
+    pub fn set_handicap(&mut self, handicap: Handicap) {
+        (self.handicap, self.handicap_move_timeout) = handicap.to_code();
+    }
+
+    // Side giving the odds: the creator, who is Black after a rematch swap
+    pub fn handicap_color(&self) -> PlayerColor {
+        if self.flag(FLAG_HANDICAP_BY_BLACK) { PlayerColor::Black } else { PlayerColor::White }
+    }
+
+    pub fn set_handicap_color(&mut self, color: PlayerColor) {
+        self.set_flag(FLAG_HANDICAP_BY_BLACK, color == PlayerColor::Black);
+    }
+
+    pub fn join_deadline(&self) -> Option<i64> {
+        self.flag(FLAG_HAS_JOIN_DEADLINE).then_some(self.join_deadline)
+    }
+
+    pub fn set_join_deadline(&mut self, join_deadline: Option<i64>) {
+        self.set_flag(FLAG_HAS_JOIN_DEADLINE, join_deadline.is_some());
+        self.join_deadline = join_deadline.unwrap_or(0);
+    }
+
+    // An open challenge can be joined up to and including its deadline, if it has one.
+    pub fn check_joinable_at(&self, now: i64) -> Result<()> {
+        if let Some(deadline) = self.join_deadline() {
+            require!(now <= deadline, ChessError::JoinDeadlinePassed);
+        }
+        Ok(())
+    }
+
+    // Only a challenge created with a deadline can expire, and only once the deadline has passed.
+    pub fn check_expirable_at(&self, now: i64) -> Result<()> {
+        let deadline = self.join_deadline().ok_or(error!(ChessError::NoJoinDeadline))?;
+        require!(now > deadline, ChessError::JoinDeadlineNotReached);
+        Ok(())
+    }
+
+    pub fn game_status(&self) -> GameStatus {
+        GameStatus::from_code(self.game_status)
+    }
+
+    pub fn set_game_status(&mut self, status: GameStatus) {
+        self.game_status = status as u8;
+    }
+
+    pub fn game_end_reason(&self) -> Option<GameEndReason> {
+        GameEndReason::from_code(self.game_end_reason.checked_sub(1)?)
+    }
+
+    pub fn set_game_end_reason(&mut self, reason: Option<GameEndReason>) {
+        self.game_end_reason = reason.map_or(0, |reason| reason as u8 + 1);
+    }
+
+    pub fn variant(&self) -> Variant {
+        Variant::from_code(self.variant)
+    }
+
+    pub fn set_variant(&mut self, variant: Variant) {
+        self.variant = variant as u8;
+    }
+
+    pub fn piece_at(&self, row: usize, col: usize) -> Option<Piece> {
+        let square = row * 8 + col;
+        unpack_piece(self.board[square / 2] >> (4 * (square % 2)) & 0x0f)
+    }
+
+    pub fn set_piece_at(&mut self, row: usize, col: usize, piece: Option<Piece>) {
+        let square = row * 8 + col;
+        let shift = 4 * (square % 2);
+        self.board[square / 2] = (self.board[square / 2] & !(0x0f << shift)) | (pack_piece(piece) << shift);
+    }
+
+    pub fn board(&self) -> [[Option<Piece>; 8]; 8] {
+        std::array::from_fn(|row| std::array::from_fn(|col| self.piece_at(row, col)))
+    }
+
+    pub fn set_board(&mut self, board: &[[Option<Piece>; 8]; 8]) {
+        for (row, pieces) in board.iter().enumerate() {
+            for (col, piece) in pieces.iter().enumerate() {
+                self.set_piece_at(row, col, *piece);
+            }
+        }
+    }
+
+    pub fn castling_rights(&self) -> CastlingRights {
+        CastlingRights::unpack(self.castling_rights)
+    }
+
+    pub fn set_castling_rights(&mut self, rights: CastlingRights) {
+        self.castling_rights = rights.pack();
+    }
+
+    pub fn en_passant_target(&self) -> Option<EnPassantSquare> {
+        EnPassantSquare::unpack(self.en_passant_target)
+    }
+
+    pub fn set_en_passant_target(&mut self, square: Option<EnPassantSquare>) {
+        self.en_passant_target = EnPassantSquare::pack(square);
+    }
+
+    // Only rated matches update player profiles at settlement
+    pub fn rated(&self) -> bool {
+        self.flag(FLAG_RATED)
+    }
+
+    pub fn set_rated(&mut self, rated: bool) {
+        self.set_flag(FLAG_RATED, rated);
+    }
+
+    // Rated games are played from the standard position with no takebacks
+    pub fn check_rated_settings(&self) -> Result<()> {
+        if self.rated() {
+            require!(
+                self.variant() == Variant::Standard && self.handicap() == Handicap::None && !self.takebacks_enabled(),
+                ChessError::RatedMatchRestriction
+            );
+        }
+        Ok(())
+    }
+
+    // The profiles settlement should rate: None for casual matches, both of them for rated ones.
+    pub fn profiles_to_rate<'a, P>(&self, white: Option<&'a mut P>, black: Option<&'a mut P>) -> Result<Option<(&'a mut P, &'a mut P)>> {
+        if !self.rated() {
+            return Ok(None);
+        }
+        match (white, black) {
+            (Some(white), Some(black)) => Ok(Some((white, black))),
+            _ => err!(ChessError::PlayerProfileRequired),
+        }
+    }
+
+    // Chess960: the creator's commitment to their share of the start position, until it is revealed
+    pub fn start_position_commitment(&self) -> Option<[u8; 32]> {
+        (self.start_position_commitment != [0; 32]).then_some(self.start_position_commitment)
+    }
+
+    pub fn set_start_position_commitment(&mut self, commitment: Option<[u8; 32]>) {
+        self.start_position_commitment = commitment.unwrap_or_default();
+    }
+
+    // Moves need an active game whose start position is settled.
+    pub fn check_accepts_moves(&self) -> Result<()> {
+        require!(self.game_status() == GameStatus::Active, ChessError::GameNotActive);
+        require!(self.start_position_commitment().is_none(), ChessError::StartPositionNotRevealed);
+        Ok(())
+    }
+
+    // Chess960: adds the creator's share, read from `secret`, to the joiner's share that join_match
+    // left in start_position_index, and sets up that position. Neither share alone decides it: the
+    // creator committed before the joiner's share existed, and the joiner never saw the secret.
+    pub fn reveal_start_position(&mut self, secret: &[u8; 32]) -> Result<()> {
+        let commitment = self.start_position_commitment().ok_or(error!(ChessError::NoStartPositionToReveal))?;
+        require!(self.game_status() == GameStatus::Active, ChessError::OpponentNotJoinedYet);
+        require!(
+            start_position_commitment(self.match_id_bytes(), secret) == commitment,
+            ChessError::StartPositionSecretMismatch
+        );
+        let creator_share = seed_to_index(secret, CHESS960_START_POSITIONS);
+        self.start_position_index = (self.start_position_index % CHESS960_START_POSITIONS + creator_share) % CHESS960_START_POSITIONS;
+        self.set_start_position_commitment(None);
+        self.reset_position();
+        Ok(())
+    }
+
+    pub fn tournament(&self) -> Option<Pubkey> {
+        (self.tournament != Pubkey::default()).then_some(self.tournament)
+    }
+
+    pub fn set_tournament(&mut self, tournament: Option<Pubkey>) {
+        self.tournament = tournament.unwrap_or_default();
+    }
+
+    // Arena: whether `color` halved their move timeout for a bonus point
+    pub fn berserk(&self, color: PlayerColor) -> bool {
+        self.flag(Self::berserk_flag(color))
+    }
+
+    pub fn set_berserk(&mut self, color: PlayerColor) {
+        self.set_flag(Self::berserk_flag(color), true);
+    }
+
+    fn berserk_flag(color: PlayerColor) -> u16 {
+        match color {
+            PlayerColor::White => FLAG_WHITE_BERSERK,
+            PlayerColor::Black => FLAG_BLACK_BERSERK,
+        }
+    }
+
+    // Live in a MagicBlock ephemeral rollup until undelegate_match commits it back
+    pub fn delegated(&self) -> bool {
+        self.flag(FLAG_DELEGATED)
+    }
+
+    pub fn set_delegated(&mut self, delegated: bool) {
+        self.set_flag(FLAG_DELEGATED, delegated);
+    }
+
+    pub fn takebacks_enabled(&self) -> bool {
+        self.flag(FLAG_TAKEBACKS_ENABLED)
+    }
+
+    pub fn set_takebacks_enabled(&mut self, enabled: bool) {
+        self.set_flag(FLAG_TAKEBACKS_ENABLED, enabled);
+    }
+
+    pub fn takeback_requested_by(&self) -> Option<PlayerColor> {
+        self.takeback_requested_by.checked_sub(1).map(PlayerColor::from_code)
+    }
+
+    pub fn set_takeback_requested_by(&mut self, color: Option<PlayerColor>) {
+        self.takeback_requested_by = color.map_or(0, |color| color as u8 + 1);
+    }
+
+    pub fn payout_processed(&self) -> bool {
+        self.flag(FLAG_PAYOUT_PROCESSED)
+    }
+
+    pub fn set_payout_processed(&mut self, processed: bool) {
+        self.set_flag(FLAG_PAYOUT_PROCESSED, processed);
+    }
+
+    // Tournament games: record_tournament_result has counted this game
+    pub fn result_recorded(&self) -> bool {
+        self.flag(FLAG_RESULT_RECORDED)
+    }
+
+    pub fn set_result_recorded(&mut self, recorded: bool) {
+        self.set_flag(FLAG_RESULT_RECORDED, recorded);
+    }
+
+    pub fn is_concluded(&self) -> bool {
+        matches!(self.game_status(), GameStatus::WhiteWins | GameStatus::BlackWins | GameStatus::Draw)
+    }
+
+    pub fn rematch_requested_by(&self) -> Option<Pubkey> {
+        (self.rematch_requested_by != Pubkey::default()).then_some(self.rematch_requested_by)
+    }
+
+    pub fn set_rematch_requested_by(&mut self, player: Option<Pubkey>) {
+        self.rematch_requested_by = player.unwrap_or_default();
+    }
+
+    pub fn premove(&self) -> Option<Premove> {
+        Premove::unpack(self.premove)
+    }
+
+    pub fn set_premove(&mut self, premove: Option<Premove>) {
+        self.premove = Premove::pack(premove);
+    }
+
+    // Puts the pieces on the start squares for this match's variant and start_position_index,
+    // minus any odds pieces, and resets the per-game counters (White to move).
+    pub fn reset_position(&mut self) {
+        self.current_player_idx = 0;
+        let start_position = chess_logic::start_position(self.variant(), self.start_position_index);
+        chess_logic::write_position_to_match(&start_position, self);
+        let handicap_color = self.handicap_color();
+        if let Some((row, col)) = self.handicap().removed_square(handicap_color) {
+            self.set_piece_at(row, col, None);
+            if self.handicap() == Handicap::RookOdds {
+                let mut castling_rights = self.castling_rights();
+                match handicap_color {
+                    PlayerColor::White => castling_rights.white_queenside = None,
+                    PlayerColor::Black => castling_rights.black_queenside = None,
+                }
+                self.set_castling_rights(castling_rights);
+            }
+        }
+        self.set_takeback_requested_by(None);
+        self.set_premove(None);
+        self.undo_history = [PackedUndoRecord::default(); MAX_UNDO_PLIES];
+    }
+
+    // Per-move timeout for `color`: shorter for the side giving time odds, and halved after berserk.
+    pub fn move_timeout_for(&self, color: PlayerColor) -> i64 {
+        let timeout = match self.handicap() {
+            Handicap::TimeOdds { creator_move_timeout } if color == self.handicap_color() => creator_move_timeout,
+            _ => self.move_timeout_duration,
+        };
+        if self.berserk(color) { timeout / 2 } else { timeout }
+    }
+
+    // Number of plies that must be rewound so that `requester` is on move again
+    // with their last move taken back.
+    pub fn takeback_plies_for(&self, requester: PlayerColor) -> usize {
+        if self.current_turn() == requester { 2 } else { 1 }
+    }
+
+    // Whether the undo history still holds `requester`'s last move and any reply to it.
+    pub fn can_take_back(&self, requester: PlayerColor) -> bool {
+        self.undo_history[..self.takeback_plies_for(requester)].iter().all(|record| !record.is_empty())
+    }
+
+    // Checks that `requester` may offer a rematch of this concluded match. While one player's offer
+    // is pending the other can only accept it: a counter-offer would orphan the first player's
+    // stake approval. The same player may renew their offer, which replaces their own approval.
+    pub fn check_rematch_offer(&self, requester: Pubkey) -> Result<()> {
+        require!(requester == self.players[0] || requester == self.players[1], ChessError::NotAPlayer);
+        require!(self.players[1] != Pubkey::default(), ChessError::OpponentNotJoinedYet);
+        require!(
+            self.rematch_requested_by().is_none_or(|pending| pending == requester),
+            ChessError::RematchAlreadyRequested
+        );
+        Ok(())
+    }
+
+    // Sets up the color-swapped rematch of `original` in this freshly created match: same settings
+    // and stakes, the creator still giving any odds, already active with White's clock running.
+    pub fn start_rematch_of(&mut self, original: &ChessMatch, now: i64) -> Result<()> {
+        self.set_match_id(&original.rematch_match_id());
+        self.players = [original.players[1], original.players[0]];
+        self.set_variant(original.variant());
+        self.start_position_index = original.start_position_index; // Chess960: same position, colors swapped
+        self.set_handicap(original.handicap());
+        self.set_handicap_color(original.handicap_color().opponent()); // Still given by the same player
+        self.reset_position();
+
+        self.last_move_timestamp = now; // White's clock starts now
+        self.move_timeout_duration = original.move_timeout_duration;
+        self.set_join_deadline(None);
+        self.set_takebacks_enabled(original.takebacks_enabled());
+        self.set_rated(original.rated());
+
+        self.set_game_status(GameStatus::Active);
+        self.set_game_end_reason(None);
+
+        let stake = original.bet_amount_player_one;
+        self.betting_token_mint = original.betting_token_mint;
+        self.bet_amount_player_one = stake;
+        self.bet_amount_player_two = stake;
+        self.total_pot = stake.checked_mul(2).ok_or(ChessError::MathError)?;
+        self.platform_fee_basis_points = original.platform_fee_basis_points;
+        self.set_payout_processed(false);
+        self.set_rematch_requested_by(None);
+        Ok(())
+    }
+
+    // Match id of the rematch spawned from this match: hex of the first 16 bytes of
+    // sha256("rematch" || match_id), so it always fits MAX_MATCH_ID_LEN and can be chained.
+    // Being derived from the match id alone, it gives each match at most one rematch
+    // (accept_rematch cannot create the same PDA twice); play on by rematching the rematch.
+    pub fn rematch_match_id(&self) -> String {
+        let digest = hashv(&[b"rematch", self.match_id_bytes()]);
+        digest.to_bytes()[..MAX_MATCH_ID_LEN / 2]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
 }
@@ -6,3 +6,15 @@ pub struct EnPassantSquare {
     pub col: u8,
 }
 
+
+impl EnPassantSquare {
+    // row * 8 + col + 1, or 0 when there is no en passant target.
+    pub fn pack(square: Option<Self>) -> u8 {
+        square.map_or(0, |square| square.row * 8 + square.col + 1)
+    }
+
+    pub fn unpack(packed: u8) -> Option<Self> {
+        let index = packed.checked_sub(1)?;
+        Some(Self { row: index / 8, col: index % 8 })
+    }
+}
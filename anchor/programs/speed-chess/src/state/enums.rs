@@ -11,8 +11,24 @@ pub enum PieceType {
     King,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+impl PieceType {
+    // Inverse of `piece_type as u8`.
+    pub fn from_code(code: u8) -> Option<Self> {
+        Some(match code {
+            0 => PieceType::Pawn,
+            1 => PieceType::Knight,
+            2 => PieceType::Bishop,
+            3 => PieceType::Rook,
+            4 => PieceType::Queen,
+            5 => PieceType::King,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
 pub enum PlayerColor {
+    #[default]
     White,
     Black,
 }
@@ -24,10 +40,94 @@ impl PlayerColor {
             PlayerColor::Black => PlayerColor::White,
         }
     }
+
+    pub fn from_code(code: u8) -> Self {
+        if code == 0 { PlayerColor::White } else { PlayerColor::Black }
+    }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum Variant {
+    #[default]
+    Standard,
+    Chess960, // Fischer Random: back rank drawn from 960 start positions when the opponent joins
+    KingOfTheHill, // A king reaching d4, e4, d5 or e5 wins
+    ThreeCheck, // Giving check for the third time wins
+    Crazyhouse, // Captured pieces change sides and may be dropped back onto the board
+    Atomic, // Captures explode the capturer and neighbouring non-pawns; exploding the enemy king wins
+    Antichess, // Captures are compulsory, kings are ordinary pieces and losing everything wins
+}
+
+impl Variant {
+    pub fn from_code(code: u8) -> Self {
+        match code {
+            1 => Variant::Chess960,
+            2 => Variant::KingOfTheHill,
+            3 => Variant::ThreeCheck,
+            4 => Variant::Crazyhouse,
+            5 => Variant::Atomic,
+            6 => Variant::Antichess,
+            _ => Variant::Standard,
+        }
+    }
+}
+
+// Odds the match creator gives the opponent. Piece odds take material off the creator's side of
+// the standard setup; time odds give the creator a shorter per-move timeout than the opponent.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum Handicap {
+    #[default]
+    None,
+    PawnOdds,   // f-pawn removed
+    KnightOdds, // Queen's knight removed
+    RookOdds,   // Queen's rook removed, along with queenside castling
+    QueenOdds,
+    TimeOdds { creator_move_timeout: i64 }, // Seconds per move for the creator; the opponent keeps move_timeout_duration
+}
+
+impl Handicap {
+    // (row, col) of the piece removed from `color`'s side of the standard setup, if any.
+    pub fn removed_square(&self, color: PlayerColor) -> Option<(usize, usize)> {
+        let (back_row, pawn_row) = match color {
+            PlayerColor::White => (0, 1),
+            PlayerColor::Black => (7, 6),
+        };
+        match self {
+            Handicap::PawnOdds => Some((pawn_row, 5)),
+            Handicap::KnightOdds => Some((back_row, 1)),
+            Handicap::RookOdds => Some((back_row, 0)),
+            Handicap::QueenOdds => Some((back_row, 3)),
+            Handicap::None | Handicap::TimeOdds { .. } => None,
+        }
+    }
+
+    // (kind, creator_move_timeout) as ChessMatch stores them; the timeout is 0 except for time odds.
+    pub fn to_code(self) -> (u8, i64) {
+        match self {
+            Handicap::None => (0, 0),
+            Handicap::PawnOdds => (1, 0),
+            Handicap::KnightOdds => (2, 0),
+            Handicap::RookOdds => (3, 0),
+            Handicap::QueenOdds => (4, 0),
+            Handicap::TimeOdds { creator_move_timeout } => (5, creator_move_timeout),
+        }
+    }
+
+    pub fn from_code(kind: u8, creator_move_timeout: i64) -> Self {
+        match kind {
+            1 => Handicap::PawnOdds,
+            2 => Handicap::KnightOdds,
+            3 => Handicap::RookOdds,
+            4 => Handicap::QueenOdds,
+            5 => Handicap::TimeOdds { creator_move_timeout },
+            _ => Handicap::None,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
 pub enum GameStatus {
+    #[default]
     WaitingForOpponent,
     Active,
     WhiteWins,
@@ -35,6 +135,42 @@ pub enum GameStatus {
     Draw,
 }
 
+impl GameStatus {
+    pub fn from_code(code: u8) -> Self {
+        match code {
+            1 => GameStatus::Active,
+            2 => GameStatus::WhiteWins,
+            3 => GameStatus::BlackWins,
+            4 => GameStatus::Draw,
+            _ => GameStatus::WaitingForOpponent,
+        }
+    }
+}
+
+// Rating pool a match counts towards, from its per-move timeout.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum TimeControl {
+    Bullet, // Up to 15 seconds per move
+    Blitz,  // Up to a minute per move
+    Rapid,  // Longer, or no timeout at all
+}
+
+impl TimeControl {
+    pub const COUNT: usize = 3;
+
+    pub fn from_move_timeout(move_timeout_duration: i64) -> Self {
+        match move_timeout_duration {
+            1..=15 => TimeControl::Bullet,
+            16..=60 => TimeControl::Blitz,
+            _ => TimeControl::Rapid,
+        }
+    }
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
 pub enum GameEndReason {
     Checkmate,
@@ -42,15 +178,39 @@ pub enum GameEndReason {
     Resignation,
     Timeout,
     FiftyMoveRule,
+    KingOfTheHill, // King reached the centre (King of the Hill)
+    ThreeCheck, // Third check given (Three-Check)
+    KingExploded, // King caught in a capture's explosion (Atomic)
+    AllPiecesLost, // Winner has given away all of their pieces (Antichess)
+    StalemateWin, // Winner has no legal move left (Antichess); unlike Stalemate, not a draw
     // ThreefoldRepetition, // Potentially later
     // InsufficientMaterial, // Potentially later
 }
 
+impl GameEndReason {
+    pub fn from_code(code: u8) -> Option<Self> {
+        Some(match code {
+            0 => GameEndReason::Checkmate,
+            1 => GameEndReason::Stalemate,
+            2 => GameEndReason::Resignation,
+            3 => GameEndReason::Timeout,
+            4 => GameEndReason::FiftyMoveRule,
+            5 => GameEndReason::KingOfTheHill,
+            6 => GameEndReason::ThreeCheck,
+            7 => GameEndReason::KingExploded,
+            8 => GameEndReason::AllPiecesLost,
+            9 => GameEndReason::StalemateWin,
+            _ => return None,
+        })
+    }
+}
+
 // Result of a single move, used internally by chess_logic
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum MoveResult {
     Normal,
     Checkmate,      // Opponent is checkmated by this move
     Stalemate,      // Game is a stalemate after this move (includes 50-move rule for now)
+    VariantWin { winner: PlayerColor, reason: GameEndReason }, // A variant-specific win condition was met
     // Check,        // If you want to explicitly signal a check without ending the game
 }
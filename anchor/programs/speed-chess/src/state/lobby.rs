@@ -0,0 +1,45 @@
+// src/state/lobby.rs
+use anchor_lang::prelude::*;
+
+use crate::errors::ChessError;
+use crate::state::{Variant, MAX_MATCH_ID_LEN};
+
+pub const LOBBY_PAGE_CAPACITY: usize = 20;
+
+// One page of open challenges, PDA seeds [b"lobby", page_index LE]. Pages are created in order
+// by create_lobby_page, so a client can render the lobby by fetching pages 0, 1, ... until one is missing.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct LobbyPage {
+    pub page_index: u32,
+    pub bump: u8,
+    #[max_len(LOBBY_PAGE_CAPACITY)]
+    pub listings: Vec<LobbyListing>, // Oldest first
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct LobbyListing {
+    #[max_len(MAX_MATCH_ID_LEN)]
+    pub match_id: String,
+    pub creator: Pubkey,
+    pub creator_rating: Option<u16>, // Elo, if the creator has a profile
+    pub betting_token_mint: Pubkey,
+    pub bet_amount: u64,
+    pub move_timeout_duration: i64,
+    pub variant: Variant,
+    pub rated: bool,
+}
+
+impl LobbyPage {
+    // Appends a new challenge; a full page sends the creator to another page.
+    pub fn add_listing(&mut self, listing: LobbyListing) -> Result<()> {
+        require!(self.listings.len() < LOBBY_PAGE_CAPACITY, ChessError::LobbyPageFull);
+        self.listings.push(listing);
+        Ok(())
+    }
+
+    // Removes the listing for `match_id`, if present.
+    pub fn remove_listing(&mut self, match_id: &str) {
+        self.listings.retain(|listing| listing.match_id != match_id);
+    }
+}
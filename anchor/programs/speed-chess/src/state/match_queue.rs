@@ -0,0 +1,83 @@
+// src/state/match_queue.rs
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::errors::ChessError;
+use crate::state::MAX_MATCH_ID_LEN;
+
+pub const MAX_QUEUE_ENTRIES: usize = 16;
+pub const QUEUE_PLATFORM_FEE_BASIS_POINTS: u16 = 250; // Neither player configures a queued match, so the fee is fixed
+// The waiting player may have stepped away, so White's first move clock starts this long after pairing
+pub const QUEUE_FIRST_MOVE_GRACE_SECONDS: i64 = 60;
+
+// Players waiting for an opponent in one (mint, stake, move timeout) bucket,
+// PDA seeds [b"match_queue", mint, bet_amount LE, move_timeout_duration LE].
+// Waiting players' stakes sit in the queue escrow ([b"queue_escrow", match_queue]) until they are paired.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct MatchQueue {
+    pub betting_token_mint: Pubkey,
+    pub bet_amount: u64,
+    pub move_timeout_duration: i64,
+    pub bump: u8,
+    pub matches_created: u64,
+    #[max_len(MAX_QUEUE_ENTRIES)]
+    pub entries: Vec<QueueEntry>, // Oldest first
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct QueueEntry {
+    pub player: Pubkey,
+    pub rating: Option<u16>,        // Elo from the player's profile when they queued, if they have one
+    pub rating_window: Option<u16>, // Largest accepted rating gap; None accepts anyone
+    pub enqueued_at: i64,
+}
+
+impl QueueEntry {
+    // Two players can be paired unless both are rated and the gap exceeds either one's window.
+    pub fn is_compatible_with(&self, other: &QueueEntry) -> bool {
+        let (Some(rating), Some(other_rating)) = (self.rating, other.rating) else {
+            return true;
+        };
+        let gap = rating.abs_diff(other_rating);
+        accepts_gap(self.rating_window, gap) && accepts_gap(other.rating_window, gap)
+    }
+}
+
+fn accepts_gap(rating_window: Option<u16>, gap: u16) -> bool {
+    match rating_window {
+        Some(window) => gap <= window,
+        None => true,
+    }
+}
+
+impl MatchQueue {
+    // Queued games are rated, so they must time out: unlike initialize_match, 0 (untimed) is not allowed.
+    pub fn check_move_timeout(move_timeout_duration: i64) -> Result<()> {
+        require!(move_timeout_duration > 0, ChessError::InvalidTimeout);
+        Ok(())
+    }
+
+    // Match id of the next pairing: hex of the first 16 bytes of
+    // sha256("queue" || mint || bet_amount || move_timeout_duration || matches_created).
+    pub fn next_match_id(&self) -> String {
+        let digest = hashv(&[
+            b"queue",
+            self.betting_token_mint.as_ref(),
+            &self.bet_amount.to_le_bytes(),
+            &self.move_timeout_duration.to_le_bytes(),
+            &self.matches_created.to_le_bytes(),
+        ]);
+        digest.to_bytes()[..MAX_MATCH_ID_LEN / 2]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    // Index of the longest-waiting player who can be paired with `entry`.
+    pub fn find_opponent(&self, entry: &QueueEntry) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|waiting| waiting.player != entry.player && waiting.is_compatible_with(entry))
+    }
+}
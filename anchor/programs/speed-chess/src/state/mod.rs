@@ -3,9 +3,27 @@ pub mod piece;
 pub mod enums;
 pub mod castling_rights;
 pub mod en_passant_square;
+pub mod undo_record;
+pub mod move_history;
+pub mod pocket;
+pub mod player_profile;
+pub mod match_queue;
+pub mod lobby;
+pub mod tournament;
+pub mod session_token;
+pub mod premove;
 
 pub use chess_match::*;
 pub use piece::*;
 pub use enums::*;
 pub use castling_rights::*;
 pub use en_passant_square::*;
+pub use undo_record::*;
+pub use move_history::*;
+pub use pocket::*;
+pub use player_profile::*;
+pub use match_queue::*;
+pub use lobby::*;
+pub use tournament::*;
+pub use session_token::*;
+pub use premove::*;
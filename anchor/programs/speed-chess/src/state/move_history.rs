@@ -0,0 +1,98 @@
+// src/state/move_history.rs
+use anchor_lang::prelude::*;
+use crate::state::{PieceType, MAX_MATCH_ID_LEN};
+
+// Packed move layout (one u32 per ply, least significant bits first):
+//   bits  0..6   from square (row * 8 + col)
+//   bits  6..12  to square   (row * 8 + col)
+//   bits 12..15  promotion   (0 = none, 1 = knight, 2 = bishop, 3 = rook, 4 = queen, 5 = pawn)
+//   A Crazyhouse drop has from == to and the dropped piece in the promotion field.
+//   bits 15..32  clock snapshot: seconds the mover spent on this move (saturating)
+const SQUARE_BITS: u32 = 6;
+const PROMOTION_BITS: u32 = 3;
+const TO_SHIFT: u32 = SQUARE_BITS;
+const PROMOTION_SHIFT: u32 = 2 * SQUARE_BITS;
+const CLOCK_SHIFT: u32 = PROMOTION_SHIFT + PROMOTION_BITS;
+pub const MAX_CLOCK_SNAPSHOT_SECONDS: u32 = (1 << (32 - CLOCK_SHIFT)) - 1;
+
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct MoveHistory {
+    #[max_len(MAX_MATCH_ID_LEN)]
+    pub match_id: String,
+    pub bump: u8,
+    #[max_len(0)] // Grows by one u32 per ply; make_move reallocates the account as it goes
+    pub moves: Vec<u32>,
+}
+
+// A single ply decoded from MoveHistory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecordedMove {
+    pub from_row: u8,
+    pub from_col: u8,
+    pub to_row: u8,
+    pub to_col: u8,
+    pub promotion: Option<PieceType>,
+    pub clock_seconds: u32,
+}
+
+pub const DELEGATED_MOVE_CAPACITY: usize = 600; // Plies pre-funded by delegate_match so the rollup never charges a payer
+
+impl MoveHistory {
+    // Account size (including discriminator) needed to hold `num_moves` packed moves.
+    pub fn space_for(num_moves: usize) -> usize {
+        8 + Self::INIT_SPACE + num_moves * std::mem::size_of::<u32>()
+    }
+
+    pub fn push(&mut self, recorded_move: &RecordedMove) {
+        self.moves.push(pack_move(recorded_move));
+    }
+
+    // Decodes the stored plies back into a move list, oldest first.
+    pub fn decode_moves(&self) -> Vec<RecordedMove> {
+        self.moves.iter().map(|&packed| unpack_move(packed)).collect()
+    }
+}
+
+// Decodes raw MoveHistory account data (as fetched over RPC, discriminator included).
+pub fn decode_move_history(account_data: &[u8]) -> Result<Vec<RecordedMove>> {
+    let history = MoveHistory::try_deserialize(&mut &account_data[..])?;
+    Ok(history.decode_moves())
+}
+
+pub fn pack_move(recorded_move: &RecordedMove) -> u32 {
+    let from = (recorded_move.from_row as u32) * 8 + recorded_move.from_col as u32;
+    let to = (recorded_move.to_row as u32) * 8 + recorded_move.to_col as u32;
+    let promotion = match recorded_move.promotion {
+        Some(PieceType::Knight) => 1,
+        Some(PieceType::Bishop) => 2,
+        Some(PieceType::Rook) => 3,
+        Some(PieceType::Queen) => 4,
+        Some(PieceType::Pawn) => 5,
+        _ => 0,
+    };
+    let clock = recorded_move.clock_seconds.min(MAX_CLOCK_SNAPSHOT_SECONDS);
+    from | (to << TO_SHIFT) | (promotion << PROMOTION_SHIFT) | (clock << CLOCK_SHIFT)
+}
+
+pub fn unpack_move(packed: u32) -> RecordedMove {
+    let square_mask = (1 << SQUARE_BITS) - 1;
+    let from = packed & square_mask;
+    let to = (packed >> TO_SHIFT) & square_mask;
+    let promotion = match (packed >> PROMOTION_SHIFT) & ((1 << PROMOTION_BITS) - 1) {
+        1 => Some(PieceType::Knight),
+        2 => Some(PieceType::Bishop),
+        3 => Some(PieceType::Rook),
+        4 => Some(PieceType::Queen),
+        5 => Some(PieceType::Pawn),
+        _ => None,
+    };
+    RecordedMove {
+        from_row: (from / 8) as u8,
+        from_col: (from % 8) as u8,
+        to_row: (to / 8) as u8,
+        to_col: (to % 8) as u8,
+        promotion,
+        clock_seconds: packed >> CLOCK_SHIFT,
+    }
+}
@@ -7,3 +7,13 @@ pub struct Piece {
     pub piece_type: PieceType,
     pub color: PlayerColor,
 }
+
+// Board nibble: 0 for an empty square, otherwise 1 (pawn) to 6 (king), plus 8 for Black.
+pub fn pack_piece(piece: Option<Piece>) -> u8 {
+    piece.map_or(0, |piece| (piece.piece_type as u8 + 1) | ((piece.color as u8) << 3))
+}
+
+pub fn unpack_piece(nibble: u8) -> Option<Piece> {
+    let piece_type = PieceType::from_code((nibble & 0b0111).checked_sub(1)?)?;
+    Some(Piece { piece_type, color: PlayerColor::from_code(nibble >> 3 & 1) })
+}
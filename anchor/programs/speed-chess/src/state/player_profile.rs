@@ -0,0 +1,67 @@
+// src/state/player_profile.rs
+use anchor_lang::prelude::*;
+
+use crate::state::TimeControl;
+use crate::utils::{
+    DEFAULT_GLICKO_RATING, DEFAULT_GLICKO_VOLATILITY, LOSS_SCORE_MILLI, MAX_GLICKO_DEVIATION, WIN_SCORE_MILLI,
+};
+
+// Glicko-2 rating in one time-control pool; rating, deviation and volatility are fixed-point (GLICKO_ONE = 1.0).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct GlickoRating {
+    pub rating: i64,
+    pub deviation: i64,
+    pub volatility: i64,
+    pub games_played: u32,
+    pub last_game_timestamp: i64, // 0 until the first game in this pool
+}
+
+impl Default for GlickoRating {
+    fn default() -> Self {
+        Self {
+            rating: DEFAULT_GLICKO_RATING,
+            deviation: MAX_GLICKO_DEVIATION,
+            volatility: DEFAULT_GLICKO_VOLATILITY,
+            games_played: 0,
+            last_game_timestamp: 0,
+        }
+    }
+}
+
+// Per-wallet rating and record, PDA seeds [b"player_profile", player].
+// Created on the player's first settled rated game and updated by process_match_settlement.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct PlayerProfile {
+    pub player: Pubkey,
+    pub bump: u8,
+    pub rating: u16, // Elo
+    pub games_played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub glicko: [GlickoRating; TimeControl::COUNT], // Indexed by TimeControl::index
+}
+
+impl PlayerProfile {
+    // Elo rating held by a profile PDA passed unchecked, or None while the profile has not been created.
+    pub fn rating_of(profile_info: &AccountInfo) -> Result<Option<u16>> {
+        if profile_info.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*profile_info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        let profile = Self::try_deserialize(&mut &profile_info.try_borrow_data()?[..])?;
+        Ok(Some(profile.rating))
+    }
+
+    // Records one finished game; `score_milli` is WIN/DRAW/LOSS_SCORE_MILLI.
+    pub fn record_game(&mut self, new_rating: u16, score_milli: i32) {
+        self.rating = new_rating;
+        self.games_played = self.games_played.saturating_add(1);
+        match score_milli {
+            WIN_SCORE_MILLI => self.wins = self.wins.saturating_add(1),
+            LOSS_SCORE_MILLI => self.losses = self.losses.saturating_add(1),
+            _ => self.draws = self.draws.saturating_add(1),
+        }
+    }
+}
@@ -0,0 +1,13 @@
+// src/state/pocket.rs
+use anchor_lang::prelude::*;
+
+// Captured pieces a side holds in hand and may drop back onto the board (Crazyhouse).
+#[zero_copy]
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Pocket {
+    pub pawns: u8,
+    pub knights: u8,
+    pub bishops: u8,
+    pub rooks: u8,
+    pub queens: u8,
+}
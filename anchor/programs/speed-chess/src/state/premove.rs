@@ -0,0 +1,49 @@
+// src/state/premove.rs
+use anchor_lang::prelude::*;
+use crate::state::{PieceType, PlayerColor};
+
+const DROP_SHIFT: u8 = 4;
+
+// A move queued by the player who is not on move, tried as soon as the opponent's move lands.
+// Same encoding as MakeMoveArgs: a Crazyhouse drop names the piece in drop_piece and from_* is ignored.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct Premove {
+    pub color: PlayerColor, // Side that queued it
+    pub from_row: u8,
+    pub from_col: u8,
+    pub to_row: u8,
+    pub to_col: u8,
+    pub promotion: Option<PieceType>,
+    pub drop_piece: Option<PieceType>,
+}
+
+impl Premove {
+    // [from square + 1 (0 = no premove), to square, promotion | drop piece << 4, color], with
+    // pieces as PieceType code + 1 (0 = none).
+    pub fn pack(premove: Option<Self>) -> [u8; 4] {
+        let Some(premove) = premove else {
+            return [0; 4];
+        };
+        let piece_code = |piece: Option<PieceType>| piece.map_or(0, |piece_type| piece_type as u8 + 1);
+        [
+            premove.from_row * 8 + premove.from_col + 1,
+            premove.to_row * 8 + premove.to_col,
+            piece_code(premove.promotion) | (piece_code(premove.drop_piece) << DROP_SHIFT),
+            premove.color as u8,
+        ]
+    }
+
+    pub fn unpack(packed: [u8; 4]) -> Option<Self> {
+        let from = packed[0].checked_sub(1)?;
+        let piece = |code: u8| code.checked_sub(1).and_then(PieceType::from_code);
+        Some(Self {
+            color: PlayerColor::from_code(packed[3]),
+            from_row: from / 8,
+            from_col: from % 8,
+            to_row: packed[1] / 8,
+            to_col: packed[1] % 8,
+            promotion: piece(packed[2] & 0x0f),
+            drop_piece: piece(packed[2] >> DROP_SHIFT),
+        })
+    }
+}
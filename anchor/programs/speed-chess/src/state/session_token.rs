@@ -0,0 +1,35 @@
+// src/state/session_token.rs
+use anchor_lang::prelude::*;
+
+use crate::errors::ChessError;
+
+pub const MAX_SESSION_DURATION: i64 = 24 * 60 * 60; // Seconds a session key may stay valid
+
+// Ephemeral key a player authorizes to act for them in a single match, PDA seeds
+// [b"session", chess_match, session_signer]. Only make_move, set_premove and resign_game accept it;
+// everything touching escrow or settlement still needs the player's wallet.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct SessionToken {
+    pub chess_match: Pubkey,
+    pub player: Pubkey,
+    pub session_signer: Pubkey,
+    pub expires_at: i64, // Unix timestamp
+    pub bump: u8,
+}
+
+impl SessionToken {
+    // Wallet a gameplay instruction acts for: the signer itself, or the player behind a live session
+    // key for this match.
+    pub fn acting_player(session_token: Option<&SessionToken>, signer: &Pubkey, chess_match: &Pubkey, now: i64) -> Result<Pubkey> {
+        let Some(session) = session_token else {
+            return Ok(*signer);
+        };
+        require!(
+            session.session_signer == *signer && session.chess_match == *chess_match,
+            ChessError::InvalidSessionToken
+        );
+        require!(now < session.expires_at, ChessError::SessionExpired);
+        Ok(session.player)
+    }
+}
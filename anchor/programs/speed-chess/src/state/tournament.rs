@@ -0,0 +1,201 @@
+// src/state/tournament.rs
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::state::{Handicap, PlayerColor, MAX_MATCH_ID_LEN};
+
+pub const MAX_TOURNAMENT_ID_LEN: usize = 32;
+pub const MAX_TOURNAMENT_PLAYERS: usize = 32;
+pub const MAX_PAYOUT_PLACES: usize = 4; // Champion, finalist, semifinalists, quarterfinalists (Swiss: 1st to 4th)
+pub const MAX_SWISS_ROUNDS: u8 = 9;
+pub const BYE: u8 = u8::MAX; // Opponent index recorded for a Swiss bye
+// Knockout Armageddon replay of a drawn game: Black gets this share of the move timeout and wins a draw
+pub const ARMAGEDDON_BLACK_TIMEOUT_PERCENT: i64 = 80;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum TournamentStatus {
+    #[default]
+    Registration,
+    InProgress,
+    Completed,
+    PrizesDistributed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum TournamentFormat {
+    #[default]
+    Knockout,
+    // Everyone plays every round; pairings come from utils::swiss_pairings
+    Swiss { rounds: u8 },
+    // Continuous pairing between unix timestamps starts_at and ends_at; pairings come from utils::arena_pairing
+    Arena { starts_at: i64, ends_at: i64 },
+}
+
+// Knockout or Swiss tournament, PDA seeds [b"tournament", tournament_id].
+// Entry fees are held in the tournament vault ([b"tournament_vault", tournament_id]) until the end.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct Tournament {
+    #[max_len(MAX_TOURNAMENT_ID_LEN)]
+    pub tournament_id: String,
+    pub organizer: Pubkey,
+    pub bump: u8,
+    pub status: TournamentStatus,
+    pub format: TournamentFormat,
+
+    pub betting_token_mint: Pubkey,
+    pub entry_fee: u64,
+    pub prize_pool: u64,
+    pub platform_fee_basis_points: u16, // Taken once from the prize pool, not per game
+    // Share of the pool (after the platform fee) in basis points for each player finishing at that place:
+    // [champion, finalist, each semifinalist, each quarterfinalist]. Shares of places nobody reached go to the champion.
+    // Swiss: one share per final place, 1st to 4th; players tied on score split the shares of the places they occupy.
+    #[max_len(MAX_PAYOUT_PLACES)]
+    pub payout_table: Vec<u16>,
+    pub move_timeout_duration: i64,
+
+    #[max_len(MAX_TOURNAMENT_PLAYERS)]
+    pub players: Vec<TournamentPlayer>, // In registration order until start_tournament sorts them by seed
+    pub current_round: u8, // 1-based; 0 before the start
+    pub total_rounds: u8,
+    // Current round in bracket order: slots 2i and 2i+1 meet (the first plays White).
+    // Pubkey::default() marks a bye.
+    #[max_len(MAX_TOURNAMENT_PLAYERS)]
+    pub bracket: Vec<Pubkey>,
+    // Winner of each pairing in the current round, Pubkey::default() until recorded (knockout only)
+    #[max_len(MAX_TOURNAMENT_PLAYERS / 2)]
+    pub round_winners: Vec<Pubkey>,
+    pub results_pending: u8, // Swiss games of the current round still to be recorded
+    pub armageddon_pairings: u16, // Knockout: bit per pairing of the current round whose game was drawn and is replayed as Armageddon
+    pub games_created: u16,  // Arena games created so far; numbers the next one
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct TournamentPlayer {
+    pub player: Pubkey,
+    pub rating: Option<u16>,     // Elo at registration, used for seeding
+    pub eliminated_in_round: u8, // 0 while still in (and for the champion)
+    pub score: u16,              // Swiss score in half points, or arena points
+    #[max_len(MAX_SWISS_ROUNDS)]
+    pub history: Vec<SwissGame>, // One entry per Swiss round played
+    pub arena: ArenaPlayer,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct ArenaPlayer {
+    pub current_game: Option<u16>, // Arena game in progress; the player is idle when None
+    pub win_streak: u8,            // Consecutive wins; two or more puts the player on fire
+    pub last_opponent: u8,         // Index into Tournament::players, or BYE before the first game
+    pub color_balance: i8,         // Games as White minus games as Black
+    pub games_played: u16,
+}
+
+impl Default for ArenaPlayer {
+    fn default() -> Self {
+        Self { current_game: None, win_streak: 0, last_opponent: BYE, color_balance: 0, games_played: 0 }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct SwissGame {
+    pub opponent: u8,               // Index into Tournament::players, or BYE
+    pub color: Option<PlayerColor>, // None for a bye
+    pub result: u8,                 // Half points: 2 win (or bye), 1 draw, 0 loss
+}
+
+// One row of the Swiss standings; every value is in half points, and Sonneborn-Berger in
+// half points times half points (a win over a player on 3 points adds 2 * 6 = 12).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwissStanding {
+    pub player: Pubkey,
+    pub score: u16,
+    pub buchholz: u16,
+    pub sonneborn_berger: u32,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArenaStanding {
+    pub player: Pubkey,
+    pub score: u16,
+    pub games_played: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TournamentPrize {
+    pub player: Pubkey,
+    pub amount: u64,
+}
+
+impl Tournament {
+    // Match id for pairing `pairing` of `round`: hex of the first 16 bytes of
+    // sha256("tournament" || tournament_id || round || pairing).
+    pub fn round_match_id(&self, round: u8, pairing: u8) -> String {
+        let digest = hashv(&[b"tournament", self.tournament_id.as_bytes(), &[round], &[pairing]]);
+        digest.to_bytes()[..MAX_MATCH_ID_LEN / 2]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    // Match id of arena game number `game`: hex of the first 16 bytes of
+    // sha256("arena" || tournament_id || game as little-endian u16).
+    pub fn arena_match_id(&self, game: u16) -> String {
+        let digest = hashv(&[b"arena", self.tournament_id.as_bytes(), &game.to_le_bytes()]);
+        digest.to_bytes()[..MAX_MATCH_ID_LEN / 2]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    // Match id of the Armageddon game replaying a drawn knockout pairing: hex of the first 16 bytes of
+    // sha256("armageddon" || tournament_id || round || pairing).
+    pub fn armageddon_match_id(&self, round: u8, pairing: u8) -> String {
+        let digest = hashv(&[b"armageddon", self.tournament_id.as_bytes(), &[round], &[pairing]]);
+        digest.to_bytes()[..MAX_MATCH_ID_LEN / 2]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    pub fn is_armageddon(&self, pairing: u8) -> bool {
+        u32::from(pairing) < u16::BITS && self.armageddon_pairings & (1 << pairing) != 0
+    }
+
+    pub fn set_armageddon(&mut self, pairing: u8) {
+        self.armageddon_pairings |= 1 << pairing;
+    }
+
+    // Match id of the game a pairing of the current round is playing now.
+    pub fn pairing_match_id(&self, pairing: u8) -> String {
+        if self.is_armageddon(pairing) {
+            self.armageddon_match_id(self.current_round, pairing)
+        } else {
+            self.round_match_id(self.current_round, pairing)
+        }
+    }
+
+    // Handicap of a pairing's current game: Armageddon time odds against Black, or none.
+    pub fn pairing_handicap(&self, pairing: u8) -> Handicap {
+        if self.is_armageddon(pairing) {
+            let creator_move_timeout = (self.move_timeout_duration * ARMAGEDDON_BLACK_TIMEOUT_PERCENT / 100).max(1);
+            Handicap::TimeOdds { creator_move_timeout }
+        } else {
+            Handicap::None
+        }
+    }
+
+    // Players (White, Black) of a pairing in the current round.
+    pub fn pairing(&self, pairing: u8) -> Option<(Pubkey, Pubkey)> {
+        let white = *self.bracket.get(2 * pairing as usize)?;
+        let black = *self.bracket.get(2 * pairing as usize + 1)?;
+        Some((white, black))
+    }
+
+    pub fn player_index(&self, player: &Pubkey) -> Option<usize> {
+        self.players.iter().position(|entry| entry.player == *player)
+    }
+
+    pub fn player_mut(&mut self, player: &Pubkey) -> Option<&mut TournamentPlayer> {
+        self.players.iter_mut().find(|entry| entry.player == *player)
+    }
+}
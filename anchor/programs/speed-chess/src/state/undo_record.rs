@@ -0,0 +1,95 @@
+// src/state/undo_record.rs
+use anchor_lang::prelude::*;
+use crate::state::{pack_piece, unpack_piece, CastlingRights, EnPassantSquare, Piece};
+
+pub const MAX_UNDO_PLIES: usize = 2; // A takeback rewinds at most the requester's move and the reply to it
+
+// Everything validate_and_apply_move overwrites that cannot be recomputed from the new position.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct UndoRecord {
+    pub from_row: u8,
+    pub from_col: u8,
+    pub to_row: u8,
+    pub to_col: u8,
+    pub moved_piece: Piece,             // As it stood on the source square (still a pawn for promotions)
+    pub captured_piece: Option<Piece>,
+    pub captured_was_promoted: bool,    // Crazyhouse: the captured piece went to the pocket as a pawn
+    pub exploded: [Option<Piece>; 8],   // Atomic: pieces blown off the squares around the capture
+    pub was_en_passant: bool,           // Captured pawn stood beside the source square, not on the target
+    pub previous_castling_rights: CastlingRights,
+    pub previous_en_passant_target: Option<EnPassantSquare>,
+    pub previous_halfmove_clock: u8,
+}
+
+const CAPTURED_WAS_PROMOTED: u8 = 1 << 0;
+const WAS_EN_PASSANT: u8 = 1 << 1;
+
+// UndoRecord as ChessMatch stores it: squares as row * 8 + col, pieces as board nibbles
+// (see pack_piece). An empty slot is all zeroes, told apart by `present`.
+#[zero_copy]
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PackedUndoRecord {
+    pub present: u8,
+    pub from: u8,
+    pub to: u8,
+    pub pieces: u8,                        // Moved piece in the low nibble, captured piece in the high nibble
+    pub exploded: [u8; 4],                 // Two squares per byte, low nibble first
+    pub flags: u8,                         // CAPTURED_WAS_PROMOTED | WAS_EN_PASSANT
+    pub previous_castling_rights: [u8; 4], // CastlingRights::pack
+    pub previous_en_passant_target: u8,    // EnPassantSquare::pack
+    pub previous_halfmove_clock: u8,
+}
+
+impl PackedUndoRecord {
+    pub fn pack(record: Option<&UndoRecord>) -> Self {
+        let Some(record) = record else {
+            return Self::default();
+        };
+        let mut exploded = [0u8; 4];
+        for (index, piece) in record.exploded.iter().enumerate() {
+            exploded[index / 2] |= pack_piece(*piece) << (4 * (index % 2));
+        }
+        let mut flags = 0;
+        if record.captured_was_promoted {
+            flags |= CAPTURED_WAS_PROMOTED;
+        }
+        if record.was_en_passant {
+            flags |= WAS_EN_PASSANT;
+        }
+        Self {
+            present: 1,
+            from: record.from_row * 8 + record.from_col,
+            to: record.to_row * 8 + record.to_col,
+            pieces: pack_piece(Some(record.moved_piece)) | (pack_piece(record.captured_piece) << 4),
+            exploded,
+            flags,
+            previous_castling_rights: record.previous_castling_rights.pack(),
+            previous_en_passant_target: EnPassantSquare::pack(record.previous_en_passant_target),
+            previous_halfmove_clock: record.previous_halfmove_clock,
+        }
+    }
+
+    pub fn unpack(&self) -> Option<UndoRecord> {
+        if self.present == 0 {
+            return None;
+        }
+        Some(UndoRecord {
+            from_row: self.from / 8,
+            from_col: self.from % 8,
+            to_row: self.to / 8,
+            to_col: self.to % 8,
+            moved_piece: unpack_piece(self.pieces & 0x0f)?,
+            captured_piece: unpack_piece(self.pieces >> 4),
+            captured_was_promoted: self.flags & CAPTURED_WAS_PROMOTED != 0,
+            exploded: std::array::from_fn(|index| unpack_piece(self.exploded[index / 2] >> (4 * (index % 2)) & 0x0f)),
+            was_en_passant: self.flags & WAS_EN_PASSANT != 0,
+            previous_castling_rights: CastlingRights::unpack(self.previous_castling_rights),
+            previous_en_passant_target: EnPassantSquare::unpack(self.previous_en_passant_target),
+            previous_halfmove_clock: self.previous_halfmove_clock,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.present == 0
+    }
+}
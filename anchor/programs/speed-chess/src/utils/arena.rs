@@ -0,0 +1,97 @@
+// src/utils/arena.rs
+// Arena pairing and scoring. Like the Swiss helpers, these work on Tournament::players indices.
+use anchor_lang::prelude::*;
+
+use crate::state::{ArenaStanding, PlayerColor, TournamentPlayer};
+use crate::utils::tied_prize_amounts;
+
+pub const ARENA_ON_FIRE_STREAK: u8 = 2;   // Wins in a row after which a player's points are doubled
+pub const ARENA_BERSERK_MIN_MOVES: u16 = 7; // A berserk win earns its bonus only after this many moves
+
+// Idle players (no arena game in progress) ranked by score, then registration order.
+fn idle_ranking(players: &[TournamentPlayer]) -> Vec<u8> {
+    let mut idle: Vec<u8> = (0..players.len() as u8)
+        .filter(|&index| players[index as usize].arena.current_game.is_none())
+        .collect();
+    idle.sort_by_key(|&index| (std::cmp::Reverse(players[index as usize].score), index));
+    idle
+}
+
+// Next arena pairing as (White, Black) indices: the highest-ranked idle player meets the next idle
+// player by rank, skipping an immediate rematch unless nobody else is idle. White goes to the
+// player who has had it less often, then to the higher-ranked one.
+pub fn arena_pairing(players: &[TournamentPlayer]) -> Option<(u8, u8)> {
+    let idle = idle_ranking(players);
+    let is_rematch = |a: u8, b: u8| players[a as usize].arena.last_opponent == b;
+    let (higher, lower) = idle
+        .iter()
+        .enumerate()
+        .find_map(|(position, &a)| {
+            idle[position + 1..].iter().find(|&&b| !is_rematch(a, b)).map(|&b| (a, b))
+        })
+        .or_else(|| Some((*idle.first()?, *idle.get(1)?)))?;
+
+    if players[lower as usize].arena.color_balance < players[higher as usize].arena.color_balance {
+        Some((lower, higher))
+    } else {
+        Some((higher, lower))
+    }
+}
+
+// Points for one arena game, with `result` in half points (2 win, 1 draw, 0 loss): doubled while
+// on fire, plus one for a berserk win that lasted long enough.
+pub fn arena_points(win_streak: u8, result: u8, berserked: bool, fullmove_number: u16) -> u16 {
+    let mut points = u16::from(result);
+    if win_streak >= ARENA_ON_FIRE_STREAK {
+        points *= 2;
+    }
+    if berserked && result == 2 && fullmove_number > ARENA_BERSERK_MIN_MOVES {
+        points += 1;
+    }
+    points
+}
+
+// Scores a finished arena game for one player and makes them idle again. Returns the points earned.
+pub fn record_arena_game(
+    player: &mut TournamentPlayer,
+    opponent: u8,
+    color: PlayerColor,
+    result: u8,
+    berserked: bool,
+    fullmove_number: u16,
+) -> u16 {
+    let points = arena_points(player.arena.win_streak, result, berserked, fullmove_number);
+    player.score += points;
+    player.arena.win_streak = if result == 2 { player.arena.win_streak.saturating_add(1) } else { 0 };
+    player.arena.current_game = None;
+    player.arena.last_opponent = opponent;
+    player.arena.color_balance += match color {
+        PlayerColor::White => 1,
+        PlayerColor::Black => -1,
+    };
+    player.arena.games_played += 1;
+    points
+}
+
+// Standings by points, then registration order (players who never played still appear, on zero).
+pub fn arena_standings(players: &[TournamentPlayer]) -> Vec<ArenaStanding> {
+    let mut order: Vec<usize> = (0..players.len()).collect();
+    order.sort_by_key(|&index| (std::cmp::Reverse(players[index].score), index));
+    order
+        .into_iter()
+        .map(|index| ArenaStanding {
+            player: players[index].player,
+            score: players[index].score,
+            games_played: players[index].arena.games_played,
+        })
+        .collect()
+}
+
+// Prizes by final arena points; players tied on points split, as in utils::tied_prize_amounts.
+pub fn arena_prize_amounts(players: &[TournamentPlayer], payout_table: &[u16], pool: u64) -> Vec<(Pubkey, u64)> {
+    let ranked: Vec<(Pubkey, u16)> = arena_standings(players)
+        .iter()
+        .map(|standing| (standing.player, standing.score))
+        .collect();
+    tied_prize_amounts(&ranked, payout_table, pool)
+}
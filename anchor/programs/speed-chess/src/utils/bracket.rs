@@ -0,0 +1,82 @@
+// src/utils/bracket.rs
+// Single-elimination bracket helpers shared by the tournament instructions.
+use anchor_lang::prelude::*;
+
+use crate::state::TournamentPlayer;
+use crate::utils::DEFAULT_RATING;
+
+// Seeds (1-based) in bracket slot order for a bracket of `size` players (a power of two), so that
+// seed 1 can only meet seed 2 in the final: [1, 2] -> [1, 4, 2, 3] -> [1, 8, 4, 5, 2, 7, 3, 6] ...
+pub fn seeding_order(size: usize) -> Vec<usize> {
+    let mut order = vec![1];
+    while order.len() < size {
+        let next_size = order.len() * 2;
+        order = order.iter().flat_map(|&seed| [seed, next_size + 1 - seed]).collect();
+    }
+    order
+}
+
+// Sorts players by rating (unrated players count as DEFAULT_RATING), keeping registration order for ties.
+pub fn sort_by_seed(players: &mut [TournamentPlayer]) {
+    players.sort_by_key(|entry| std::cmp::Reverse(entry.rating.unwrap_or(DEFAULT_RATING)));
+}
+
+// First-round bracket for players already sorted by seed; missing seeds become byes for the top seeds.
+pub fn first_round_bracket(seeded_players: &[Pubkey]) -> Vec<Pubkey> {
+    let size = seeded_players.len().next_power_of_two().max(2);
+    seeding_order(size)
+        .into_iter()
+        .map(|seed| seeded_players.get(seed - 1).copied().unwrap_or_default())
+        .collect()
+}
+
+// Winners known before any game is played: a player facing a bye advances.
+pub fn winners_by_bye(bracket: &[Pubkey]) -> Vec<Pubkey> {
+    bracket
+        .chunks(2)
+        .map(|pair| match (pair[0], pair[1]) {
+            (white, black) if black == Pubkey::default() => white,
+            (white, black) if white == Pubkey::default() => black,
+            _ => Pubkey::default(),
+        })
+        .collect()
+}
+
+// Number of players finishing at payout place `place`: one champion, one finalist, two semifinalists, ...
+fn place_count(place: usize) -> u64 {
+    if place == 0 { 1 } else { 1 << (place - 1) }
+}
+
+// A payout table must hand out exactly the whole pool when every place is filled.
+pub fn payout_table_is_valid(payout_table: &[u16]) -> bool {
+    let total: u64 = payout_table
+        .iter()
+        .enumerate()
+        .map(|(place, &basis_points)| u64::from(basis_points) * place_count(place))
+        .sum();
+    !payout_table.is_empty() && total == 10_000
+}
+
+// Prizes for a finished bracket of `total_rounds`, out of `pool` (platform fee already taken):
+// the champion first, then every other paid player in seed order.
+// Shares of places nobody finished at (a short bracket) go to the champion, along with rounding dust.
+pub fn prize_amounts(players: &[TournamentPlayer], total_rounds: u8, payout_table: &[u16], pool: u64) -> Vec<(Pubkey, u64)> {
+    let Some(champion) = players.iter().find(|entry| entry.eliminated_in_round == 0) else {
+        return Vec::new();
+    };
+    let others: Vec<(Pubkey, u64)> = players
+        .iter()
+        .filter(|entry| entry.eliminated_in_round != 0)
+        .filter_map(|entry| {
+            let place = usize::from(total_rounds + 1 - entry.eliminated_in_round);
+            let basis_points = *payout_table.get(place)?;
+            Some((entry.player, pool * u64::from(basis_points) / 10_000))
+        })
+        .filter(|&(_, amount)| amount > 0)
+        .collect();
+
+    let paid: u64 = others.iter().map(|&(_, amount)| amount).sum();
+    let mut prizes = vec![(champion.player, pool - paid)];
+    prizes.extend(others);
+    prizes
+}
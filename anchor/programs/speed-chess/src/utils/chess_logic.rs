@@ -1,33 +1,27 @@
 // src/utils/chess_logic.rs
+// Thin adapter between the on-chain ChessMatch account and the speed-chess-core rules engine.
+// All move validation lives in the core crate; this module converts state types and maps errors.
 use anchor_lang::prelude::*;
+use speed_chess_core::{self as core_rules, CastleSide, GameResult, Position, RulesError, CHESS960_START_POSITIONS};
+
 use crate::errors::ChessError;
-use crate::state::{ChessMatch, MoveResult, PlayerColor, PieceType, EnPassantSquare, Piece, CastlingRights}; // Ensure all used state types are here
+use crate::state::{ChessMatch, GameEndReason, MoveResult, PlayerColor, PieceType, EnPassantSquare, Piece, CastlingRights, MoveHistory, PackedUndoRecord, Pocket, Premove, UndoRecord, Variant, MAX_UNDO_PLIES}; // Ensure all used state types are here
 
 pub fn initialize_chess_board() -> [[Option<Piece>; 8]; 8] {
-    let mut board = [[None; 8]; 8];
-    
-    for col in 0..8 {
-        board[1][col] = Some(Piece { piece_type: PieceType::Pawn, color: PlayerColor::White });
-        board[6][col] = Some(Piece { piece_type: PieceType::Pawn, color: PlayerColor::Black });
-    }
-    
-    let back_row_types = [
-        PieceType::Rook, PieceType::Knight, PieceType::Bishop, PieceType::Queen,
-        PieceType::King, PieceType::Bishop, PieceType::Knight, PieceType::Rook,
-    ];
-    
-    // Corrected loop for white back row
-    for (col, &piece_type) in back_row_types.iter().enumerate() {
-        board[0][col] = Some(Piece { piece_type, color: PlayerColor::White });
-    }
-    
-    for (col, &piece_type) in back_row_types.iter().enumerate() {
-        board[7][col] = Some(Piece { piece_type, color: PlayerColor::Black });
+    core_rules::starting_board().map(|row| row.map(|square| square.map(Into::into)))
+}
+
+// Start position of a match. Chess960 indices wrap modulo 960, so any stored value is a valid setup.
+pub fn start_position(variant: Variant, start_position_index: u16) -> Position {
+    match variant {
+        Variant::Chess960 => Position::chess960(start_position_index % CHESS960_START_POSITIONS)
+            .expect("index is below CHESS960_START_POSITIONS"),
+        _ => Position::with_variant(variant.into()),
     }
-    board
 }
 
 // --- Main function to validate and apply a chess move ---
+// A Crazyhouse drop is passed as from == to with the dropped piece in `promotion`.
 pub fn validate_and_apply_move(
     game_state: &mut ChessMatch,
     from_row: u8,
@@ -38,468 +32,328 @@ pub fn validate_and_apply_move(
     promotion: Option<PieceType>,
 ) -> Result<MoveResult> {
     // 0. Consistency check (optional but good practice)
-    if game_state.current_turn != player_color {
-        msg!("Inconsistency: player_color arg ({:?}) does not match game_state.current_turn ({:?})", player_color, game_state.current_turn);
+    if game_state.current_turn() != player_color {
+        msg!("Inconsistency: player_color arg ({:?}) does not match game_state.current_turn ({:?})", player_color, game_state.current_turn());
         return err!(ChessError::NotYourTurn); // Or a more specific internal error
     }
 
-    // 1. Basic pre-move validations
-    if from_row > 7 || from_col > 7 || to_row > 7 || to_col > 7 {
-        return err!(ChessError::InvalidMoveOutOfBounds);
-    }
-    if from_row == to_row && from_col == to_col {
-        return err!(ChessError::InvalidMoveIllegalPieceMovement);
-    }
+    let mut position = position_from_match(game_state);
+    let mv = core_move(from_row, from_col, to_row, to_col, promotion);
+    let undo = position.make_move(mv).map_err(chess_error)?;
+    write_position_to_match(&position, game_state);
 
-    let source_piece_data = game_state.board[from_row as usize][from_col as usize]
-        .as_ref()
-        .cloned() // Clone to avoid issues if game_state.board is modified before this is used again
-        .ok_or(error!(ChessError::InvalidMoveEmptySource))?;
+    // Remember how to rewind this ply (only the most recent MAX_UNDO_PLIES are kept)
+    push_undo_record(game_state, undo_record_from_core(&undo));
 
-    if source_piece_data.color != player_color {
-        return err!(ChessError::InvalidMoveNotYourPiece);
-    }
+    // --- Determine game result for the opponent (whose turn it now is) ---
+    Ok(match position.game_result() {
+        GameResult::Ongoing => MoveResult::Normal,
+        GameResult::Checkmate { .. } => MoveResult::Checkmate,
+        GameResult::Stalemate | GameResult::FiftyMoveRule => MoveResult::Stalemate,
+        GameResult::KingOfTheHill { winner } => MoveResult::VariantWin { winner: winner.into(), reason: GameEndReason::KingOfTheHill },
+        GameResult::ThreeChecks { winner } => MoveResult::VariantWin { winner: winner.into(), reason: GameEndReason::ThreeCheck },
+        GameResult::KingExploded { winner } => MoveResult::VariantWin { winner: winner.into(), reason: GameEndReason::KingExploded },
+        GameResult::AllPiecesLost { winner } => MoveResult::VariantWin { winner: winner.into(), reason: GameEndReason::AllPiecesLost },
+        GameResult::StalemateWin { winner } => MoveResult::VariantWin { winner: winner.into(), reason: GameEndReason::StalemateWin },
+    })
+}
 
-    let mut is_capture = false;
-    if let Some(target_piece_data) = game_state.board[to_row as usize][to_col as usize].as_ref() {
-        if target_piece_data.color == player_color {
-            return err!(ChessError::InvalidMoveCannotCaptureOwnPiece);
-        }
-        is_capture = true;
-    }
+// Plays the premove queued for the side now on move, if any, and clears the slot either way.
+// Returns None (leaving game_state untouched) when there is none or it is illegal in the new position.
+pub fn apply_premove(game_state: &mut ChessMatch) -> Option<(Premove, MoveResult)> {
+    let premove = game_state.premove()?;
+    game_state.set_premove(None);
+    if premove.color != game_state.current_turn() {
+        return None;
+    }
+    let (from_row, from_col, promotion) = match premove.drop_piece {
+        Some(piece_type) => (premove.to_row, premove.to_col, Some(piece_type)),
+        None => (premove.from_row, premove.from_col, premove.promotion),
+    };
+    validate_and_apply_move(game_state, from_row, from_col, premove.to_row, premove.to_col, premove.color, promotion)
+        .ok()
+        .map(|move_result| (premove, move_result))
+}
 
-    // 2. Validate piece-specific movement rules
-    if !is_legal_move_for_piece(
-        &game_state.board,
-        &source_piece_data,
-        from_row, from_col, to_row, to_col,
-        game_state.en_passant_target,
-        &game_state.castling_rights,
-        player_color,
-    ) {
-        return err!(ChessError::InvalidMoveIllegalPieceMovement);
-    }
+// --- Takeback Support ---
+fn push_undo_record(game_state: &mut ChessMatch, record: UndoRecord) {
+    game_state.undo_history.copy_within(0..MAX_UNDO_PLIES - 1, 1);
+    game_state.undo_history[0] = PackedUndoRecord::pack(Some(&record));
+}
 
-    let piece_type_moved = source_piece_data.piece_type;
-
-    // 3. Simulate the move and check if it leaves own king in check
-    let mut temp_board = game_state.board; // Copy board for simulation
-    let mut piece_to_move_temp = temp_board[from_row as usize][from_col as usize].take().unwrap();
-    
-    if piece_to_move_temp.piece_type == PieceType::Pawn {
-        if let Some(ep_square) = game_state.en_passant_target {
-            if ep_square.row == to_row && ep_square.col == to_col &&
-               (to_col as i8 - from_col as i8).abs() == 1 &&
-               (to_row as i8 - from_row as i8).abs() == 1
-            {
-                let captured_pawn_row = if player_color == PlayerColor::White { to_row - 1 } else { to_row + 1 };
-                temp_board[captured_pawn_row as usize][to_col as usize].take();
-            }
-        }
-    }
-    if piece_to_move_temp.piece_type == PieceType::King && (to_col as i8 - from_col as i8).abs() == 2 {
-        let (rook_from_col, rook_to_col) = if (to_col as i8 - from_col as i8) > 0 { (7, 5) } else { (0, 3) };
-        if let Some(rook) = temp_board[from_row as usize][rook_from_col as usize].take() {
-            temp_board[from_row as usize][rook_to_col as usize] = Some(rook);
-        }
-    }
-    let _captured_piece_temp = temp_board[to_row as usize][to_col as usize].replace(piece_to_move_temp);
+// Rewinds the most recent ply recorded by validate_and_apply_move.
+pub fn undo_last_move(game_state: &mut ChessMatch) -> Result<()> {
+    let record = game_state.undo_history[0].unpack().ok_or(error!(ChessError::NoMoveToTakeBack))?;
+    game_state.undo_history.copy_within(1..MAX_UNDO_PLIES, 0);
+    game_state.undo_history[MAX_UNDO_PLIES - 1] = PackedUndoRecord::default();
 
-    if is_king_in_check(&temp_board, player_color) {
-        return err!(ChessError::InvalidMoveLeavesKingInCheck);
-    }
+    let mut position = position_from_match(game_state);
+    position.unmake_move(&undo_from_record(&record));
+    write_position_to_match(&position, game_state);
+    Ok(())
+}
 
-    // --- Apply the move permanently to game_state ---
-    let previous_en_passant_target = game_state.en_passant_target.take(); 
-
-    let mut actual_captured_piece = game_state.board[to_row as usize][to_col as usize].take();
-    if piece_type_moved == PieceType::Pawn {
-        if let Some(ep_square) = previous_en_passant_target {
-            if ep_square.row == to_row && ep_square.col == to_col &&
-               (to_col as i8 - from_col as i8).abs() == 1 &&
-               (to_row as i8 - from_row as i8).abs() == 1
-            {
-                let captured_pawn_row = if player_color == PlayerColor::White { to_row - 1 } else { to_row + 1 };
-                actual_captured_piece = game_state.board[captured_pawn_row as usize][to_col as usize].take();
-                is_capture = true; 
-            }
-        }
+// Rewinds until `requester` is on move again with their last move taken back, popping the same
+// plies off the move history. Returns the number of plies undone.
+pub fn take_back(game_state: &mut ChessMatch, move_history: &mut MoveHistory, requester: PlayerColor) -> Result<usize> {
+    let plies = game_state.takeback_plies_for(requester);
+    for _ in 0..plies {
+        undo_last_move(game_state)?;
+        move_history.moves.pop(); // Space is reclaimed by the next make_move realloc
     }
+    Ok(plies)
+}
 
-    let mut piece_to_move_actual = game_state.board[from_row as usize][from_col as usize].take().unwrap();
+// Whether the side to move may play from -> to in the current position (promotion piece aside).
+// Does not modify game_state; used for move listings and notation.
+pub fn is_legal_move(game_state: &ChessMatch, from_row: u8, from_col: u8, to_row: u8, to_col: u8) -> bool {
+    position_from_match(game_state).is_legal(core_move(from_row, from_col, to_row, to_col, None))
+}
 
-    update_castling_rights(&mut game_state.castling_rights, &piece_to_move_actual, from_row, from_col);
+// Which side a king move castles on, if it is castling in the current position.
+// Chess960 castling is encoded as the king moving onto its own rook.
+pub fn castling_side(game_state: &ChessMatch, from_row: u8, from_col: u8, to_row: u8, to_col: u8) -> Option<CastleSide> {
+    position_from_match(game_state).castling_side(core_move(from_row, from_col, to_row, to_col, None))
+}
 
-    if piece_to_move_actual.piece_type == PieceType::King {
-        let col_diff = to_col as i8 - from_col as i8;
-        if col_diff.abs() == 2 { 
-            let (rook_from_col, rook_to_col) = if col_diff > 0 { (7, 5) } else { (0, 3) };
-            if let Some(rook_piece) = game_state.board[from_row as usize][rook_from_col as usize].take() {
-                game_state.board[from_row as usize][rook_to_col as usize] = Some(rook_piece);
-            } else { return err!(ChessError::InvalidMoveIllegalPieceMovement); }
-        }
-    }
-    
-    if piece_to_move_actual.piece_type == PieceType::Pawn {
-        if (player_color == PlayerColor::White && to_row == 7) || 
-           (player_color == PlayerColor::Black && to_row == 0) {
-            match promotion {
-                Some(PieceType::Queen) | Some(PieceType::Rook) | Some(PieceType::Bishop) | Some(PieceType::Knight) => {
-                    piece_to_move_actual.piece_type = promotion.unwrap();
-                }
-                Some(_) => return err!(ChessError::InvalidPromotionPiece),
-                None => piece_to_move_actual.piece_type = PieceType::Queen,
-            }
-        } else if promotion.is_some() { return err!(ChessError::InvalidPromotionNotOnLastRank); }
-    } else if promotion.is_some() { return err!(ChessError::InvalidPromotionNotAPawn); }
-
-    game_state.board[to_row as usize][to_col as usize] = Some(piece_to_move_actual);
-
-    if piece_type_moved == PieceType::Pawn && (to_row as i8 - from_row as i8).abs() == 2 {
-        let ep_row = (from_row as i8 + to_row as i8) / 2;
-        game_state.en_passant_target = Some(EnPassantSquare { row: ep_row as u8, col: from_col });
-    }
+// (from_row, from_col, to_row, to_col) of castling on `side` for the side to move, if the right remains.
+pub fn castling_move(game_state: &ChessMatch, side: CastleSide) -> Option<(u8, u8, u8, u8)> {
+    let mv = position_from_match(game_state).castling_move(side)?;
+    Some((mv.from.row, mv.from.col, mv.to.row, mv.to.col))
+}
+
+// Check as the match variant defines it (never in Antichess, not between touching kings in Atomic).
+pub fn is_in_check(game_state: &ChessMatch, king_color: PlayerColor) -> bool {
+    position_from_match(game_state).is_in_check(king_color.into())
+}
 
-    if piece_type_moved == PieceType::Pawn || is_capture || actual_captured_piece.is_some() {
-        game_state.halfmove_clock = 0;
-    } else {
-        game_state.halfmove_clock += 1;
+// --- Error Mapping ---
+pub fn chess_error(error: RulesError) -> Error {
+    let chess_error = match error {
+        RulesError::OutOfBounds => ChessError::InvalidMoveOutOfBounds,
+        RulesError::EmptySource => ChessError::InvalidMoveEmptySource,
+        RulesError::NotYourPiece => ChessError::InvalidMoveNotYourPiece,
+        RulesError::CannotCaptureOwnPiece => ChessError::InvalidMoveCannotCaptureOwnPiece,
+        RulesError::IllegalPieceMovement => ChessError::InvalidMoveIllegalPieceMovement,
+        RulesError::LeavesKingInCheck => ChessError::InvalidMoveLeavesKingInCheck,
+        RulesError::InvalidPromotionPiece => ChessError::InvalidPromotionPiece,
+        RulesError::PromotionNotOnLastRank => ChessError::InvalidPromotionNotOnLastRank,
+        RulesError::PromotionNotAPawn => ChessError::InvalidPromotionNotAPawn,
+        RulesError::KingNotFound => ChessError::KingNotFound,
+        RulesError::NoMoveToUndo => ChessError::NoMoveToTakeBack,
+        RulesError::DropNotAllowed => ChessError::DropNotAllowed,
+        RulesError::PieceNotInPocket => ChessError::InvalidDropPieceNotInPocket,
+        RulesError::DropOnOccupiedSquare => ChessError::InvalidDropOccupiedSquare,
+        RulesError::PawnDropOnBackRank => ChessError::InvalidDropPawnOnBackRank,
+        RulesError::CaptureRequired => ChessError::InvalidMoveCaptureRequired,
+    };
+    error!(chess_error)
+}
+
+// --- State Conversions ---
+pub fn position_from_match(game_state: &ChessMatch) -> Position {
+    Position {
+        variant: game_state.variant().into(),
+        board: board_to_core(game_state),
+        side_to_move: game_state.current_turn().into(),
+        castling_rights: game_state.castling_rights().into(),
+        en_passant_target: game_state.en_passant_target().map(Into::into),
+        halfmove_clock: game_state.halfmove_clock,
+        fullmove_number: game_state.fullmove_number,
+        checks_given: game_state.checks_given,
+        pockets: game_state.pockets.map(Into::into),
+        promoted: game_state.promoted_squares,
     }
+}
+
+pub fn write_position_to_match(position: &Position, game_state: &mut ChessMatch) {
+    write_board_from_core(&position.board, game_state);
+    game_state.set_current_turn(position.side_to_move.into());
+    game_state.set_castling_rights(position.castling_rights.into());
+    game_state.set_en_passant_target(position.en_passant_target.map(Into::into));
+    game_state.halfmove_clock = position.halfmove_clock;
+    game_state.fullmove_number = position.fullmove_number;
+    game_state.checks_given = position.checks_given;
+    game_state.pockets = position.pockets.map(Into::into);
+    game_state.promoted_squares = position.promoted;
+}
 
-    if player_color == PlayerColor::Black {
-        game_state.fullmove_number += 1;
+fn core_move(from_row: u8, from_col: u8, to_row: u8, to_col: u8, promotion: Option<PieceType>) -> core_rules::Move {
+    core_rules::Move {
+        from: core_rules::Square::new(from_row, from_col),
+        to: core_rules::Square::new(to_row, to_col),
+        promotion: promotion.map(Into::into),
     }
-    game_state.current_turn = player_color.opponent(); // Switch turn
+}
 
-    // --- Determine game result for the opponent (whose turn it now is) ---
-    let opponent_color = game_state.current_turn; 
-    if are_no_legal_moves(
-        &game_state.board, 
-        opponent_color, 
-        &game_state.castling_rights,
-        game_state.en_passant_target 
-    ) {
-        if is_king_in_check(&game_state.board, opponent_color) {
-            return Ok(MoveResult::Checkmate);
-        } else {
-            return Ok(MoveResult::Stalemate);
+fn board_to_core(game_state: &ChessMatch) -> core_rules::Board {
+    std::array::from_fn(|row| std::array::from_fn(|col| game_state.piece_at(row, col).map(Into::into)))
+}
+
+fn write_board_from_core(board: &core_rules::Board, game_state: &mut ChessMatch) {
+    for (row, squares) in board.iter().enumerate() {
+        for (col, square) in squares.iter().enumerate() {
+            game_state.set_piece_at(row, col, square.map(Into::into));
         }
     }
+}
 
-    if game_state.halfmove_clock >= 100 {
-        return Ok(MoveResult::Stalemate); 
+fn undo_record_from_core(undo: &core_rules::Undo) -> UndoRecord {
+    UndoRecord {
+        from_row: undo.from.row,
+        from_col: undo.from.col,
+        to_row: undo.to.row,
+        to_col: undo.to.col,
+        moved_piece: undo.moved_piece.into(),
+        captured_piece: undo.captured_piece.map(Into::into),
+        captured_was_promoted: undo.captured_was_promoted,
+        exploded: undo.exploded.map(|piece| piece.map(Into::into)),
+        was_en_passant: undo.was_en_passant,
+        previous_castling_rights: undo.previous_castling_rights.into(),
+        previous_en_passant_target: undo.previous_en_passant_target.map(Into::into),
+        previous_halfmove_clock: undo.previous_halfmove_clock,
     }
+}
 
-    Ok(MoveResult::Normal)
-}
-
-// --- Function to check if a player has ANY legal moves ---
-fn are_no_legal_moves(
-    board: &[[Option<Piece>; 8]; 8], // Takes immutable board ref
-    player_color: PlayerColor,
-    castling_rights: &CastlingRights, // Takes immutable castling_rights ref
-    en_passant_target: Option<EnPassantSquare>, // Takes EP by value (it's Copy)
-) -> bool {
-    for r_from_idx in 0..8 {
-        for c_from_idx in 0..8 {
-            if let Some(piece) = &board[r_from_idx][c_from_idx] {
-                if piece.color == player_color {
-                    for r_to_idx in 0..8 {
-                        for c_to_idx in 0..8 {
-                            let (from_r, from_c, to_r, to_c) = (r_from_idx as u8, c_from_idx as u8, r_to_idx as u8, c_to_idx as u8);
-                            if from_r == to_r && from_c == to_c { continue; }
-
-                            if is_legal_move_for_piece(board, piece, from_r, from_c, to_r, to_c, en_passant_target, castling_rights, player_color) {
-                                let mut temp_board = *board; 
-                                let mut temp_piece_to_move = temp_board[r_from_idx][c_from_idx].take().unwrap();
-                                
-                                if temp_piece_to_move.piece_type == PieceType::Pawn {
-                                    if let Some(ep_square) = en_passant_target {
-                                        if ep_square.row == to_r && ep_square.col == to_c &&
-                                           (c_to_idx as i8 - c_from_idx as i8).abs() == 1 &&
-                                           (r_to_idx as i8 - r_from_idx as i8).abs() == 1
-                                        {
-                                            let captured_pawn_row = if player_color == PlayerColor::White { to_r - 1 } else { to_r + 1 };
-                                            temp_board[captured_pawn_row as usize][c_to_idx].take();
-                                        }
-                                    }
-                                }
-                                if temp_piece_to_move.piece_type == PieceType::King && (c_to_idx as i8 - c_from_idx as i8).abs() == 2 {
-                                    let (rook_from_col, rook_to_col) = if (c_to_idx as i8 - c_from_idx as i8) > 0 { (7, 5) } else { (0, 3) };
-                                    if let Some(rook) = temp_board[r_from_idx][rook_from_col as usize].take() {
-                                        temp_board[r_from_idx][rook_to_col as usize] = Some(rook);
-                                    }
-                                }
-                                temp_board[r_to_idx][c_to_idx] = Some(temp_piece_to_move);
-
-                                if !is_king_in_check(&temp_board, player_color) {
-                                    return false; 
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
+fn undo_from_record(record: &UndoRecord) -> core_rules::Undo {
+    core_rules::Undo {
+        from: core_rules::Square::new(record.from_row, record.from_col),
+        to: core_rules::Square::new(record.to_row, record.to_col),
+        moved_piece: record.moved_piece.into(),
+        captured_piece: record.captured_piece.map(Into::into),
+        captured_was_promoted: record.captured_was_promoted,
+        exploded: record.exploded.map(|piece| piece.map(Into::into)),
+        was_en_passant: record.was_en_passant,
+        previous_castling_rights: record.previous_castling_rights.into(),
+        previous_en_passant_target: record.previous_en_passant_target.map(Into::into),
+        previous_halfmove_clock: record.previous_halfmove_clock,
     }
-    true 
-}
-
-// --- Piece-Specific Movement Validation ---
-fn is_legal_move_for_piece(
-    board: &[[Option<Piece>; 8]; 8], // Takes immutable board ref
-    piece_data: &Piece,
-    from_r: u8, from_c: u8,
-    to_r: u8, to_c: u8,
-    en_passant_target: Option<EnPassantSquare>, // Takes EP by value
-    castling_rights: &CastlingRights,   // Takes immutable castling_rights ref
-    current_player_color: PlayerColor,
-) -> bool {
-    if let Some(target_piece) = board[to_r as usize][to_c as usize] {
-        if target_piece.color == piece_data.color {
-            return false; 
+}
+
+impl From<Variant> for core_rules::Variant {
+    fn from(variant: Variant) -> Self {
+        match variant {
+            Variant::Standard => core_rules::Variant::Standard,
+            Variant::Chess960 => core_rules::Variant::Chess960,
+            Variant::KingOfTheHill => core_rules::Variant::KingOfTheHill,
+            Variant::ThreeCheck => core_rules::Variant::ThreeCheck,
+            Variant::Crazyhouse => core_rules::Variant::Crazyhouse,
+            Variant::Atomic => core_rules::Variant::Atomic,
+            Variant::Antichess => core_rules::Variant::Antichess,
         }
     }
+}
 
-    match piece_data.piece_type {
-        PieceType::Pawn => is_valid_pawn_move(board, piece_data.color, from_r, from_c, to_r, to_c, en_passant_target),
-        PieceType::Rook => is_valid_rook_move(board, from_r, from_c, to_r, to_c),
-        PieceType::Knight => is_valid_knight_move(from_r, from_c, to_r, to_c),
-        PieceType::Bishop => is_valid_bishop_move(board, from_r, from_c, to_r, to_c),
-        PieceType::Queen => is_valid_queen_move(board, from_r, from_c, to_r, to_c),
-        PieceType::King => {
-            is_valid_king_move_basic(from_r, from_c, to_r, to_c) || 
-            is_valid_castling_move(board, from_r, from_c, to_r, to_c, castling_rights, current_player_color)
-        },
+impl From<PlayerColor> for core_rules::Color {
+    fn from(color: PlayerColor) -> Self {
+        match color {
+            PlayerColor::White => core_rules::Color::White,
+            PlayerColor::Black => core_rules::Color::Black,
+        }
     }
 }
 
-fn is_valid_pawn_move(
-    board: &[[Option<Piece>; 8]; 8],
-    color: PlayerColor,
-    from_r: u8, from_c: u8,
-    to_r: u8, to_c: u8,
-    en_passant_target: Option<EnPassantSquare>,
-) -> bool {
-    let (fr, fc, tr, tc) = (from_r as i8, from_c as i8, to_r as i8, to_c as i8);
-    let direction: i8 = if color == PlayerColor::White { 1 } else { -1 };
-
-    if tc == fc && tr == fr + direction && board[to_r as usize][to_c as usize].is_none() { return true; }
-    if tc == fc && ( (color == PlayerColor::White && fr == 1 && tr == fr + 2 * direction) ||
-        (color == PlayerColor::Black && fr == 6 && tr == fr + 2 * direction) ) &&
-        board[to_r as usize][to_c as usize].is_none() && board[(fr + direction) as usize][fc as usize].is_none() { return true; }
-    if (tc == fc + 1 || tc == fc - 1) && tr == fr + direction {
-        if let Some(target_piece) = &board[to_r as usize][to_c as usize] {
-            if target_piece.color != color { return true; }
+impl From<core_rules::Color> for PlayerColor {
+    fn from(color: core_rules::Color) -> Self {
+        match color {
+            core_rules::Color::White => PlayerColor::White,
+            core_rules::Color::Black => PlayerColor::Black,
         }
     }
-    
-    if let Some(ep_square) = en_passant_target {
-        if ep_square.row == to_r && ep_square.col == to_c && 
-           (tc == fc + 1 || tc == fc - 1) && 
-           tr == fr + direction 
-        {
-            return true;
+}
+
+impl From<PieceType> for core_rules::PieceKind {
+    fn from(piece_type: PieceType) -> Self {
+        match piece_type {
+            PieceType::Pawn => core_rules::PieceKind::Pawn,
+            PieceType::Knight => core_rules::PieceKind::Knight,
+            PieceType::Bishop => core_rules::PieceKind::Bishop,
+            PieceType::Rook => core_rules::PieceKind::Rook,
+            PieceType::Queen => core_rules::PieceKind::Queen,
+            PieceType::King => core_rules::PieceKind::King,
         }
     }
-    false
 }
 
-fn is_valid_king_move_basic(from_r: u8, from_c: u8, to_r: u8, to_c: u8) -> bool {
-    let dr = (to_r as i8 - from_r as i8).abs();
-    let dc = (to_c as i8 - from_c as i8).abs();
-    (dr <= 1 && dc <= 1) && (dr != 0 || dc != 0)
+impl From<core_rules::PieceKind> for PieceType {
+    fn from(kind: core_rules::PieceKind) -> Self {
+        match kind {
+            core_rules::PieceKind::Pawn => PieceType::Pawn,
+            core_rules::PieceKind::Knight => PieceType::Knight,
+            core_rules::PieceKind::Bishop => PieceType::Bishop,
+            core_rules::PieceKind::Rook => PieceType::Rook,
+            core_rules::PieceKind::Queen => PieceType::Queen,
+            core_rules::PieceKind::King => PieceType::King,
+        }
+    }
 }
 
-fn is_valid_rook_move(board: &[[Option<Piece>; 8]; 8], from_r: u8, from_c: u8, to_r: u8, to_c: u8) -> bool {
-    if from_r == to_r || from_c == to_c { return is_path_clear_linear(board, from_r, from_c, to_r, to_c); }
-    false
-}
-fn is_valid_knight_move(from_r: u8, from_c: u8, to_r: u8, to_c: u8) -> bool {
-    let dr = (to_r as i8 - from_r as i8).abs(); let dc = (to_c as i8 - from_c as i8).abs();
-    (dr == 2 && dc == 1) || (dr == 1 && dc == 2)
-}
-fn is_valid_bishop_move(board: &[[Option<Piece>; 8]; 8], from_r: u8, from_c: u8, to_r: u8, to_c: u8) -> bool {
-    if (to_r as i8 - from_r as i8).abs() == (to_c as i8 - from_c as i8).abs() {
-        return is_path_clear_diagonal(board, from_r, from_c, to_r, to_c);
+impl From<Piece> for core_rules::Piece {
+    fn from(piece: Piece) -> Self {
+        core_rules::Piece::new(piece.piece_type.into(), piece.color.into())
     }
-    false
 }
-fn is_valid_queen_move(board: &[[Option<Piece>; 8]; 8], from_r: u8, from_c: u8, to_r: u8, to_c: u8) -> bool {
-    if from_r == to_r || from_c == to_c { return is_path_clear_linear(board, from_r, from_c, to_r, to_c); }
-    if (to_r as i8 - from_r as i8).abs() == (to_c as i8 - from_c as i8).abs() {
-        return is_path_clear_diagonal(board, from_r, from_c, to_r, to_c);
+
+impl From<core_rules::Piece> for Piece {
+    fn from(piece: core_rules::Piece) -> Self {
+        Piece { piece_type: piece.kind.into(), color: piece.color.into() }
     }
-    false
 }
 
-// --- Castling Logic ---
-fn update_castling_rights(rights: &mut CastlingRights, moved_piece: &Piece, from_r: u8, from_c: u8) {
-    if moved_piece.piece_type == PieceType::King {
-        if moved_piece.color == PlayerColor::White {
-            rights.white_kingside = false; rights.white_queenside = false;
-        } else {
-            rights.black_kingside = false; rights.black_queenside = false;
-        }
-    } else if moved_piece.piece_type == PieceType::Rook {
-        if moved_piece.color == PlayerColor::White {
-            if from_r == 0 && from_c == 0 { rights.white_queenside = false; }
-            if from_r == 0 && from_c == 7 { rights.white_kingside = false; }
-        } else { // Black
-            if from_r == 7 && from_c == 0 { rights.black_queenside = false; }
-            if from_r == 7 && from_c == 7 { rights.black_kingside = false; }
-        }
+impl From<EnPassantSquare> for core_rules::Square {
+    fn from(square: EnPassantSquare) -> Self {
+        core_rules::Square::new(square.row, square.col)
     }
 }
 
-fn is_valid_castling_move(
-    board: &[[Option<Piece>; 8]; 8],
-    from_r: u8, from_c: u8, to_r: u8, to_c: u8,
-    rights: &CastlingRights, player_color: PlayerColor,
-) -> bool {
-    if from_r != to_r { return false; }
-    let king_initial_row = if player_color == PlayerColor::White { 0 } else { 7 };
-    if from_r != king_initial_row || from_c != 4 { return false; } 
-    if is_king_in_check(board, player_color) { return false; }
-
-    let attacker_color = player_color.opponent();
-
-    if to_c == 6 { // Kingside (G file)
-        let can_castle = if player_color == PlayerColor::White { rights.white_kingside } else { rights.black_kingside };
-        if !can_castle { return false; }
-        if board[king_initial_row as usize][5].is_some() || board[king_initial_row as usize][6].is_some() { return false; }
-        if is_square_attacked(board, king_initial_row, 5, attacker_color) || 
-           is_square_attacked(board, king_initial_row, 6, attacker_color) { return false; }
-        return true;
-    } else if to_c == 2 { // Queenside (C file)
-        let can_castle = if player_color == PlayerColor::White { rights.white_queenside } else { rights.black_queenside };
-        if !can_castle { return false; }
-        if board[king_initial_row as usize][3].is_some() || board[king_initial_row as usize][2].is_some() || board[king_initial_row as usize][1].is_some() { return false; }
-        if is_square_attacked(board, king_initial_row, 3, attacker_color) || 
-           is_square_attacked(board, king_initial_row, 2, attacker_color) { return false; }
-        return true;
+impl From<core_rules::Square> for EnPassantSquare {
+    fn from(square: core_rules::Square) -> Self {
+        EnPassantSquare { row: square.row, col: square.col }
     }
-    false
-}
-
-// --- Path Clearing Helpers ---
-fn is_path_clear_linear(board: &[[Option<Piece>; 8]; 8], from_r: u8, from_c: u8, to_r: u8, to_c: u8) -> bool {
-    if from_r == to_r {
-        let start_col = std::cmp::min(from_c, to_c) + 1; let end_col = std::cmp::max(from_c, to_c);
-        for c in start_col..end_col { if board[from_r as usize][c as usize].is_some() { return false; } }
-    } else if from_c == to_c {
-        let start_row = std::cmp::min(from_r, to_r) + 1; let end_row = std::cmp::max(from_r, to_r);
-        for r in start_row..end_row { if board[r as usize][from_c as usize].is_some() { return false; } }
-    } else { return false; } 
-    true
-}
-fn is_path_clear_diagonal(board: &[[Option<Piece>; 8]; 8], from_r: u8, from_c: u8, to_r: u8, to_c: u8) -> bool {
-    let dr_total = to_r as i8 - from_r as i8; let dc_total = to_c as i8 - from_c as i8;
-    if dr_total.abs() != dc_total.abs() || dr_total == 0 { return false; }
-    let dr_step = dr_total.signum(); let dc_step = dc_total.signum();
-    let mut r = from_r as i8 + dr_step; let mut c = from_c as i8 + dc_step;
-    while r != to_r as i8 || c != to_c as i8 {
-        if board[r as usize][c as usize].is_some() { return false; }
-        r += dr_step; c += dc_step;
+}
+
+impl From<CastlingRights> for core_rules::CastlingRights {
+    fn from(rights: CastlingRights) -> Self {
+        core_rules::CastlingRights {
+            white_kingside: rights.white_kingside,
+            white_queenside: rights.white_queenside,
+            black_kingside: rights.black_kingside,
+            black_queenside: rights.black_queenside,
+        }
     }
-    true
 }
 
-// --- Check Detection Helpers ---
-fn find_king(board: &[[Option<Piece>; 8]; 8], king_color: PlayerColor) -> Result<(u8, u8)> {
-    for r_idx in 0..8 { for c_idx in 0..8 {
-        if let Some(p) = &board[r_idx][c_idx] {
-            if p.piece_type == PieceType::King && p.color == king_color { return Ok((r_idx as u8, c_idx as u8)); }
+impl From<core_rules::CastlingRights> for CastlingRights {
+    fn from(rights: core_rules::CastlingRights) -> Self {
+        CastlingRights {
+            white_kingside: rights.white_kingside,
+            white_queenside: rights.white_queenside,
+            black_kingside: rights.black_kingside,
+            black_queenside: rights.black_queenside,
         }
-    }}
-    err!(ChessError::KingNotFound)
-}
-
-fn can_pawn_attack(p_r: u8, p_c: u8, t_r: u8, t_c: u8, attacker_color: PlayerColor) -> bool {
-    let dir: i8 = if attacker_color == PlayerColor::White { 1 } else { -1 };
-    (t_c as i8 == p_c as i8 + 1 || t_c as i8 == p_c as i8 - 1) && t_r as i8 == p_r as i8 + dir
-}
-
-fn can_knight_attack(k_r: u8, k_c: u8, t_r: u8, t_c: u8) -> bool {
-    let dr = (t_r as i8 - k_r as i8).abs(); let dc = (t_c as i8 - k_c as i8).abs();
-    (dr == 2 && dc == 1) || (dr == 1 && dc == 2)
-}
-
-fn can_king_attack(k_r: u8, k_c: u8, t_r: u8, t_c: u8) -> bool {
-    let dr = (t_r as i8 - k_r as i8).abs(); let dc = (t_c as i8 - k_c as i8).abs();
-    dr <= 1 && dc <= 1 && (dr != 0 || dc != 0)
-}
-
-fn can_slider_attack(
-    board: &[[Option<Piece>; 8]; 8],
-    s_r: u8, s_c: u8, t_r: u8, t_c: u8,
-    piece_type: PieceType
-) -> bool {
-    match piece_type {
-        PieceType::Rook => {
-            if s_r == t_r || s_c == t_c { 
-                return is_path_clear_linear(board, s_r, s_c, t_r, t_c);
-            }
-        },
-        PieceType::Bishop => {
-            if (t_r as i8 - s_r as i8).abs() == (t_c as i8 - s_c as i8).abs() { 
-                return is_path_clear_diagonal(board, s_r, s_c, t_r, t_c);
-            }
-        },
-        PieceType::Queen => {
-            if s_r == t_r || s_c == t_c {
-                if is_path_clear_linear(board, s_r, s_c, t_r, t_c) { return true; }
-            }
-            if (t_r as i8 - s_r as i8).abs() == (t_c as i8 - s_c as i8).abs() {
-                if is_path_clear_diagonal(board, s_r, s_c, t_r, t_c) { return true; }
-            }
-        },
-        _ => return false, 
     }
-    false
-}
-
-fn is_square_attacked(
-    board: &[[Option<Piece>; 8]; 8],
-    target_r: u8, target_c: u8,
-    attacker_color: PlayerColor,
-) -> bool {
-    for r_from in 0..8 {
-        for c_from in 0..8 {
-            if let Some(attacker_piece) = &board[r_from as usize][c_from as usize] {
-                if attacker_piece.color == attacker_color {
-                    match attacker_piece.piece_type {
-                        PieceType::Pawn => {
-                            if can_pawn_attack(r_from as u8, c_from as u8, target_r, target_c, attacker_color) {
-                                return true;
-                            }
-                        }
-                        PieceType::Knight => {
-                            if can_knight_attack(r_from as u8, c_from as u8, target_r, target_c) {
-                                return true;
-                            }
-                        }
-                        PieceType::King => {
-                            if can_king_attack(r_from as u8, c_from as u8, target_r, target_c) {
-                                return true;
-                            }
-                        }
-                        PieceType::Rook | PieceType::Bishop | PieceType::Queen => {
-                            if can_slider_attack(board, r_from as u8, c_from as u8, target_r, target_c, attacker_piece.piece_type) {
-                                return true;
-                            }
-                        }
-                    }
-                }
-            }
+}
+
+impl From<Pocket> for core_rules::Pocket {
+    fn from(pocket: Pocket) -> Self {
+        core_rules::Pocket {
+            pawns: pocket.pawns,
+            knights: pocket.knights,
+            bishops: pocket.bishops,
+            rooks: pocket.rooks,
+            queens: pocket.queens,
         }
     }
-    false
 }
 
-pub fn is_king_in_check(board: &[[Option<Piece>; 8]; 8], king_color: PlayerColor) -> bool {
-    match find_king(board, king_color) {
-        Ok((kr, kc)) => is_square_attacked(board, kr, kc, king_color.opponent()),
-        Err(_) => true, // If king not found, treat as an error state / king effectively in check
+impl From<core_rules::Pocket> for Pocket {
+    fn from(pocket: core_rules::Pocket) -> Self {
+        Pocket {
+            pawns: pocket.pawns,
+            knights: pocket.knights,
+            bishops: pocket.bishops,
+            rooks: pocket.rooks,
+            queens: pocket.queens,
+        }
     }
 }
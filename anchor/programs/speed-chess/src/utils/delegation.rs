@@ -0,0 +1,224 @@
+// src/utils/delegation.rs
+// MagicBlock ephemeral rollup CPIs: handing match accounts to the delegation program on the base
+// layer, scheduling commits from inside the rollup, and restoring accounts when the delegation
+// program hands them back. Mirrors the account flow of MagicBlock's ephemeral-rollups-sdk.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::system_instruction;
+
+use crate::errors::ChessError;
+
+pub const DELEGATION_PROGRAM_ID: Pubkey = pubkey!("DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh");
+pub const MAGIC_PROGRAM_ID: Pubkey = pubkey!("Magic11111111111111111111111111111111111111");
+pub const MAGIC_CONTEXT_ID: Pubkey = pubkey!("MagicContext1111111111111111111111111111111");
+
+pub const BUFFER_SEED: &[u8] = b"buffer"; // Owned by this program: [b"buffer", delegated account]
+pub const DELEGATION_RECORD_SEED: &[u8] = b"delegation"; // Owned by the delegation program
+pub const DELEGATION_METADATA_SEED: &[u8] = b"delegation-metadata";
+
+// Instruction the delegation program calls on this program to hand an undelegated account back.
+pub const UNDELEGATE_CALLBACK_DISCRIMINATOR: [u8; 8] = [196, 28, 41, 206, 48, 37, 51, 167];
+const DELEGATE_DISCRIMINATOR: [u8; 8] = [0; 8];
+const SCHEDULE_COMMIT: u32 = 1; // MagicBlockInstruction variants (bincode, u32 little-endian)
+const SCHEDULE_COMMIT_AND_UNDELEGATE: u32 = 2;
+
+pub const DEFAULT_COMMIT_FREQUENCY_MS: u32 = 30_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DelegateArgs {
+    pub commit_frequency_ms: u32,
+    pub seeds: Vec<Vec<u8>>, // Seeds of the delegated PDA under its owner program, without the bump
+    pub validator: Option<Pubkey>, // Ephemeral validator allowed to take the account, any when None
+}
+
+pub struct DelegateAccounts<'a, 'info> {
+    pub payer: &'a AccountInfo<'info>,
+    pub pda: &'a AccountInfo<'info>,
+    pub owner_program: &'a AccountInfo<'info>,
+    pub buffer: &'a AccountInfo<'info>,
+    pub delegation_record: &'a AccountInfo<'info>,
+    pub delegation_metadata: &'a AccountInfo<'info>,
+    pub delegation_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+pub fn buffer_address(pda: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BUFFER_SEED, pda.as_ref()], &crate::ID)
+}
+
+pub fn delegation_record_address(pda: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[DELEGATION_RECORD_SEED, pda.as_ref()], &DELEGATION_PROGRAM_ID).0
+}
+
+pub fn delegation_metadata_address(pda: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[DELEGATION_METADATA_SEED, pda.as_ref()], &DELEGATION_PROGRAM_ID).0
+}
+
+// Instruction data for the delegation program's `delegate`.
+pub fn delegate_instruction_data(args: &DelegateArgs) -> Result<Vec<u8>> {
+    let mut data = DELEGATE_DISCRIMINATOR.to_vec();
+    args.serialize(&mut data)?;
+    Ok(data)
+}
+
+// Magic program instruction that commits `accounts` from the rollup to the base layer and, with
+// `undelegate`, hands them back to this program afterwards.
+pub fn schedule_commit_instruction(payer: &Pubkey, accounts: &[Pubkey], undelegate: bool) -> Instruction {
+    let variant = if undelegate { SCHEDULE_COMMIT_AND_UNDELEGATE } else { SCHEDULE_COMMIT };
+    let mut metas = vec![AccountMeta::new(*payer, true), AccountMeta::new(MAGIC_CONTEXT_ID, false)];
+    metas.extend(accounts.iter().map(|account| AccountMeta::new(*account, false)));
+    Instruction::new_with_bytes(MAGIC_PROGRAM_ID, &variant.to_le_bytes(), metas)
+}
+
+// Creates (or, when it already holds lamports, allocates and assigns) a PDA of `owner`.
+fn create_pda<'info>(
+    account: &AccountInfo<'info>,
+    owner: &Pubkey,
+    space: usize,
+    signer_seeds: &[&[u8]],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let rent_minimum = Rent::get()?.minimum_balance(space);
+    if account.lamports() == 0 {
+        invoke_signed(
+            &system_instruction::create_account(payer.key, account.key, rent_minimum, space as u64, owner),
+            &[payer.clone(), account.clone(), system_program.clone()],
+            &[signer_seeds],
+        )?;
+        return Ok(());
+    }
+    let shortfall = rent_minimum.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, shortfall),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+    invoke_signed(
+        &system_instruction::allocate(account.key, space as u64),
+        &[account.clone(), system_program.clone()],
+        &[signer_seeds],
+    )?;
+    invoke_signed(
+        &system_instruction::assign(account.key, owner),
+        &[account.clone(), system_program.clone()],
+        &[signer_seeds],
+    )?;
+    Ok(())
+}
+
+// Returns an account's lamports to `destination` and leaves it empty and owned by the system program.
+fn close_account<'info>(account: &AccountInfo<'info>, destination: &AccountInfo<'info>) -> Result<()> {
+    **destination.lamports.borrow_mut() = destination.lamports().checked_add(account.lamports()).ok_or(ChessError::MathError)?;
+    **account.lamports.borrow_mut() = 0;
+    account.assign(&anchor_lang::system_program::ID);
+    account.realloc(0, false)?;
+    Ok(())
+}
+
+// Delegates a PDA of this program (seeds `pda_seeds`, without the bump): its data is parked in the
+// buffer, the account is handed to the delegation program, which copies the data back from the buffer.
+pub fn delegate_account(accounts: DelegateAccounts, pda_seeds: &[&[u8]], validator: Option<Pubkey>) -> Result<()> {
+    let DelegateAccounts { payer, pda, owner_program, buffer, delegation_record, delegation_metadata, delegation_program, system_program } = accounts;
+    require_keys_eq!(*delegation_program.key, DELEGATION_PROGRAM_ID, ChessError::InvalidDelegationAccounts);
+    require_keys_eq!(*owner_program.key, crate::ID, ChessError::InvalidDelegationAccounts);
+
+    let (pda_key, pda_bump) = Pubkey::find_program_address(pda_seeds, &crate::ID);
+    let (buffer_key, buffer_bump) = buffer_address(pda.key);
+    require_keys_eq!(pda_key, *pda.key, ChessError::InvalidDelegationAccounts);
+    require_keys_eq!(buffer_key, *buffer.key, ChessError::InvalidDelegationAccounts);
+    let pda_bump_slice = [pda_bump];
+    let pda_signer_seeds = [pda_seeds, &[&pda_bump_slice[..]]].concat();
+    let buffer_signer_seeds: &[&[u8]] = &[BUFFER_SEED, pda.key.as_ref(), &[buffer_bump]];
+
+    // 1. Park the data in the buffer
+    let data_len = pda.data_len();
+    create_pda(buffer, &crate::ID, data_len, buffer_signer_seeds, payer, system_program)?;
+    buffer.try_borrow_mut_data()?.copy_from_slice(&pda.try_borrow_data()?);
+
+    // 2. Empty the account and give it to the delegation program
+    pda.try_borrow_mut_data()?.fill(0);
+    pda.assign(&anchor_lang::system_program::ID);
+    invoke_signed(
+        &system_instruction::assign(pda.key, delegation_program.key),
+        &[pda.clone(), system_program.clone()],
+        &[&pda_signer_seeds],
+    )?;
+
+    // 3. Delegate, then release the buffer
+    let args = DelegateArgs {
+        commit_frequency_ms: DEFAULT_COMMIT_FREQUENCY_MS,
+        seeds: pda_seeds.iter().map(|seed| seed.to_vec()).collect(),
+        validator,
+    };
+    let delegate_ix = Instruction::new_with_bytes(
+        DELEGATION_PROGRAM_ID,
+        &delegate_instruction_data(&args)?,
+        vec![
+            AccountMeta::new(*payer.key, true),
+            AccountMeta::new(*pda.key, true),
+            AccountMeta::new_readonly(*owner_program.key, false),
+            AccountMeta::new(*buffer.key, false),
+            AccountMeta::new(*delegation_record.key, false),
+            AccountMeta::new(*delegation_metadata.key, false),
+            AccountMeta::new_readonly(*system_program.key, false),
+        ],
+    );
+    invoke_signed(
+        &delegate_ix,
+        &[
+            payer.clone(),
+            pda.clone(),
+            owner_program.clone(),
+            buffer.clone(),
+            delegation_record.clone(),
+            delegation_metadata.clone(),
+            system_program.clone(),
+        ],
+        &[&pda_signer_seeds],
+    )?;
+    close_account(buffer, payer)
+}
+
+// Inside the rollup: asks the magic program to commit `accounts` (and, with `undelegate`, to return them).
+pub fn schedule_commit<'info>(
+    payer: &AccountInfo<'info>,
+    accounts: &[&AccountInfo<'info>],
+    magic_context: &AccountInfo<'info>,
+    magic_program: &AccountInfo<'info>,
+    undelegate: bool,
+) -> Result<()> {
+    require_keys_eq!(*magic_program.key, MAGIC_PROGRAM_ID, ChessError::InvalidDelegationAccounts);
+    require_keys_eq!(*magic_context.key, MAGIC_CONTEXT_ID, ChessError::InvalidDelegationAccounts);
+    let keys: Vec<Pubkey> = accounts.iter().map(|account| *account.key).collect();
+    let mut infos = vec![payer.clone(), magic_context.clone()];
+    infos.extend(accounts.iter().map(|&account| account.clone()));
+    invoke(&schedule_commit_instruction(payer.key, &keys, undelegate), &infos)?;
+    Ok(())
+}
+
+// Called back by the delegation program after undelegation: re-creates the PDA under this program
+// and restores the committed data it left in its (signing) buffer.
+pub fn restore_undelegated_account<'info>(
+    delegated: &AccountInfo<'info>,
+    buffer: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    account_seeds: &[Vec<u8>],
+) -> Result<()> {
+    require!(
+        buffer.is_signer && *buffer.owner == DELEGATION_PROGRAM_ID,
+        ChessError::InvalidDelegationAccounts
+    );
+    let seeds: Vec<&[u8]> = account_seeds.iter().map(|seed| seed.as_slice()).collect();
+    let (key, bump) = Pubkey::find_program_address(&seeds, &crate::ID);
+    require_keys_eq!(key, *delegated.key, ChessError::InvalidDelegationAccounts);
+    let bump_slice = [bump];
+    let signer_seeds = [&seeds[..], &[&bump_slice[..]]].concat();
+
+    create_pda(delegated, &crate::ID, buffer.data_len(), &signer_seeds, payer, system_program)?;
+    delegated.try_borrow_mut_data()?.copy_from_slice(&buffer.try_borrow_data()?);
+    Ok(())
+}
@@ -0,0 +1,179 @@
+// src/utils/glicko.rs
+// Glicko-2 in fixed-point integer arithmetic (GLICKO_ONE = 1.0), so every validator computes
+// identical updates. Each settled game is treated as its own one-game rating period;
+// inactivity between games is folded in by growing the deviation with elapsed Clock time.
+use crate::state::GlickoRating;
+
+pub const GLICKO_ONE: i64 = 1_000_000_000;
+pub const DEFAULT_GLICKO_RATING: i64 = 1500 * GLICKO_ONE;
+pub const MAX_GLICKO_DEVIATION: i64 = 350 * GLICKO_ONE;
+pub const DEFAULT_GLICKO_VOLATILITY: i64 = 60_000_000; // 0.06
+pub const RATING_PERIOD_SECONDS: i64 = 86_400; // Inactivity grows the deviation once per day
+
+const ONE: i128 = GLICKO_ONE as i128;
+const GLICKO2_SCALE: i128 = 173_717_800_000; // 400 / ln(10)
+const TAU: i128 = 500_000_000; // System constant constraining volatility change
+const CONVERGENCE_TOLERANCE: i128 = 1_000; // 0.000001
+const MAX_ITERATIONS: u32 = 100;
+const LN_2: i128 = 693_147_181;
+const PI_SQUARED: i128 = 9_869_604_401;
+const MAX_EXP_INPUT: i128 = 60 * ONE;
+const MIN_EXP_INPUT: i128 = -40 * ONE;
+
+fn mul(a: i128, b: i128) -> i128 {
+    a * b / ONE
+}
+
+fn div(a: i128, b: i128) -> i128 {
+    a * ONE / b
+}
+
+// e^x, by reducing x to k ln 2 + r with |r| <= ln(2) / 2 and summing the Taylor series of e^r.
+fn exp(x: i128) -> i128 {
+    if x < MIN_EXP_INPUT {
+        return 0;
+    }
+    let x = x.min(MAX_EXP_INPUT);
+    let k = (x + x.signum() * LN_2 / 2) / LN_2;
+    let r = x - k * LN_2;
+    let mut sum = ONE;
+    let mut term = ONE;
+    for n in 1..30 {
+        term = mul(term, r) / n;
+        if term == 0 {
+            break;
+        }
+        sum += term;
+    }
+    if k >= 0 {
+        sum << k
+    } else {
+        sum >> -k
+    }
+}
+
+// Natural log of x > 0: x = m * 2^k with m in [1, 2), ln m = 2 atanh((m - 1) / (m + 1)).
+fn ln(x: i128) -> i128 {
+    let mut m = x;
+    let mut k: i128 = 0;
+    while m >= 2 * ONE {
+        m >>= 1;
+        k += 1;
+    }
+    while m < ONE {
+        m <<= 1;
+        k -= 1;
+    }
+    let z = div(m - ONE, m + ONE);
+    let z_squared = mul(z, z);
+    let mut power = z;
+    let mut sum = 0;
+    for n in (1..60).step_by(2) {
+        let term = power / n;
+        if term == 0 {
+            break;
+        }
+        sum += term;
+        power = mul(power, z_squared);
+    }
+    k * LN_2 + 2 * sum
+}
+
+fn sqrt(x: i128) -> i128 {
+    if x <= 0 {
+        return 0;
+    }
+    let target = x * ONE;
+    let mut guess = target;
+    let mut next = (guess + 1) / 2;
+    while next < guess {
+        guess = next;
+        next = (guess + target / guess) / 2;
+    }
+    guess
+}
+
+// Glicko-2 g(phi): discounts an opponent's result by their rating uncertainty.
+fn g(phi: i128) -> i128 {
+    div(ONE, sqrt(ONE + div(3 * mul(phi, phi), PI_SQUARED)))
+}
+
+// Deviation after `elapsed_seconds` without games: phi' = sqrt(phi^2 + t * sigma^2), capped at 350.
+pub fn deviation_after_inactivity(deviation: i64, volatility: i64, elapsed_seconds: i64) -> i64 {
+    if elapsed_seconds <= 0 {
+        return deviation;
+    }
+    let phi = div(deviation as i128, GLICKO2_SCALE);
+    let sigma = volatility as i128;
+    let periods = i128::from(elapsed_seconds) * ONE / i128::from(RATING_PERIOD_SECONDS);
+    let grown = mul(sqrt(mul(phi, phi) + mul(periods, mul(sigma, sigma))), GLICKO2_SCALE);
+    grown.min(MAX_GLICKO_DEVIATION as i128) as i64
+}
+
+// New volatility from the Illinois-algorithm root search in step 5 of the Glicko-2 paper.
+fn new_volatility(phi: i128, sigma: i128, v: i128, delta: i128) -> i128 {
+    let phi_squared = mul(phi, phi);
+    let delta_squared = mul(delta, delta);
+    let a = ln(mul(sigma, sigma));
+    let tau_squared = mul(TAU, TAU);
+    let f = |x: i128| {
+        let ex = exp(x);
+        let d = phi_squared + v + ex;
+        mul(div(ex, d), div(delta_squared - phi_squared - v - ex, 2 * d)) - div(x - a, tau_squared)
+    };
+
+    let mut big_a = a;
+    let mut big_b = if delta_squared > phi_squared + v {
+        ln(delta_squared - phi_squared - v)
+    } else {
+        let mut k = 1;
+        while f(a - k * TAU) < 0 && k < MAX_ITERATIONS as i128 {
+            k += 1;
+        }
+        a - k * TAU
+    };
+    let mut f_a = f(big_a);
+    let mut f_b = f(big_b);
+    let mut iterations = 0;
+    while (big_b - big_a).abs() > CONVERGENCE_TOLERANCE && f_b != f_a && iterations < MAX_ITERATIONS {
+        let big_c = big_a + (big_a - big_b) * f_a / (f_b - f_a);
+        let f_c = f(big_c);
+        if f_c.signum() * f_b.signum() <= 0 {
+            big_a = big_b;
+            f_a = f_b;
+        } else {
+            f_a /= 2;
+        }
+        big_b = big_c;
+        f_b = f_c;
+        iterations += 1;
+    }
+    exp(big_a / 2)
+}
+
+// Rating after one game scoring `score_milli` (thousandths) against `opponent`.
+// Both ratings should already include any inactivity growth of their deviation.
+pub fn glicko_update(player: &GlickoRating, opponent: &GlickoRating, score_milli: i32) -> GlickoRating {
+    let mu = div(player.rating as i128 - DEFAULT_GLICKO_RATING as i128, GLICKO2_SCALE);
+    let phi = div(player.deviation as i128, GLICKO2_SCALE);
+    let sigma = player.volatility as i128;
+    let opponent_mu = div(opponent.rating as i128 - DEFAULT_GLICKO_RATING as i128, GLICKO2_SCALE);
+    let opponent_g = g(div(opponent.deviation as i128, GLICKO2_SCALE));
+    let score = i128::from(score_milli) * ONE / 1000;
+
+    let expected = div(ONE, ONE + exp(-mul(opponent_g, mu - opponent_mu))).clamp(1, ONE - 1);
+    let v = div(ONE, mul(mul(opponent_g, opponent_g), mul(expected, ONE - expected)).max(1));
+    let delta = mul(mul(v, opponent_g), score - expected);
+
+    let new_sigma = new_volatility(phi, sigma, v, delta);
+    let phi_star_squared = mul(phi, phi) + mul(new_sigma, new_sigma);
+    let new_phi = div(ONE, sqrt(div(ONE, phi_star_squared) + div(ONE, v)));
+    let new_mu = mu + mul(mul(new_phi, new_phi), mul(opponent_g, score - expected));
+
+    GlickoRating {
+        rating: (mul(new_mu, GLICKO2_SCALE) + DEFAULT_GLICKO_RATING as i128) as i64,
+        deviation: (mul(new_phi, GLICKO2_SCALE).min(MAX_GLICKO_DEVIATION as i128)) as i64,
+        volatility: new_sigma as i64,
+        ..*player
+    }
+}
@@ -1,5 +1,21 @@
+pub mod arena;
+pub mod bracket;
 pub mod chess_logic;
+pub mod delegation;
+pub mod glicko;
+pub mod move_signatures;
 pub mod payout_logic;
+pub mod randomness;
+pub mod rating;
+pub mod swiss;
 
+pub use arena::*;
+pub use bracket::*;
 pub use chess_logic::*;
-pub use payout_logic::*;
\ No newline at end of file
+pub use delegation::*;
+pub use glicko::*;
+pub use move_signatures::*;
+pub use payout_logic::*;
+pub use randomness::*;
+pub use rating::*;
+pub use swiss::*;
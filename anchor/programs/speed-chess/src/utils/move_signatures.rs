@@ -0,0 +1,86 @@
+// src/utils/move_signatures.rs
+// Pre-signed move lists for make_moves: the message both players sign, and finding their signatures
+// among the ed25519 program instructions of the transaction (through the instructions sysvar).
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::load_instruction_at_checked;
+
+use crate::errors::ChessError;
+use crate::instructions::MakeMoveArgs;
+use crate::state::ChessMatch;
+
+pub const MAX_BATCHED_MOVES: usize = 32; // Longer games are submitted as several signed batches
+const MOVES_MESSAGE_PREFIX: &[u8] = b"speed-chess:make_moves";
+
+// Ed25519 program instruction data: u8 signature count, u8 padding, then per signature seven u16
+// offsets (signature, signature ix, public key, public key ix, message, message size, message ix).
+const ED25519_OFFSETS_START: usize = 2;
+const ED25519_OFFSETS_LEN: usize = 14;
+const ED25519_PUBKEY_LEN: usize = 32;
+const THIS_INSTRUCTION: u16 = u16::MAX; // Instruction index meaning "in the ed25519 instruction's own data"
+
+// What both players sign: prefix || match account || plies played so far || borsh(moves).
+// Binding the match and ChessMatch::plies_played keeps a signed list from being replayed elsewhere
+// or later on; unlike the move history length, plies_played does not go back on a takeback.
+pub fn moves_message(chess_match: &Pubkey, plies_played: u64, moves: &[MakeMoveArgs]) -> Result<Vec<u8>> {
+    let mut message = MOVES_MESSAGE_PREFIX.to_vec();
+    message.extend_from_slice(chess_match.as_ref());
+    message.extend_from_slice(&plies_played.to_le_bytes());
+    moves.serialize(&mut message)?;
+    Ok(message)
+}
+
+// Both players of `chess_match` (the account at `chess_match_key`) signed `moves` for its current ply.
+pub fn check_moves_signed(
+    instructions_sysvar: &AccountInfo,
+    chess_match_key: &Pubkey,
+    chess_match: &ChessMatch,
+    moves: &[MakeMoveArgs],
+) -> Result<()> {
+    let message = moves_message(chess_match_key, chess_match.plies_played, moves)?;
+    let signers = message_signers(instructions_sysvar, &message);
+    require!(
+        chess_match.players.iter().all(|player| signers.contains(player)),
+        ChessError::MissingMoveSignatures
+    );
+    Ok(())
+}
+
+// (signer, message) pairs carried by an ed25519 program instruction. Entries whose key or message
+// lives in another instruction are skipped rather than followed.
+pub fn ed25519_signed_messages(data: &[u8]) -> Vec<(Pubkey, &[u8])> {
+    let count = data.first().copied().unwrap_or(0) as usize;
+    (0..count)
+        .filter_map(|index| {
+            let start = ED25519_OFFSETS_START + index * ED25519_OFFSETS_LEN;
+            let entry = data.get(start..start + ED25519_OFFSETS_LEN)?;
+            let field = |n: usize| u16::from_le_bytes([entry[2 * n], entry[2 * n + 1]]);
+            let (pubkey_offset, message_offset, message_size) = (field(2) as usize, field(4) as usize, field(5) as usize);
+            if [field(1), field(3), field(6)].iter().any(|&instruction| instruction != THIS_INSTRUCTION) {
+                return None;
+            }
+            let pubkey = data.get(pubkey_offset..pubkey_offset + ED25519_PUBKEY_LEN)?;
+            let message = data.get(message_offset..message_offset + message_size)?;
+            Some((Pubkey::try_from(pubkey).ok()?, message))
+        })
+        .collect()
+}
+
+// Wallets that signed `message` in this transaction. The runtime verifies ed25519 program
+// instructions before any instruction runs, so finding a matching entry is proof of the signature.
+pub fn message_signers(instructions_sysvar: &AccountInfo, message: &[u8]) -> Vec<Pubkey> {
+    let mut signers = Vec::new();
+    let mut index = 0;
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions_sysvar) {
+        if instruction.program_id == ed25519_program::ID {
+            signers.extend(
+                ed25519_signed_messages(&instruction.data)
+                    .into_iter()
+                    .filter(|&(_, signed)| signed == message)
+                    .map(|(signer, _)| signer),
+            );
+        }
+        index += 1;
+    }
+    signers
+}
@@ -1,6 +1,6 @@
 // src/utils/payout_logic.rs
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Transfer, TokenAccount, Token};
+use anchor_spl::token::{self, CloseAccount, Transfer, TokenAccount, Token};
 
 use crate::errors::ChessError;
 use crate::state::ChessMatch;
@@ -32,7 +32,7 @@ fn transfer_tokens_with_signer<'a, 'b, 'c, 'info>(
 
 
 pub fn process_payout<'info>( // Explicitly adding 'info lifetime here
-    chess_match: &Account<'info, ChessMatch>,
+    chess_match: &ChessMatch, // Loaded match state; the match account itself takes no part in the CPIs
     // Pass AccountInfo directly for accounts involved in CPI to better manage lifetimes
     match_escrow_token_account_info: &AccountInfo<'info>, // PDA-controlled token account
     winner_token_account_info: &AccountInfo<'info>,
@@ -45,7 +45,7 @@ pub fn process_payout<'info>( // Explicitly adding 'info lifetime here
 ) -> Result<()> {
     // 1. Derive the PDA which is the authority of the match_escrow_token_account
     let (pda_authority, bump_seed) = Pubkey::find_program_address(
-        &[b"match_escrow", &chess_match.match_id.as_bytes()],
+        &[b"match_escrow", &chess_match.match_id_bytes()],
         program_id,
     );
 
@@ -56,7 +56,7 @@ pub fn process_payout<'info>( // Explicitly adding 'info lifetime here
     }
 
     // 3. Prepare signer seeds for the PDA
-    let match_id_bytes = chess_match.match_id.as_bytes();
+    let match_id_bytes = chess_match.match_id_bytes();
     let seeds: &[&[u8]] = &[
         b"match_escrow",
         &match_id_bytes,
@@ -138,7 +138,7 @@ pub fn process_payout<'info>( // Explicitly adding 'info lifetime here
 }
 
 pub fn process_draw_payout<'info>( // Explicitly adding 'info lifetime here
-    chess_match: &Account<'info, ChessMatch>,
+    chess_match: &ChessMatch,
     match_escrow_token_account_info: &AccountInfo<'info>,
     player_one_token_account_info: &AccountInfo<'info>,
     player_two_token_account_info: &AccountInfo<'info>,
@@ -148,7 +148,7 @@ pub fn process_draw_payout<'info>( // Explicitly adding 'info lifetime here
     match_escrow_token_account_data: &Account<'info, TokenAccount>,
 ) -> Result<()> {
     let (pda_authority, bump_seed) = Pubkey::find_program_address(
-        &[b"match_escrow", &chess_match.match_id.as_bytes()],
+        &[b"match_escrow", &chess_match.match_id_bytes()],
         program_id,
     );
 
@@ -156,7 +156,7 @@ pub fn process_draw_payout<'info>( // Explicitly adding 'info lifetime here
         return err!(ChessError::InvalidEscrowAccount);
     }
 
-    let match_id_bytes = chess_match.match_id.as_bytes();
+    let match_id_bytes = chess_match.match_id_bytes();
     let seeds: &[&[u8]] = &[
         b"match_escrow",
         &match_id_bytes,
@@ -233,3 +233,52 @@ pub fn process_draw_payout<'info>( // Explicitly adding 'info lifetime here
 
     Ok(())
 }
+
+// Returns everything held in the match escrow to a single recipient and closes the escrow.
+// Used when a match never started (e.g. an open challenge expired), so no platform fee is taken.
+// The escrow token account's authority is the chess_match PDA, so the program signs with its seeds.
+pub fn refund_and_close_escrow<'info>(
+    chess_match: &AccountLoader<'info, ChessMatch>,
+    match_escrow_token_account: &Account<'info, TokenAccount>,
+    recipient_token_account_info: &AccountInfo<'info>,
+    rent_recipient_info: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<u64> {
+    // A shared borrow of the match data is fine while it signs: the token CPIs only read it
+    let match_state = chess_match.load()?;
+    let seeds: &[&[u8]] = &[
+        b"chess_match",
+        match_state.match_id_bytes(),
+        &[match_state.bump],
+    ];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+    let refund_amount = match_escrow_token_account.amount;
+    if refund_amount > 0 {
+        msg!("Refunding escrow balance: {}", refund_amount);
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                Transfer {
+                    from: match_escrow_token_account.to_account_info(),
+                    to: recipient_token_account_info.clone(),
+                    authority: chess_match.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            refund_amount,
+        )?;
+    }
+
+    token::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: match_escrow_token_account.to_account_info(),
+            destination: rent_recipient_info.clone(),
+            authority: chess_match.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    Ok(refund_amount)
+}
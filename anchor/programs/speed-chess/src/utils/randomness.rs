@@ -0,0 +1,36 @@
+// src/utils/randomness.rs
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::errors::ChessError;
+
+// SlotHashes sysvar layout: u64 entry count, then (slot: u64, hash: [u8; 32]) entries, newest first.
+const SLOT_HASHES_FIRST_HASH: std::ops::Range<usize> = 16..48;
+
+// A seed from the most recent slot hash mixed with caller-supplied context (match id, participants).
+// This is not fair randomness on its own: the slot hash is public before the transaction that reads
+// it lands, so its sender can compute the seed in advance and, by holding the transaction back for
+// a new slot, pick among seeds. Only combine it with a share that sender cannot see (see
+// start_position_commitment).
+pub fn recent_slot_hash_seed(slot_hashes: &AccountInfo, context: &[&[u8]]) -> Result<[u8; 32]> {
+    let data = slot_hashes.try_borrow_data()?;
+    let recent_hash = data.get(SLOT_HASHES_FIRST_HASH).ok_or(error!(ChessError::SlotHashesUnavailable))?;
+
+    let mut parts: Vec<&[u8]> = Vec::with_capacity(context.len() + 1);
+    parts.push(recent_hash);
+    parts.extend_from_slice(context);
+    Ok(hashv(&parts).to_bytes())
+}
+
+// Uniform-enough index in 0..bound from a 32-byte seed (bias below 2^-50 for small bounds).
+pub fn seed_to_index(seed: &[u8; 32], bound: u16) -> u16 {
+    let value = u64::from_le_bytes(seed[..8].try_into().expect("seed has 32 bytes"));
+    (value % bound as u64) as u16
+}
+
+// Commitment the creator of a Chess960 match publishes at initialize_match and opens with
+// reveal_start_position once the opponent's share is drawn. Bound to the match id so a commitment
+// cannot be copied from another match.
+pub fn start_position_commitment(match_id: &[u8], secret: &[u8; 32]) -> [u8; 32] {
+    hashv(&[b"chess960_start_position", match_id, secret]).to_bytes()
+}
@@ -0,0 +1,51 @@
+// src/utils/rating.rs
+// Integer-only Elo, so every validator computes identical rating updates.
+
+pub const DEFAULT_RATING: u16 = 1200;
+pub const MIN_RATING: u16 = 100;
+pub const PROVISIONAL_GAMES: u32 = 30; // Players below this many games use the higher K-factor
+const PROVISIONAL_K_FACTOR: i32 = 40;
+const ESTABLISHED_K_FACTOR: i32 = 20;
+
+const MAX_RATING_DIFF: i32 = 400; // Larger gaps are treated as 400, as in the FIDE tables
+const DIFF_STEP: i32 = 25;
+// Expected score in thousandths for rating differences 0, 25, ..., 400: 1000 / (1 + 10^(-d/400))
+const EXPECTED_SCORE_MILLI: [i32; 17] = [500, 536, 571, 606, 640, 673, 703, 733, 760, 785, 808, 830, 849, 867, 882, 896, 909];
+
+// Game score in thousandths from one player's point of view.
+pub const WIN_SCORE_MILLI: i32 = 1000;
+pub const DRAW_SCORE_MILLI: i32 = 500;
+pub const LOSS_SCORE_MILLI: i32 = 0;
+
+pub fn k_factor(games_played: u32) -> i32 {
+    if games_played < PROVISIONAL_GAMES {
+        PROVISIONAL_K_FACTOR
+    } else {
+        ESTABLISHED_K_FACTOR
+    }
+}
+
+// Expected score (thousandths) of a player rated `rating` against `opponent_rating`,
+// interpolated linearly between the table entries.
+pub fn expected_score_milli(rating: u16, opponent_rating: u16) -> i32 {
+    let diff = i32::from(rating) - i32::from(opponent_rating);
+    let magnitude = diff.abs().min(MAX_RATING_DIFF);
+    let index = (magnitude / DIFF_STEP) as usize;
+    let remainder = magnitude % DIFF_STEP;
+    let low = EXPECTED_SCORE_MILLI[index];
+    let high = EXPECTED_SCORE_MILLI[(index + 1).min(EXPECTED_SCORE_MILLI.len() - 1)];
+    let expected = low + (high - low) * remainder / DIFF_STEP;
+    if diff >= 0 { expected } else { 1000 - expected }
+}
+
+// New rating after one game scoring `score_milli` against `opponent_rating`, rounded to the nearest point.
+pub fn elo_update(rating: u16, games_played: u32, opponent_rating: u16, score_milli: i32) -> u16 {
+    let scaled_change = k_factor(games_played) * (score_milli - expected_score_milli(rating, opponent_rating));
+    let change = if scaled_change >= 0 {
+        (scaled_change + 500) / 1000
+    } else {
+        (scaled_change - 500) / 1000
+    };
+    let new_rating = (i32::from(rating) + change).clamp(i32::from(MIN_RATING), i32::from(u16::MAX));
+    new_rating as u16
+}
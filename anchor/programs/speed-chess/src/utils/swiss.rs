@@ -0,0 +1,212 @@
+// src/utils/swiss.rs
+// Swiss-system pairing, tiebreaks and prize splits. Pure functions over the tournament's player list
+// (indices are positions in Tournament::players, which is in seed order), so clients can run the
+// same code off-chain to preview pairings and standings.
+use anchor_lang::prelude::*;
+
+use crate::state::{PlayerColor, SwissGame, SwissStanding, Tournament, TournamentPlayer, BYE};
+
+const MAX_PAIRING_STEPS: u32 = 20_000; // Backtracking budget before repeat pairings are allowed
+
+fn has_played(players: &[TournamentPlayer], a: u8, b: u8) -> bool {
+    players[a as usize].history.iter().any(|game| game.opponent == b)
+}
+
+fn had_bye(player: &TournamentPlayer) -> bool {
+    player.history.iter().any(|game| game.opponent == BYE)
+}
+
+// Indices ordered by score, then seed.
+fn ranking(players: &[TournamentPlayer]) -> Vec<u8> {
+    let mut order: Vec<u8> = (0..players.len() as u8).collect();
+    order.sort_by_key(|&index| (std::cmp::Reverse(players[index as usize].score), index));
+    order
+}
+
+// Preferred opponents for the top remaining player: the upper half of its score group meets the
+// lower half (S1[i] vs S2[i]), then lower score groups in rank order.
+fn candidates(players: &[TournamentPlayer], remaining: &[u8]) -> Vec<u8> {
+    let top_score = players[remaining[0] as usize].score;
+    let group_len = remaining.iter().take_while(|&&index| players[index as usize].score == top_score).count();
+    let half = (group_len / 2).max(1);
+    let mut order: Vec<u8> = remaining[half..group_len].to_vec();
+    order.extend(remaining[1..half].iter().rev());
+    order.extend(&remaining[group_len..]);
+    order
+}
+
+fn pair_remaining(
+    players: &[TournamentPlayer],
+    remaining: &[u8],
+    allow_repeats: bool,
+    steps: &mut u32,
+) -> Option<Vec<(u8, u8)>> {
+    if remaining.is_empty() {
+        return Some(Vec::new());
+    }
+    let top = remaining[0];
+    for opponent in candidates(players, remaining) {
+        *steps += 1;
+        if *steps > MAX_PAIRING_STEPS && !allow_repeats {
+            return None;
+        }
+        if !allow_repeats && has_played(players, top, opponent) {
+            continue;
+        }
+        let rest: Vec<u8> = remaining[1..].iter().copied().filter(|&index| index != opponent).collect();
+        if let Some(mut pairs) = pair_remaining(players, &rest, allow_repeats, steps) {
+            pairs.insert(0, (top, opponent));
+            return Some(pairs);
+        }
+    }
+    None
+}
+
+fn color_balance(player: &TournamentPlayer) -> i32 {
+    player.history.iter().map(|game| match game.color {
+        Some(PlayerColor::White) => 1,
+        Some(PlayerColor::Black) => -1,
+        None => 0,
+    }).sum()
+}
+
+fn last_color(player: &TournamentPlayer) -> Option<PlayerColor> {
+    player.history.iter().rev().find_map(|game| game.color)
+}
+
+// Orders a pairing (higher-ranked player first) as (White, Black): the player who has had White
+// less often gets it, then the player who had Black last; otherwise colors alternate by board.
+fn assign_colors(players: &[TournamentPlayer], higher: u8, lower: u8, board: usize) -> (u8, u8) {
+    let (a, b) = (&players[higher as usize], &players[lower as usize]);
+    let higher_gets_white = match color_balance(a).cmp(&color_balance(b)) {
+        std::cmp::Ordering::Less => true,
+        std::cmp::Ordering::Greater => false,
+        std::cmp::Ordering::Equal => match (last_color(a), last_color(b)) {
+            (Some(PlayerColor::Black), Some(PlayerColor::White)) | (Some(PlayerColor::Black), None) => true,
+            (Some(PlayerColor::White), Some(PlayerColor::Black)) | (None, Some(PlayerColor::Black)) => false,
+            _ => board.is_multiple_of(2),
+        },
+    };
+    if higher_gets_white { (higher, lower) } else { (lower, higher) }
+}
+
+// Pairings for the next round as (White, Black) indices; an odd player out gets (player, BYE).
+// The bye goes to the lowest-ranked player who has not had one. Repeat pairings are avoided
+// whenever a full pairing without them exists.
+pub fn swiss_pairings(players: &[TournamentPlayer]) -> Vec<(u8, u8)> {
+    let mut order = ranking(players);
+    let bye = if order.len() % 2 == 1 {
+        let position = order
+            .iter()
+            .rposition(|&index| !had_bye(&players[index as usize]))
+            .unwrap_or(order.len() - 1);
+        Some(order.remove(position))
+    } else {
+        None
+    };
+
+    let mut steps = 0;
+    let pairs = pair_remaining(players, &order, false, &mut steps)
+        .or_else(|| pair_remaining(players, &order, true, &mut steps))
+        .unwrap_or_default();
+    let mut pairings: Vec<(u8, u8)> = pairs
+        .into_iter()
+        .enumerate()
+        .map(|(board, (higher, lower))| assign_colors(players, higher, lower, board))
+        .collect();
+    if let Some(index) = bye {
+        pairings.push((index, BYE));
+    }
+    pairings
+}
+
+// Pairs the tournament's next round: fills the bracket with (White, Black) slots, scores the bye
+// straight away (its slot holds Pubkey::default() as the opponent) and counts the games to record.
+pub fn begin_swiss_round(tournament: &mut Tournament) {
+    let pairings = swiss_pairings(&tournament.players);
+    tournament.bracket.clear();
+    tournament.results_pending = 0;
+    for (white, black) in pairings {
+        tournament.bracket.push(tournament.players[white as usize].player);
+        if black == BYE {
+            tournament.bracket.push(Pubkey::default());
+            record_swiss_game(&mut tournament.players[white as usize], SwissGame { opponent: BYE, color: None, result: 2 });
+        } else {
+            tournament.bracket.push(tournament.players[black as usize].player);
+            tournament.results_pending += 1;
+        }
+    }
+}
+
+// Records one game (or bye) for a player. Results are in half points: 2 win, 1 draw, 0 loss; a bye scores 2.
+pub fn record_swiss_game(player: &mut TournamentPlayer, game: SwissGame) {
+    player.score += u16::from(game.result);
+    player.history.push(game);
+}
+
+// Standings by score, then Buchholz (sum of opponents' scores), then Sonneborn-Berger
+// (opponents' scores weighted by the result against them), then seed. Byes add nothing to either tiebreak.
+pub fn swiss_standings(players: &[TournamentPlayer]) -> Vec<SwissStanding> {
+    let mut standings: Vec<(u8, SwissStanding)> = players
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let opponents = entry.history.iter().filter(|game| game.opponent != BYE);
+            let buchholz = opponents.clone().map(|game| players[game.opponent as usize].score).sum();
+            let sonneborn_berger = opponents
+                .map(|game| u32::from(game.result) * u32::from(players[game.opponent as usize].score))
+                .sum();
+            (index as u8, SwissStanding { player: entry.player, score: entry.score, buchholz, sonneborn_berger })
+        })
+        .collect();
+    standings.sort_by_key(|(index, standing)| {
+        (
+            std::cmp::Reverse((standing.score, standing.buchholz, standing.sonneborn_berger)),
+            *index,
+        )
+    });
+    standings.into_iter().map(|(_, standing)| standing).collect()
+}
+
+// Swiss and arena places are paid one player each, so the table must add up to the whole pool.
+pub fn swiss_payout_table_is_valid(payout_table: &[u16]) -> bool {
+    let total: u64 = payout_table.iter().map(|&basis_points| u64::from(basis_points)).sum();
+    !payout_table.is_empty() && total == 10_000
+}
+
+// Prizes by final Swiss standings; see tied_prize_amounts.
+pub fn swiss_prize_amounts(players: &[TournamentPlayer], payout_table: &[u16], pool: u64) -> Vec<(Pubkey, u64)> {
+    let ranked: Vec<(Pubkey, u16)> = swiss_standings(players)
+        .iter()
+        .map(|standing| (standing.player, standing.score))
+        .collect();
+    tied_prize_amounts(&ranked, payout_table, pool)
+}
+
+// Prizes for players ranked best first as (player, score), one payout place each: players tied on
+// score pool the shares of the places they occupy and split them evenly (the remainder goes to the
+// first of them). Unclaimed shares go to the winner.
+pub fn tied_prize_amounts(ranked: &[(Pubkey, u16)], payout_table: &[u16], pool: u64) -> Vec<(Pubkey, u64)> {
+    let mut prizes: Vec<(Pubkey, u64)> = Vec::with_capacity(ranked.len());
+    let mut place = 0;
+    for group in ranked.chunk_by(|a, b| a.1 == b.1) {
+        let basis_points: u64 = (place..place + group.len())
+            .filter_map(|place| payout_table.get(place))
+            .map(|&basis_points| u64::from(basis_points))
+            .sum();
+        let group_amount = pool * basis_points / 10_000;
+        let share = group_amount / group.len() as u64;
+        for (position, &(player, _)) in group.iter().enumerate() {
+            let remainder = if position == 0 { group_amount - share * group.len() as u64 } else { 0 };
+            prizes.push((player, share + remainder));
+        }
+        place += group.len();
+    }
+
+    let paid: u64 = prizes.iter().map(|&(_, amount)| amount).sum();
+    if let Some((_, amount)) = prizes.first_mut() {
+        *amount += pool - paid;
+    }
+    prizes.retain(|&(_, amount)| amount > 0);
+    prizes
+}
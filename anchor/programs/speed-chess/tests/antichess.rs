@@ -0,0 +1,37 @@
+// tests/antichess.rs
+// The Antichess stalemate win: the side left without a move wins, so the end reason must not read as a draw.
+use anchor_lang::prelude::*;
+use counter::instructions::{record_ply, MakeMoveArgs};
+use counter::state::*;
+use counter::utils::validate_and_apply_move;
+
+fn pawn(color: PlayerColor) -> Option<Piece> {
+    Some(Piece { piece_type: PieceType::Pawn, color })
+}
+
+#[test]
+fn stalemated_side_wins_with_its_own_end_reason() {
+    // White: a6, h2. Black: a7, blocked by a6 with nothing to capture
+    let mut chess_match = ChessMatch::default();
+    chess_match.set_variant(Variant::Antichess);
+    let mut board = [[None; 8]; 8];
+    board[5][0] = pawn(PlayerColor::White);
+    board[1][7] = pawn(PlayerColor::White);
+    board[6][0] = pawn(PlayerColor::Black);
+    chess_match.set_board(&board);
+    chess_match.set_castling_rights(CastlingRights { white_kingside: None, white_queenside: None, black_kingside: None, black_queenside: None });
+    chess_match.fullmove_number = 1;
+    chess_match.players = [Pubkey::new_unique(), Pubkey::new_unique()];
+    chess_match.set_game_status(GameStatus::Active);
+
+    // 1. h3 leaves Black without a legal move
+    let result = validate_and_apply_move(&mut chess_match, 1, 7, 2, 7, PlayerColor::White, None).unwrap();
+    assert_eq!(result, MoveResult::VariantWin { winner: PlayerColor::Black, reason: GameEndReason::StalemateWin });
+
+    let mut history = MoveHistory { match_id: "antichess".to_string(), bump: 0, moves: Vec::new() };
+    let args = MakeMoveArgs { from_row: 1, from_col: 7, to_row: 2, to_col: 7, promotion: None, drop_piece: None };
+    let white = chess_match.players[0];
+    record_ply(&mut chess_match, &mut history, white, PlayerColor::White, &args, result, 0, 0);
+    assert_eq!(chess_match.game_status(), GameStatus::BlackWins);
+    assert_eq!(chess_match.game_end_reason(), Some(GameEndReason::StalemateWin));
+}
@@ -0,0 +1,77 @@
+// tests/arena.rs
+// Arena pairing, streak and berserk scoring, and prizes by final points.
+use anchor_lang::prelude::*;
+use counter::state::{ArenaPlayer, PlayerColor, TournamentPlayer};
+use counter::utils::*;
+
+fn players(count: usize) -> Vec<TournamentPlayer> {
+    (0..count)
+        .map(|_| TournamentPlayer {
+            player: Pubkey::new_unique(),
+            rating: None,
+            eliminated_in_round: 0,
+            score: 0,
+            history: Vec::new(),
+            arena: ArenaPlayer::default(),
+        })
+        .collect()
+}
+
+// Plays a 30-move arena game between White and Black, `result` being White's half points.
+fn play(players: &mut [TournamentPlayer], white: u8, black: u8, result: u8) -> (u16, u16) {
+    let white_points = record_arena_game(&mut players[white as usize], black, PlayerColor::White, result, false, 30);
+    let black_points = record_arena_game(&mut players[black as usize], white, PlayerColor::Black, 2 - result, false, 30);
+    (white_points, black_points)
+}
+
+#[test]
+fn idle_players_are_paired_by_score_without_immediate_rematches() {
+    let mut players = players(4);
+    assert_eq!(arena_pairing(&players), Some((0, 1)));
+
+    play(&mut players, 0, 1, 2);
+    players[2].arena.current_game = Some(0); // 2 and 3 are still playing
+    players[3].arena.current_game = Some(0);
+    // Only 0 and 1 are idle, so they meet again; 1 has had Black and now gets White
+    assert_eq!(arena_pairing(&players), Some((1, 0)));
+
+    // Once 3 is free, the leader skips 1, whom they just played; 3 has had White less often
+    players[3].arena.current_game = None;
+    assert_eq!(arena_pairing(&players), Some((3, 0)));
+
+    players.iter_mut().skip(1).for_each(|entry| entry.arena.current_game = Some(1));
+    assert_eq!(arena_pairing(&players), None);
+}
+
+#[test]
+fn streaks_double_points_and_berserk_wins_add_one() {
+    assert_eq!(arena_points(0, 2, false, 30), 2);
+    assert_eq!(arena_points(0, 1, false, 30), 1);
+    assert_eq!(arena_points(2, 2, false, 30), 4); // On fire
+    assert_eq!(arena_points(2, 1, false, 30), 2);
+    assert_eq!(arena_points(0, 2, true, 30), 3);
+    assert_eq!(arena_points(3, 2, true, 30), 5);
+    assert_eq!(arena_points(0, 2, true, 5), 2); // Too short for the berserk bonus
+    assert_eq!(arena_points(0, 0, true, 30), 0);
+
+    let mut players = players(2);
+    let points: Vec<u16> = (0..4).map(|_| play(&mut players, 0, 1, 2).0).collect();
+    assert_eq!(points, vec![2, 2, 4, 4]);
+    play(&mut players, 0, 1, 1); // A draw on fire still scores double, then ends the streak
+    assert_eq!((players[0].score, players[0].arena.win_streak), (14, 0));
+    assert_eq!(players[0].arena.games_played, 5);
+}
+
+#[test]
+fn standings_rank_by_points_and_ties_split_prizes() {
+    let mut players = players(4);
+    play(&mut players, 0, 1, 0);
+    play(&mut players, 2, 3, 2);
+
+    let standings = arena_standings(&players);
+    let order: Vec<Pubkey> = standings.iter().map(|standing| standing.player).collect();
+    assert_eq!(order, [1, 2, 0, 3].map(|index| players[index].player));
+
+    let prizes = arena_prize_amounts(&players, &[6000, 3000, 1000], 1_000);
+    assert_eq!(prizes, vec![(players[1].player, 450), (players[2].player, 450), (players[0].player, 50), (players[3].player, 50)]);
+}
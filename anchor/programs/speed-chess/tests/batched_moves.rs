@@ -0,0 +1,114 @@
+// tests/batched_moves.rs
+// The message players sign for make_moves, and reading signers back out of ed25519 program instructions.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{self as instructions_sysvar, construct_instructions_data, BorrowedInstruction};
+use counter::errors::ChessError;
+use counter::instructions::{record_ply, MakeMoveArgs};
+use counter::state::*;
+use counter::utils::{check_moves_signed, ed25519_signed_messages, moves_message, take_back, validate_and_apply_move};
+
+const OWN_DATA: u16 = u16::MAX;
+
+fn pawn_push(from_row: u8, to_row: u8) -> MakeMoveArgs {
+    MakeMoveArgs { from_row, from_col: 4, to_row, to_col: 4, promotion: None, drop_piece: None }
+}
+
+// Ed25519 program instruction data in the layout web3.js and solana-sdk produce: header, offsets,
+// then each signer's key and (dummy) signature, with the message stored once after them.
+fn ed25519_data(signers: &[Pubkey], message: &[u8], key_instruction: u16) -> Vec<u8> {
+    let header_len = 2 + 14 * signers.len();
+    let message_offset = header_len + signers.len() * (32 + 64);
+    let mut data = vec![signers.len() as u8, 0];
+    for index in 0..signers.len() {
+        let pubkey_offset = header_len + index * (32 + 64);
+        let offsets = [
+            (pubkey_offset + 32) as u16, OWN_DATA,
+            pubkey_offset as u16, key_instruction,
+            message_offset as u16, message.len() as u16, OWN_DATA,
+        ];
+        data.extend(offsets.iter().flat_map(|offset| offset.to_le_bytes()));
+    }
+    for signer in signers {
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&[7; 64]);
+    }
+    data.extend_from_slice(message);
+    data
+}
+
+#[test]
+fn signed_list_is_bound_to_match_plies_played_and_moves() {
+    let chess_match = Pubkey::new_unique();
+    let moves = vec![pawn_push(1, 3), pawn_push(6, 4)];
+    let message = moves_message(&chess_match, 0, &moves).unwrap();
+
+    assert_eq!(message, moves_message(&chess_match, 0, &moves).unwrap());
+    assert_ne!(message, moves_message(&Pubkey::new_unique(), 0, &moves).unwrap());
+    assert_ne!(message, moves_message(&chess_match, 2, &moves).unwrap());
+    assert_ne!(message, moves_message(&chess_match, 0, &moves[..1]).unwrap());
+}
+
+#[test]
+fn both_signers_are_read_from_one_ed25519_instruction() {
+    let (white, black) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let message = moves_message(&Pubkey::new_unique(), 4, &[pawn_push(1, 3)]).unwrap();
+    let data = ed25519_data(&[white, black], &message, OWN_DATA);
+
+    let signed = ed25519_signed_messages(&data);
+    assert_eq!(signed, vec![(white, message.as_slice()), (black, message.as_slice())]);
+}
+
+#[test]
+fn entries_pointing_into_other_instructions_or_out_of_bounds_are_ignored() {
+    let message = moves_message(&Pubkey::new_unique(), 0, &[pawn_push(1, 3)]).unwrap();
+    let borrowed_key = ed25519_data(&[Pubkey::new_unique()], &message, 0);
+    assert!(ed25519_signed_messages(&borrowed_key).is_empty());
+
+    let mut truncated = ed25519_data(&[Pubkey::new_unique()], &message, OWN_DATA);
+    truncated.truncate(truncated.len() - 1);
+    assert!(ed25519_signed_messages(&truncated).is_empty());
+    assert!(ed25519_signed_messages(&[]).is_empty());
+}
+
+// Instructions sysvar data for a transaction whose only other instruction is one ed25519 instruction.
+fn instructions_with_ed25519(ed25519_data: &[u8]) -> Vec<u8> {
+    construct_instructions_data(&[BorrowedInstruction { program_id: &ed25519_program::ID, accounts: Vec::new(), data: ed25519_data }])
+}
+
+#[test]
+fn signed_list_cannot_be_replayed_after_a_takeback() {
+    let (white, black, match_key) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let mut chess_match = ChessMatch::default();
+    chess_match.set_match_id("batched");
+    chess_match.players = [white, black];
+    chess_match.reset_position();
+    chess_match.set_takebacks_enabled(true);
+    chess_match.set_game_status(GameStatus::Active);
+    let mut history = MoveHistory { match_id: "batched".to_string(), bump: 0, moves: Vec::new() };
+
+    let moves = vec![pawn_push(1, 3), pawn_push(6, 4)];
+    let message = moves_message(&match_key, chess_match.plies_played, &moves).unwrap();
+    let mut sysvar_data = instructions_with_ed25519(&ed25519_data(&[white, black], &message, OWN_DATA));
+    let mut lamports = 0;
+    let sysvar_key = instructions_sysvar::ID;
+    let instructions = AccountInfo::new(&sysvar_key, false, false, &mut lamports, &mut sysvar_data, &sysvar_key, false, 0);
+
+    check_moves_signed(&instructions, &match_key, &chess_match, &moves).unwrap();
+    for args in &moves {
+        let color = chess_match.current_turn();
+        let result = validate_and_apply_move(&mut chess_match, args.from_row, args.from_col, args.to_row, args.to_col, color, None).unwrap();
+        let player = chess_match.players[color as usize];
+        record_ply(&mut chess_match, &mut history, player, color, args, result, 0, 0);
+    }
+    assert_eq!(chess_match.plies_played, 2);
+
+    // White takes back both plies: the history is back at ply 0, but plies_played is not
+    assert_eq!(take_back(&mut chess_match, &mut history, PlayerColor::White).unwrap(), 2);
+    assert!(history.moves.is_empty());
+    assert_eq!(chess_match.plies_played, 2);
+    assert_eq!(
+        check_moves_signed(&instructions, &match_key, &chess_match, &moves).unwrap_err(),
+        ChessError::MissingMoveSignatures.into()
+    );
+}
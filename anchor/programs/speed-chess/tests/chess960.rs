@@ -0,0 +1,77 @@
+// tests/chess960.rs
+// The Chess960 commit-reveal: the creator's committed share and the joiner's share decide the start position together.
+use anchor_lang::prelude::*;
+use counter::errors::ChessError;
+use counter::state::*;
+use counter::utils::{seed_to_index, start_position_commitment};
+
+const SECRET: [u8; 32] = [7; 32];
+
+// A Chess960 match as join_match leaves it: the joiner's share in start_position_index, board unset.
+fn joined_match(joiner_share: u16) -> ChessMatch {
+    let mut chess_match = ChessMatch::default();
+    chess_match.set_match_id("chess960");
+    chess_match.players = [Pubkey::new_unique(), Pubkey::new_unique()];
+    chess_match.set_variant(Variant::Chess960);
+    chess_match.start_position_index = STANDARD_START_POSITION;
+    chess_match.reset_position();
+    chess_match.set_start_position_commitment(Some(start_position_commitment(b"chess960", &SECRET)));
+    chess_match.start_position_index = joiner_share;
+    chess_match.set_game_status(GameStatus::Active);
+    chess_match
+}
+
+#[test]
+fn reveal_adds_the_creator_share_and_sets_up_the_position() {
+    let creator_share = seed_to_index(&SECRET, 960);
+    let mut chess_match = joined_match(900);
+    assert_eq!(chess_match.check_accepts_moves().unwrap_err(), ChessError::StartPositionNotRevealed.into());
+
+    chess_match.reveal_start_position(&SECRET).unwrap();
+    let index = (900 + creator_share) % 960;
+    assert_eq!(chess_match.start_position_index, index);
+    assert_eq!(chess_match.start_position_commitment(), None);
+    chess_match.check_accepts_moves().unwrap();
+
+    let mut expected = ChessMatch::default();
+    expected.set_variant(Variant::Chess960);
+    expected.start_position_index = index;
+    expected.reset_position();
+    assert_eq!(chess_match.board(), expected.board());
+    assert_eq!(chess_match.castling_rights(), expected.castling_rights());
+
+    // The secret opens the commitment once
+    assert_eq!(chess_match.reveal_start_position(&SECRET).unwrap_err(), ChessError::NoStartPositionToReveal.into());
+}
+
+#[test]
+fn every_joiner_share_is_reachable_whatever_the_secret() {
+    // For a fixed secret the joiner's share moves the result over all 960 positions, so a joiner
+    // who does not know the secret cannot aim for one
+    let mut reached = [false; 960];
+    for joiner_share in 0..960 {
+        let mut chess_match = joined_match(joiner_share);
+        chess_match.reveal_start_position(&SECRET).unwrap();
+        reached[chess_match.start_position_index as usize] = true;
+    }
+    assert!(reached.iter().all(|&hit| hit));
+}
+
+#[test]
+fn reveal_needs_the_committed_secret_and_an_opponent() {
+    let mut chess_match = joined_match(3);
+    assert_eq!(chess_match.reveal_start_position(&[8; 32]).unwrap_err(), ChessError::StartPositionSecretMismatch.into());
+
+    // The same secret committed for another match id does not open this one
+    let mut other = joined_match(3);
+    other.set_start_position_commitment(Some(start_position_commitment(b"another-match", &SECRET)));
+    assert_eq!(other.reveal_start_position(&SECRET).unwrap_err(), ChessError::StartPositionSecretMismatch.into());
+
+    // Revealing before the join would let the joiner aim their share
+    chess_match.set_game_status(GameStatus::WaitingForOpponent);
+    assert_eq!(chess_match.reveal_start_position(&SECRET).unwrap_err(), ChessError::OpponentNotJoinedYet.into());
+
+    let mut standard = ChessMatch::default();
+    standard.set_game_status(GameStatus::Active);
+    assert_eq!(standard.reveal_start_position(&SECRET).unwrap_err(), ChessError::NoStartPositionToReveal.into());
+}
@@ -0,0 +1,29 @@
+// tests/crazyhouse.rs
+// Crazyhouse drops through make_move's bookkeeping: the history and the squares in MoveMadeEvent.
+use anchor_lang::prelude::*;
+use counter::instructions::{ply_source, record_ply, MakeMoveArgs};
+use counter::state::*;
+use counter::utils::chess_logic::validate_and_apply_move;
+
+#[test]
+fn drop_ignores_whatever_from_square_the_client_sent() {
+    let mut game_state = ChessMatch::default();
+    game_state.set_variant(Variant::Crazyhouse);
+    game_state.reset_position();
+    game_state.pockets[0].knights = 1;
+    let mut move_history = MoveHistory { match_id: "drop".to_string(), bump: 0, moves: Vec::new() };
+
+    // N@e4 with junk in the unused from_* fields
+    let args = MakeMoveArgs { from_row: 255, from_col: 255, to_row: 3, to_col: 4, promotion: None, drop_piece: Some(PieceType::Knight) };
+    let (from_row, from_col, promotion) = ply_source(&args);
+    assert_eq!((from_row, from_col, promotion), (3, 4, Some(PieceType::Knight)));
+    let move_result = validate_and_apply_move(&mut game_state, from_row, from_col, args.to_row, args.to_col, PlayerColor::White, promotion).unwrap();
+    record_ply(&mut game_state, &mut move_history, Pubkey::new_unique(), PlayerColor::White, &args, move_result, 3, 0);
+
+    assert_eq!(game_state.piece_at(3, 4), Some(Piece { piece_type: PieceType::Knight, color: PlayerColor::White }));
+    assert_eq!(game_state.pockets[0].knights, 0);
+    assert_eq!(
+        move_history.decode_moves(),
+        vec![RecordedMove { from_row: 3, from_col: 4, to_row: 3, to_col: 4, promotion: Some(PieceType::Knight), clock_seconds: 3 }]
+    );
+}
@@ -0,0 +1,58 @@
+// tests/delegation.rs
+// Wire format of the ephemeral rollup CPIs, which are built by hand rather than through an SDK.
+use anchor_lang::prelude::*;
+use counter::utils::*;
+
+#[test]
+fn delegate_instruction_data_is_discriminator_then_borsh_args() {
+    let validator = Pubkey::new_unique();
+    let args = DelegateArgs {
+        commit_frequency_ms: 30_000,
+        seeds: vec![b"chess_match".to_vec(), b"m1".to_vec()],
+        validator: Some(validator),
+    };
+    let data = delegate_instruction_data(&args).unwrap();
+
+    let mut expected = vec![0u8; 8];
+    expected.extend(30_000u32.to_le_bytes());
+    expected.extend(2u32.to_le_bytes()); // Two seeds
+    expected.extend(11u32.to_le_bytes());
+    expected.extend(b"chess_match");
+    expected.extend(2u32.to_le_bytes());
+    expected.extend(b"m1");
+    expected.push(1); // Some(validator)
+    expected.extend(validator.to_bytes());
+    assert_eq!(data, expected);
+    assert_eq!(DelegateArgs::deserialize(&mut &data[8..]).unwrap(), args);
+}
+
+#[test]
+fn schedule_commit_targets_the_magic_program() {
+    let payer = Pubkey::new_unique();
+    let chess_match = Pubkey::new_unique();
+    let move_history = Pubkey::new_unique();
+
+    let commit = schedule_commit_instruction(&payer, &[chess_match, move_history], false);
+    assert_eq!(commit.program_id, MAGIC_PROGRAM_ID);
+    assert_eq!(commit.data, vec![1, 0, 0, 0]);
+    let keys: Vec<(Pubkey, bool, bool)> = commit.accounts.iter().map(|meta| (meta.pubkey, meta.is_signer, meta.is_writable)).collect();
+    assert_eq!(
+        keys,
+        vec![(payer, true, true), (MAGIC_CONTEXT_ID, false, true), (chess_match, false, true), (move_history, false, true)]
+    );
+
+    let undelegate = schedule_commit_instruction(&payer, &[chess_match], true);
+    assert_eq!(undelegate.data, vec![2, 0, 0, 0]);
+}
+
+#[test]
+fn delegation_accounts_are_derived_per_delegated_account() {
+    let chess_match = Pubkey::new_unique();
+    let (buffer, _) = buffer_address(&chess_match);
+    assert_eq!(buffer, Pubkey::find_program_address(&[b"buffer", chess_match.as_ref()], &counter::ID).0);
+    assert_eq!(
+        delegation_record_address(&chess_match),
+        Pubkey::find_program_address(&[b"delegation", chess_match.as_ref()], &DELEGATION_PROGRAM_ID).0
+    );
+    assert_ne!(delegation_record_address(&chess_match), delegation_metadata_address(&chess_match));
+}
@@ -0,0 +1,143 @@
+// tests/expire_match.rs
+// Join deadlines of open challenges, and the refund expire_match pays the creator from the escrow.
+use std::sync::Once;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+use anchor_lang::{Discriminator, Space};
+use anchor_spl::token::spl_token::state::{Account as SplTokenAccount, AccountState};
+use anchor_spl::token::{spl_token, Token, TokenAccount};
+use counter::errors::ChessError;
+use counter::state::*;
+use counter::utils::payout_logic::refund_and_close_escrow;
+
+// Just enough of the token program for refund_and_close_escrow: Transfer and CloseAccount, with the
+// authority's PDA signature checked against the seeds the program signs with.
+struct TokenProgramStub;
+
+impl SyscallStubs for TokenProgramStub {
+    fn sol_invoke_signed(&self, instruction: &Instruction, account_infos: &[AccountInfo], signers_seeds: &[&[&[u8]]]) -> ProgramResult {
+        assert_eq!(instruction.program_id, spl_token::ID);
+        let info = |index: usize| account_infos.iter().find(|info| *info.key == instruction.accounts[index].pubkey).unwrap();
+        let authority = info(2);
+        let signed = signers_seeds
+            .iter()
+            .any(|seeds| Pubkey::create_program_address(seeds, &counter::ID).as_ref() == Ok(authority.key));
+        assert!(authority.is_signer || signed, "authority did not sign");
+
+        let (source, destination) = (info(0), info(1));
+        let mut source_state = SplTokenAccount::unpack(&source.try_borrow_data()?)?;
+        assert_eq!(source_state.owner, *authority.key);
+        match instruction.data[0] {
+            3 => {
+                let amount = u64::from_le_bytes(instruction.data[1..9].try_into().unwrap());
+                let mut destination_state = SplTokenAccount::unpack(&destination.try_borrow_data()?)?;
+                source_state.amount -= amount;
+                destination_state.amount += amount;
+                SplTokenAccount::pack(source_state, &mut source.try_borrow_mut_data()?)?;
+                SplTokenAccount::pack(destination_state, &mut destination.try_borrow_mut_data()?)?;
+            }
+            9 => {
+                assert_eq!(source_state.amount, 0, "only empty token accounts can be closed");
+                **destination.try_borrow_mut_lamports()? += source.lamports();
+                **source.try_borrow_mut_lamports()? = 0;
+                source.try_borrow_mut_data()?.fill(0);
+            }
+            other => panic!("unexpected token instruction {}", other),
+        }
+        Ok(())
+    }
+}
+
+static STUBS: Once = Once::new();
+
+fn token_account_data(mint: Pubkey, owner: Pubkey, amount: u64) -> Vec<u8> {
+    let state = SplTokenAccount {
+        mint,
+        owner,
+        amount,
+        delegate: COption::None,
+        state: AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    };
+    let mut data = vec![0; SplTokenAccount::LEN];
+    SplTokenAccount::pack(state, &mut data).unwrap();
+    data
+}
+
+fn open_challenge(join_deadline: Option<i64>) -> ChessMatch {
+    let mut chess_match = ChessMatch::default();
+    chess_match.set_match_id("expiring");
+    chess_match.players[0] = Pubkey::new_unique();
+    chess_match.set_join_deadline(join_deadline);
+    chess_match.set_game_status(GameStatus::WaitingForOpponent);
+    chess_match
+}
+
+#[test]
+fn challenge_is_joinable_until_its_deadline_and_expirable_after() {
+    let chess_match = open_challenge(Some(1_000));
+    chess_match.check_joinable_at(999).unwrap();
+    chess_match.check_joinable_at(1_000).unwrap();
+    assert_eq!(chess_match.check_joinable_at(1_001).unwrap_err(), ChessError::JoinDeadlinePassed.into());
+
+    assert_eq!(chess_match.check_expirable_at(1_000).unwrap_err(), ChessError::JoinDeadlineNotReached.into());
+    chess_match.check_expirable_at(1_001).unwrap();
+
+    // Without a deadline a challenge stays open and cannot be expired
+    let open_ended = open_challenge(None);
+    open_ended.check_joinable_at(i64::MAX).unwrap();
+    assert_eq!(open_ended.check_expirable_at(i64::MAX).unwrap_err(), ChessError::NoJoinDeadline.into());
+}
+
+#[test]
+fn expiry_refunds_the_whole_escrow_and_its_rent_to_the_creator() {
+    STUBS.call_once(|| {
+        set_syscall_stubs(Box::new(TokenProgramStub));
+    });
+    let mut chess_match = open_challenge(Some(1_000));
+    let (match_key, bump) = Pubkey::find_program_address(&[b"chess_match", b"expiring"], &counter::ID);
+    chess_match.bump = bump;
+    let creator = chess_match.players[0];
+    let mint = Pubkey::new_unique();
+    let stake = 10_000_000;
+
+    let mut match_data = ChessMatch::DISCRIMINATOR.to_vec();
+    match_data.extend_from_slice(bytemuck::bytes_of(&chess_match));
+    assert_eq!(match_data.len(), 8 + ChessMatch::INIT_SPACE);
+    let mut match_lamports = 1;
+    let match_info = AccountInfo::new(&match_key, false, true, &mut match_lamports, &mut match_data, &counter::ID, false, 0);
+
+    let (escrow_key, creator_tokens_key, token_program_key) = (Pubkey::new_unique(), Pubkey::new_unique(), spl_token::ID);
+    let mut escrow_data = token_account_data(mint, match_key, stake);
+    let mut escrow_lamports = 2_039_280;
+    let escrow_info = AccountInfo::new(&escrow_key, false, true, &mut escrow_lamports, &mut escrow_data, &token_program_key, false, 0);
+    let mut creator_tokens_data = token_account_data(mint, creator, 5);
+    let mut creator_tokens_lamports = 2_039_280;
+    let creator_tokens_info =
+        AccountInfo::new(&creator_tokens_key, false, true, &mut creator_tokens_lamports, &mut creator_tokens_data, &token_program_key, false, 0);
+    let mut creator_lamports = 0;
+    let mut creator_data = Vec::new();
+    let system_program = Pubkey::default();
+    let creator_info = AccountInfo::new(&creator, false, true, &mut creator_lamports, &mut creator_data, &system_program, false, 0);
+    let mut program_lamports = 1;
+    let mut program_data = Vec::new();
+    let loader = Pubkey::new_unique();
+    let token_program_info = AccountInfo::new(&token_program_key, false, false, &mut program_lamports, &mut program_data, &loader, true, 0);
+
+    let chess_match_loader = AccountLoader::<ChessMatch>::try_from(&match_info).unwrap();
+    let escrow = Account::<TokenAccount>::try_from(&escrow_info).unwrap();
+    let token_program = Program::<Token>::try_from(&token_program_info).unwrap();
+    let refunded = refund_and_close_escrow(&chess_match_loader, &escrow, &creator_tokens_info, &creator_info, &token_program).unwrap();
+
+    assert_eq!(refunded, stake);
+    assert_eq!(SplTokenAccount::unpack(&creator_tokens_info.try_borrow_data().unwrap()).unwrap().amount, stake + 5);
+    assert_eq!(escrow_info.lamports(), 0);
+    assert_eq!(creator_info.lamports(), 2_039_280);
+}
@@ -0,0 +1,68 @@
+// tests/lobby.rs
+// Listing challenges on a lobby page until it fills up, and removing them when they leave the lobby.
+use anchor_lang::prelude::*;
+use counter::errors::ChessError;
+use counter::state::*;
+
+fn listing(match_id: &str) -> LobbyListing {
+    LobbyListing {
+        match_id: match_id.to_string(),
+        creator: Pubkey::new_unique(),
+        creator_rating: None,
+        betting_token_mint: Pubkey::new_unique(),
+        bet_amount: 10_000_000,
+        move_timeout_duration: 30,
+        variant: Variant::Standard,
+        rated: false,
+    }
+}
+
+fn empty_page() -> LobbyPage {
+    LobbyPage { page_index: 0, bump: 255, listings: Vec::new() }
+}
+
+#[test]
+fn listings_are_appended_until_the_page_is_full() {
+    let mut page = empty_page();
+    for index in 0..LOBBY_PAGE_CAPACITY {
+        page.add_listing(listing(&format!("match-{}", index))).unwrap();
+    }
+    assert_eq!(page.listings.len(), LOBBY_PAGE_CAPACITY);
+    assert_eq!(page.listings[0].match_id, "match-0"); // Oldest first
+    assert_eq!(page.add_listing(listing("overflow")).unwrap_err(), ChessError::LobbyPageFull.into());
+    assert_eq!(page.listings.len(), LOBBY_PAGE_CAPACITY);
+
+    // Removing one makes room again
+    page.remove_listing("match-3");
+    page.add_listing(listing("overflow")).unwrap();
+    assert_eq!(page.listings.last().unwrap().match_id, "overflow");
+}
+
+#[test]
+fn removing_a_listing_keeps_the_others_in_order() {
+    let mut page = empty_page();
+    for match_id in ["first", "second", "third"] {
+        page.add_listing(listing(match_id)).unwrap();
+    }
+    page.remove_listing("second");
+    let remaining: Vec<&str> = page.listings.iter().map(|listing| listing.match_id.as_str()).collect();
+    assert_eq!(remaining, ["first", "third"]);
+
+    // Unknown ids (e.g. a challenge already joined) leave the page as it is
+    page.remove_listing("second");
+    page.remove_listing("never-listed");
+    assert_eq!(page.listings.len(), 2);
+}
+
+#[test]
+fn a_full_page_fits_its_account_space() {
+    let mut page = empty_page();
+    for index in 0..LOBBY_PAGE_CAPACITY {
+        let mut long = listing(&format!("{:0>width$}", index, width = MAX_MATCH_ID_LEN));
+        long.creator_rating = Some(1500);
+        page.add_listing(long).unwrap();
+    }
+    let mut account_data = Vec::new();
+    page.try_serialize(&mut account_data).unwrap();
+    assert_eq!(account_data.len(), 8 + LobbyPage::INIT_SPACE);
+}
@@ -0,0 +1,82 @@
+// tests/match_queue.rs
+// Pairing rules of the matchmaking queue.
+use anchor_lang::prelude::*;
+use counter::errors::ChessError;
+use counter::state::{GlickoRating, MatchQueue, PlayerProfile, QueueEntry, TimeControl};
+
+fn entry(rating: Option<u16>, rating_window: Option<u16>) -> QueueEntry {
+    QueueEntry { player: Pubkey::new_unique(), rating, rating_window, enqueued_at: 0 }
+}
+
+#[test]
+fn pairs_the_longest_waiting_player_within_both_rating_windows() {
+    let strong = entry(Some(1900), Some(100));
+    let unrated = entry(None, None);
+    let close = entry(Some(1550), Some(100));
+    let mut queue = MatchQueue {
+        betting_token_mint: Pubkey::new_unique(),
+        bet_amount: 10_000_000,
+        move_timeout_duration: 30,
+        bump: 255,
+        matches_created: 0,
+        entries: vec![strong, unrated, close],
+    };
+
+    // A 1500 player with a 100 point window skips the 1900 player but pairs with anyone unrated
+    assert_eq!(queue.find_opponent(&entry(Some(1500), Some(100))), Some(1));
+    queue.entries.remove(1);
+    assert_eq!(queue.find_opponent(&entry(Some(1500), Some(100))), Some(1));
+    // No window of your own still respects the waiting player's
+    assert_eq!(queue.find_opponent(&entry(Some(1700), None)), None);
+    // Players are never paired with themselves
+    assert_eq!(queue.find_opponent(&strong), None);
+
+    // Every pairing gets a fresh match id that fits a PDA seed
+    let first_id = queue.next_match_id();
+    queue.matches_created += 1;
+    assert_eq!(first_id.len(), 32);
+    assert_ne!(queue.next_match_id(), first_id);
+}
+
+#[test]
+fn queue_reads_the_rating_from_any_created_profile() {
+    let key = Pubkey::new_unique();
+    let system_program = Pubkey::default();
+
+    // Not created yet: the player queues unrated
+    let (mut lamports, mut data) = (0, Vec::new());
+    let uncreated = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &system_program, false, 0);
+    assert_eq!(PlayerProfile::rating_of(&uncreated).unwrap(), None);
+
+    let profile = PlayerProfile {
+        player: Pubkey::new_unique(),
+        bump: 255,
+        rating: 1840,
+        games_played: 12,
+        wins: 7,
+        losses: 4,
+        draws: 1,
+        glicko: [GlickoRating::default(); TimeControl::COUNT],
+    };
+    let mut profile_data = Vec::new();
+    profile.try_serialize(&mut profile_data).unwrap();
+    let mut lamports = 1;
+    let created = AccountInfo::new(&key, false, false, &mut lamports, &mut profile_data, &counter::ID, false, 0);
+    assert_eq!(PlayerProfile::rating_of(&created).unwrap(), Some(1840));
+
+    // Data at the PDA that this program does not own is refused rather than read as "no profile"
+    let mut lamports = 1;
+    let mut foreign_data = vec![1; 16];
+    let foreign = AccountInfo::new(&key, false, false, &mut lamports, &mut foreign_data, &system_program, false, 0);
+    assert!(PlayerProfile::rating_of(&foreign).is_err());
+}
+
+#[test]
+fn queued_matches_need_a_positive_move_timeout() {
+    MatchQueue::check_move_timeout(1).unwrap();
+    MatchQueue::check_move_timeout(60).unwrap();
+    // An untimed rated game could never be claimed on time
+    for move_timeout_duration in [0, -1, i64::MIN] {
+        assert_eq!(MatchQueue::check_move_timeout(move_timeout_duration).unwrap_err(), ChessError::InvalidTimeout.into());
+    }
+}
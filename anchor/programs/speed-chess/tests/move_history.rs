@@ -0,0 +1,74 @@
+// tests/move_history.rs
+// Packing plies into MoveHistory and decoding them back, from the struct and from raw account data.
+use anchor_lang::prelude::*;
+use counter::state::{
+    decode_move_history, pack_move, unpack_move, MoveHistory, PieceType, RecordedMove, MAX_CLOCK_SNAPSHOT_SECONDS,
+};
+
+fn ply(from: (u8, u8), to: (u8, u8), promotion: Option<PieceType>, clock_seconds: u32) -> RecordedMove {
+    RecordedMove { from_row: from.0, from_col: from.1, to_row: to.0, to_col: to.1, promotion, clock_seconds }
+}
+
+#[test]
+fn every_square_promotion_and_drop_round_trips() {
+    for from in 0..64u8 {
+        for to in [0u8, 27, 63] {
+            let recorded = ply((from / 8, from % 8), (to / 8, to % 8), None, 5);
+            assert_eq!(unpack_move(pack_move(&recorded)), recorded);
+        }
+    }
+    // Promotions on the last rank, and drops (from == to, dropped piece in the promotion slot)
+    for piece_type in [PieceType::Knight, PieceType::Bishop, PieceType::Rook, PieceType::Queen] {
+        let promotion = ply((6, 0), (7, 1), Some(piece_type), 12);
+        assert_eq!(unpack_move(pack_move(&promotion)), promotion);
+        let drop = ply((4, 4), (4, 4), Some(piece_type), 0);
+        assert_eq!(unpack_move(pack_move(&drop)), drop);
+    }
+    let pawn_drop = ply((2, 3), (2, 3), Some(PieceType::Pawn), 1);
+    assert_eq!(unpack_move(pack_move(&pawn_drop)), pawn_drop);
+}
+
+#[test]
+fn clock_snapshot_saturates_instead_of_wrapping() {
+    let limit = (1 << 17) - 1;
+    assert_eq!(MAX_CLOCK_SNAPSHOT_SECONDS, limit);
+    let at_limit = ply((1, 4), (3, 4), Some(PieceType::Queen), limit);
+    assert_eq!(unpack_move(pack_move(&at_limit)), at_limit);
+
+    // Above 2^17 the low bits would land on a different, shorter time; the field pins at the maximum instead
+    for clock_seconds in [1 << 17, (1 << 17) + 5, u32::MAX] {
+        let long_think = ply((1, 4), (3, 4), Some(PieceType::Queen), clock_seconds);
+        let decoded = unpack_move(pack_move(&long_think));
+        assert_eq!(decoded.clock_seconds, MAX_CLOCK_SNAPSHOT_SECONDS);
+        assert_eq!(decoded, RecordedMove { clock_seconds: MAX_CLOCK_SNAPSHOT_SECONDS, ..long_think });
+    }
+}
+
+#[test]
+fn account_data_decodes_to_the_pushed_moves() {
+    let plies = vec![
+        ply((1, 4), (3, 4), None, 3),
+        ply((6, 4), (4, 4), None, 200_000),
+        ply((6, 0), (7, 0), Some(PieceType::Knight), 9),
+        ply((5, 5), (5, 5), Some(PieceType::Pawn), 0),
+    ];
+    let mut history = MoveHistory { match_id: "history".to_string(), bump: 254, moves: Vec::new() };
+    for recorded in &plies {
+        history.push(recorded);
+    }
+    let expected: Vec<RecordedMove> = plies
+        .iter()
+        .map(|recorded| RecordedMove { clock_seconds: recorded.clock_seconds.min(MAX_CLOCK_SNAPSHOT_SECONDS), ..*recorded })
+        .collect();
+    assert_eq!(history.decode_moves(), expected);
+
+    let mut account_data = Vec::new();
+    history.try_serialize(&mut account_data).unwrap();
+    // Discriminator, match_id (length + bytes), bump, moves (length + one u32 per ply)
+    assert_eq!(account_data.len(), 8 + (4 + 7) + 1 + (4 + 4 * plies.len()));
+    assert_eq!(decode_move_history(&account_data).unwrap(), expected);
+
+    // Data from another account type is rejected by the discriminator check
+    account_data[0] ^= 0xff;
+    assert!(decode_move_history(&account_data).is_err());
+}
@@ -0,0 +1,312 @@
+// tests/pgn_replay.rs
+// Replays every game in tests/pgn_corpus through chess_logic, plus a few deliberately broken games.
+// Requires the "pgn" feature: cargo test --features pgn (run by npm run ci and the Program tests workflow)
+use std::fs;
+use std::path::Path;
+
+use anchor_lang::prelude::*;
+use counter::errors::ChessError;
+use counter::events::MoveMadeEvent;
+use counter::pgn::*;
+use counter::state::{
+    ChessMatch, GameEndReason, GameStatus, Handicap, MoveResult, Piece, PieceType, PlayerColor, RecordedMove, Variant,
+};
+
+fn corpus() -> Vec<(String, String)> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/pgn_corpus");
+    let mut files: Vec<_> = fs::read_dir(&dir)
+        .expect("corpus directory")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "pgn"))
+        .collect();
+    files.sort();
+    files
+        .into_iter()
+        .map(|path| (path.file_name().unwrap().to_string_lossy().into_owned(), fs::read_to_string(&path).unwrap()))
+        .collect()
+}
+
+fn assert_illegal(pgn: &str, expected_ply: usize, expected: ChessError) {
+    match replay_pgn_text(pgn) {
+        Err(ReplayError::IllegalMove { ply, error, san }) => {
+            assert_eq!(ply, expected_ply, "wrong ply reported for {}", san);
+            assert_eq!(error, Error::from(expected), "wrong error for {}", san);
+        }
+        other => panic!("expected an illegal move, got {:?}", other),
+    }
+}
+
+#[test]
+fn corpus_games_replay_and_match_their_results() {
+    let games = corpus();
+    assert!(!games.is_empty());
+    for (name, text) in games {
+        let parsed = parse_pgn(&text).unwrap_or_else(|e| panic!("{}: {}", name, e));
+        let outcome = replay_pgn(&parsed).unwrap_or_else(|e| panic!("{}: {:?}", name, e));
+        assert_eq!(outcome.moves.len(), parsed.moves.len(), "{}", name);
+
+        // Decisive corpus games all end in mate on the board
+        if matches!(parsed.result.as_deref(), Some("1-0") | Some("0-1")) {
+            assert_eq!(outcome.final_move_result, Some(MoveResult::Checkmate), "{}", name);
+        }
+    }
+}
+
+#[test]
+fn corpus_games_round_trip_through_export() {
+    for (name, text) in corpus() {
+        let parsed = parse_pgn(&text).unwrap();
+        if parsed.tag("FEN").is_some() {
+            continue; // Export always starts from the standard position
+        }
+        let outcome = replay_pgn(&parsed).unwrap();
+        let game = PgnGame {
+            status: match parsed.result.as_deref() {
+                Some("1-0") => GameStatus::WhiteWins,
+                Some("0-1") => GameStatus::BlackWins,
+                Some("1/2-1/2") => GameStatus::Draw,
+                _ => GameStatus::Active,
+            },
+            ..PgnGame::from_match(&outcome.game_state, &[])
+        };
+        let exported = PgnGame { moves: outcome.moves.clone(), ..game }.to_pgn().unwrap();
+        let reparsed = parse_pgn(&exported).unwrap();
+        assert_eq!(reparsed.moves, parsed.moves, "{}", name);
+        assert_eq!(reparsed.result, parsed.result, "{}", name);
+    }
+}
+
+#[test]
+fn export_renders_tags_and_clock_comments() {
+    let outcome = replay_pgn_text("1. e4 e5 2. Qh5 Nc6 3. Bc4 Nf6 4. Qxf7# 1-0").unwrap();
+    let moves: Vec<RecordedMove> = outcome
+        .moves
+        .iter()
+        .map(|m| RecordedMove { from_row: m.from_row, from_col: m.from_col, to_row: m.to_row, to_col: m.to_col, promotion: m.promotion, clock_seconds: 65 })
+        .collect();
+    let mut game = PgnGame::from_match(&outcome.game_state, &moves);
+    game.move_timeout_duration = 30;
+    game.status = GameStatus::WhiteWins;
+    let pgn = game.to_pgn().unwrap();
+
+    for tag in ["Event", "Site", "Date", "Round", "White", "Black", "Result"] {
+        assert!(pgn.contains(&format!("[{} ", tag)), "missing {} tag", tag);
+    }
+    assert!(pgn.contains("[TimeControl \"1/30\"]"));
+    let movetext = pgn.split("\n\n").nth(1).unwrap().replace('\n', " ");
+    assert!(movetext.trim_end().ends_with("4. Qxf7# {[%emt 0:01:05]} 1-0"));
+}
+
+#[test]
+fn chess960_castling_replays_and_exports_with_start_position() {
+    let text = fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/pgn_corpus/chess960_castling_both_sides.pgn")).unwrap();
+    let outcome = replay_pgn_text(&text).unwrap();
+    let board = outcome.game_state.board();
+    let piece = |piece_type, color| Some(Piece { piece_type, color });
+    // O-O-O from b1 lands on c1/d1; O-O from b8 lands on g8 with the f8 rook staying put
+    assert_eq!(board[0][2], piece(PieceType::King, PlayerColor::White));
+    assert_eq!(board[0][3], piece(PieceType::Rook, PlayerColor::White));
+    assert_eq!(board[7][6], piece(PieceType::King, PlayerColor::Black));
+    assert_eq!(board[7][5], piece(PieceType::Rook, PlayerColor::Black));
+
+    // 719 is RKQNNRBB in the Scharnagl numbering
+    let mut game = PgnGame::from_match(&outcome.game_state, &[]);
+    game.variant = Variant::Chess960;
+    game.start_position_index = 719;
+    game.moves = outcome.moves.clone();
+    let exported = game.to_pgn().unwrap();
+    assert!(exported.contains("[Variant \"Chess960\"]"));
+    assert!(exported.contains("[FEN \"rkqnnrbb/pppppppp/8/8/8/8/PPPPPPPP/RKQNNRBB w FAfa - 0 1\"]"));
+    assert_eq!(parse_pgn(&exported).unwrap().moves, parse_pgn(&text).unwrap().moves);
+}
+
+#[test]
+fn chess960_king_two_file_move_is_not_castling() {
+    let pgn = "[Variant \"Chess960\"]\n[FEN \"rkqnnrbb/pppppppp/8/8/8/8/PPPPPPPP/RKQNNRBB w FAfa - 0 1\"]\n\n1. Ne3 Ne6 2. d3 Nf6 3. Qd2 d6 4. Kd1 *";
+    assert_illegal(pgn, 6, ChessError::InvalidMoveIllegalPieceMovement);
+}
+
+#[test]
+fn odds_games_export_their_start_position() {
+    let text = "[SetUp \"1\"]\n[FEN \"r1bqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1\"]\n\n1. e4 e5 2. Nf3 Nf6 *";
+    let outcome = replay_pgn_text(text).unwrap();
+    let mut game = PgnGame::from_match(&outcome.game_state, &[]);
+    game.handicap = Handicap::KnightOdds;
+    game.handicap_color = PlayerColor::Black; // Creator playing Black after a rematch
+    game.moves = outcome.moves.clone();
+    let exported = game.to_pgn().unwrap();
+    assert!(exported.contains("[SetUp \"1\"]"));
+    assert!(exported.contains("[FEN \"r1bqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1\"]"));
+    assert_eq!(parse_pgn(&exported).unwrap().moves, parse_pgn(text).unwrap().moves);
+
+    // Rook odds also give up queenside castling; time odds keep the standard setup
+    let mut rook_odds = ChessMatch::default();
+    rook_odds.set_handicap(Handicap::RookOdds);
+    rook_odds.reset_position();
+    assert_eq!(rook_odds.piece_at(0, 0), None);
+    assert_eq!(rook_odds.castling_rights().white_queenside, None);
+    assert_eq!(rook_odds.castling_rights().black_queenside, Some(0));
+
+    let mut time_odds = ChessMatch::default();
+    time_odds.move_timeout_duration = 60;
+    time_odds.set_handicap(Handicap::TimeOdds { creator_move_timeout: 30 });
+    assert_eq!(time_odds.move_timeout_for(PlayerColor::White), 30);
+    assert_eq!(time_odds.move_timeout_for(PlayerColor::Black), 60);
+    game.handicap = time_odds.handicap();
+    assert!(!game.to_pgn().unwrap().contains("[FEN"));
+}
+
+#[test]
+fn king_of_the_hill_ends_when_a_king_reaches_the_centre() {
+    let outcome = replay_pgn_text("[Variant \"King of the Hill\"]\n\n1. e3 a6 2. Ke2 a5 3. Kd3 a4 4. Ke4 1-0").unwrap();
+    assert_eq!(
+        outcome.final_move_result,
+        Some(MoveResult::VariantWin { winner: PlayerColor::White, reason: GameEndReason::KingOfTheHill })
+    );
+    match replay_pgn_text("[Variant \"King of the Hill\"]\n\n1. e3 a6 2. Ke2 a5 3. Kd3 a4 4. Ke4 a3 1-0") {
+        Err(ReplayError::MoveAfterGameEnd { ply, .. }) => assert_eq!(ply, 7),
+        other => panic!("expected MoveAfterGameEnd, got {:?}", other),
+    }
+    // Without the tag the same king walk is an ordinary move
+    let standard = replay_pgn_text("1. e3 a6 2. Ke2 a5 3. Kd3 a4 4. Ke4 *").unwrap();
+    assert_eq!(standard.final_move_result, Some(MoveResult::Normal));
+}
+
+#[test]
+fn three_check_ends_on_the_third_check() {
+    let outcome = replay_pgn_text("[Variant \"Three-check\"]\n\n1. e4 d6 2. Bb5+ Bd7 3. Bxd7+ Kxd7 4. Qg4+ 1-0").unwrap();
+    assert_eq!(outcome.game_state.checks_given, [3, 0]);
+    assert_eq!(
+        outcome.final_move_result,
+        Some(MoveResult::VariantWin { winner: PlayerColor::White, reason: GameEndReason::ThreeCheck })
+    );
+
+    let mut game = PgnGame::from_match(&outcome.game_state, &[]);
+    game.variant = Variant::ThreeCheck;
+    game.moves = outcome.moves.clone();
+    assert!(game.to_pgn().unwrap().contains("[Variant \"Three-check\"]"));
+}
+
+#[test]
+fn crazyhouse_drops_replay_and_export() {
+    // White blocks the check with the pawn it captured; Black drops its pawn on d4
+    let text = "[Variant \"Crazyhouse\"]\n\n1. e4 d5 2. exd5 Qxd5 3. Nc3 Qe5+ 4. P@e2 P@d4 *";
+    let outcome = replay_pgn_text(text).unwrap();
+    let board = outcome.game_state.board();
+    assert_eq!(board[1][4], Some(Piece { piece_type: PieceType::Pawn, color: PlayerColor::White }));
+    assert_eq!(board[3][3], Some(Piece { piece_type: PieceType::Pawn, color: PlayerColor::Black }));
+    assert_eq!(outcome.game_state.pockets, [Default::default(); 2]);
+
+    let mut game = PgnGame::from_match(&outcome.game_state, &[]);
+    game.variant = Variant::Crazyhouse;
+    game.moves = outcome.moves.clone();
+    let exported = game.to_pgn().unwrap();
+    assert!(exported.contains("[Variant \"Crazyhouse\"]"));
+    assert!(exported.contains("4. P@e2 P@d4"));
+
+    // The same game rebuilt from MoveMadeEvents, where a drop carries drop_piece instead of a promotion
+    let events: Vec<MoveMadeEvent> = outcome
+        .moves
+        .iter()
+        .map(|m| {
+            let drop_piece = if (m.from_row, m.from_col) == (m.to_row, m.to_col) { m.promotion } else { None };
+            MoveMadeEvent {
+                match_id: "zh".to_string(),
+                player: Pubkey::default(),
+                player_color: PlayerColor::White,
+                algebraic_move: String::new(),
+                from_row: if drop_piece.is_some() { 0 } else { m.from_row }, // a drop's from_* mean nothing
+                from_col: m.from_col,
+                to_row: m.to_row,
+                to_col: m.to_col,
+                promotion_piece: if drop_piece.is_some() { None } else { m.promotion },
+                drop_piece,
+                move_time_seconds: 0,
+                board_fen: String::new(),
+                is_check: false,
+                is_checkmate: false,
+                is_stalemate: false,
+            }
+        })
+        .collect();
+    let mut from_events = PgnGame::from_events(Pubkey::default(), Pubkey::default(), 30, &events, None);
+    from_events.variant = Variant::Crazyhouse;
+    let timed: Vec<PgnMove> = outcome.moves.iter().map(|&m| PgnMove { clock_seconds: Some(0), ..m }).collect();
+    assert_eq!(from_events.moves, timed);
+    let exported = from_events.to_pgn().unwrap().replace('\n', " ");
+    assert!(exported.contains("4. P@e2 {[%emt 0:00:00]} P@d4"));
+
+    // Without the variant tag drops are rejected
+    assert_illegal("1. e4 d5 2. exd5 Qxd5 3. Nc3 Qe5+ 4. P@e2 *", 6, ChessError::DropNotAllowed);
+}
+
+#[test]
+fn crazyhouse_pawn_drop_on_back_rank_is_rejected() {
+    assert_illegal("[Variant \"Crazyhouse\"]\n\n1. Nf3 e5 2. Nxe5 d6 3. P@g1 *", 4, ChessError::InvalidDropPawnOnBackRank);
+}
+
+#[test]
+fn atomic_capture_next_to_the_king_wins() {
+    let outcome = replay_pgn_text("[Variant \"Atomic\"]\n\n1. e3 e6 2. Qh5 a6 3. Qxf7 1-0").unwrap();
+    assert_eq!(
+        outcome.final_move_result,
+        Some(MoveResult::VariantWin { winner: PlayerColor::White, reason: GameEndReason::KingExploded })
+    );
+    // Queen, pawn, king, knight and bishop are all gone from around f7
+    let board = outcome.game_state.board();
+    assert!([(6, 5), (7, 4), (7, 5), (7, 6)].iter().all(|&(row, col)| board[row][col].is_none()));
+    assert!(board[6][6].is_some(), "the g7 pawn survives");
+}
+
+#[test]
+fn antichess_requires_captures() {
+    assert_illegal("[Variant \"Antichess\"]\n\n1. e3 b5 2. Nf3 *", 2, ChessError::InvalidMoveCaptureRequired);
+    let outcome = replay_pgn_text("[Variant \"Antichess\"]\n\n1. e3 b5 2. Bxb5 *").unwrap();
+    assert_eq!(outcome.final_move_result, Some(MoveResult::Normal));
+}
+
+#[test]
+fn pinned_piece_reports_leaves_king_in_check() {
+    assert_illegal("1. e4 e5 2. Nc3 Bb4 3. d3 Nf6 4. Nd5 *", 6, ChessError::InvalidMoveLeavesKingInCheck);
+}
+
+#[test]
+fn blocked_bishop_reports_illegal_piece_movement() {
+    assert_illegal("1. e4 e5 2. Bb5 Nc6 3. Bd7 *", 4, ChessError::InvalidMoveIllegalPieceMovement);
+}
+
+#[test]
+fn castling_through_check_is_rejected() {
+    // The bishop on a6 covers f1 once e2 and f1 have been vacated
+    assert_illegal("1. g3 b6 2. Bg2 Ba6 3. Nf3 Nc6 4. e4 e6 5. O-O *", 8, ChessError::InvalidMoveIllegalPieceMovement);
+}
+
+#[test]
+fn promotion_to_king_is_rejected() {
+    let pgn = "[FEN \"4k3/P7/8/8/8/8/8/4K3 w - - 0 1\"]\n\n1. a8=K *";
+    assert_illegal(pgn, 0, ChessError::InvalidPromotionPiece);
+}
+
+#[test]
+fn moves_after_mate_are_rejected() {
+    match replay_pgn_text("1. f3 e5 2. g4 Qh4# 3. a3 0-1") {
+        Err(ReplayError::MoveAfterGameEnd { ply, .. }) => assert_eq!(ply, 4),
+        other => panic!("expected MoveAfterGameEnd, got {:?}", other),
+    }
+}
+
+#[test]
+fn stray_closing_delimiters_are_rejected() {
+    assert_eq!(parse_pgn("1. e4 ) e5 *"), Err(PgnParseError::UnbalancedDelimiter(')')));
+    assert_eq!(parse_pgn("1. e4 e5} 2. Nf3 *"), Err(PgnParseError::UnbalancedDelimiter('}')));
+    assert_eq!(parse_pgn("1. e4 (1. d4 d5)) e5 *"), Err(PgnParseError::UnbalancedDelimiter(')')));
+    assert_eq!(parse_pgn("1. e4 (1. d4 {a comment} d5) e5 *").unwrap().moves, vec!["e4", "e5"]);
+}
+
+#[test]
+fn percent_escapes_only_a_whole_line() {
+    let parsed = parse_pgn("[Event \"Escape\"]\n%private data\n1. e4 e5\n%more 2. Nf3\n2. Nc3 *").unwrap();
+    assert_eq!(parsed.moves, vec!["e4", "e5", "Nc3"]);
+    // Mid-line `%` is not an escape, so it cannot hide the rest of the line
+    assert_eq!(parse_pgn("1. e4 %e5 2. Nf3 *").unwrap().moves, vec!["e4", "%e5", "Nf3"]);
+}
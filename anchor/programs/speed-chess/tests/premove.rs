@@ -0,0 +1,59 @@
+// tests/premove.rs
+// Premoves are played straight after the opponent's move when still legal, and dropped otherwise.
+use counter::state::{ChessMatch, MoveResult, Piece, PieceType, PlayerColor, Premove};
+use counter::utils::chess_logic::{apply_premove, validate_and_apply_move};
+
+fn new_game() -> ChessMatch {
+    let mut game_state = ChessMatch::default();
+    game_state.reset_position();
+    game_state
+}
+
+fn play(game_state: &mut ChessMatch, from: (u8, u8), to: (u8, u8)) {
+    let color = game_state.current_turn();
+    assert_eq!(validate_and_apply_move(game_state, from.0, from.1, to.0, to.1, color, None).unwrap(), MoveResult::Normal);
+}
+
+fn premove(color: PlayerColor, from: (u8, u8), to: (u8, u8)) -> Premove {
+    Premove { color, from_row: from.0, from_col: from.1, to_row: to.0, to_col: to.1, promotion: None, drop_piece: None }
+}
+
+#[test]
+fn legal_premove_is_played_for_the_side_now_on_move() {
+    let mut game_state = new_game();
+    let queued = premove(PlayerColor::Black, (6, 4), (4, 4)); // ...e5
+    game_state.set_premove(Some(queued));
+    play(&mut game_state, (1, 4), (3, 4)); // 1. e4
+
+    assert_eq!(apply_premove(&mut game_state), Some((queued, MoveResult::Normal)));
+    assert_eq!(game_state.premove(), None);
+    assert_eq!(game_state.current_turn(), PlayerColor::White);
+    assert_eq!(game_state.piece_at(4, 4), Some(Piece { piece_type: PieceType::Pawn, color: PlayerColor::Black }));
+    assert_eq!(game_state.piece_at(6, 4), None);
+}
+
+#[test]
+fn premove_made_illegal_by_the_reply_is_discarded() {
+    let mut game_state = new_game();
+    play(&mut game_state, (1, 4), (3, 4)); // 1. e4
+    play(&mut game_state, (6, 3), (4, 3)); // 1... d5
+    game_state.set_premove(Some(premove(PlayerColor::Black, (4, 3), (3, 4)))); // ...dxe4
+    play(&mut game_state, (3, 4), (4, 4)); // 2. e5 sidesteps the capture
+    let board_before = game_state.board();
+
+    assert_eq!(apply_premove(&mut game_state), None);
+    assert_eq!(game_state.premove(), None);
+    assert_eq!(game_state.current_turn(), PlayerColor::Black);
+    assert_eq!(game_state.board(), board_before);
+}
+
+#[test]
+fn premove_for_the_side_that_just_moved_is_never_played() {
+    let mut game_state = new_game();
+    game_state.set_premove(Some(premove(PlayerColor::White, (1, 3), (3, 3))));
+    play(&mut game_state, (1, 4), (3, 4));
+
+    assert_eq!(apply_premove(&mut game_state), None);
+    assert_eq!(game_state.premove(), None);
+    assert_eq!(game_state.current_turn(), PlayerColor::Black);
+}
@@ -0,0 +1,56 @@
+// tests/rated_match.rs
+// The settings a rated match may not use, and the profiles settlement needs before it rates one.
+use counter::errors::ChessError;
+use counter::state::*;
+
+fn new_match(rated: bool) -> ChessMatch {
+    let mut chess_match = ChessMatch::default();
+    chess_match.set_rated(rated);
+    chess_match.reset_position();
+    chess_match
+}
+
+#[test]
+fn rated_matches_reject_variants_odds_and_takebacks() {
+    new_match(true).check_rated_settings().unwrap();
+
+    let mut chess960 = new_match(true);
+    chess960.set_variant(Variant::Chess960);
+    assert_eq!(chess960.check_rated_settings().unwrap_err(), ChessError::RatedMatchRestriction.into());
+
+    let mut odds = new_match(true);
+    odds.set_handicap(Handicap::TimeOdds { creator_move_timeout: 20 });
+    assert_eq!(odds.check_rated_settings().unwrap_err(), ChessError::RatedMatchRestriction.into());
+
+    let mut takebacks = new_match(true);
+    takebacks.set_takebacks_enabled(true);
+    assert_eq!(takebacks.check_rated_settings().unwrap_err(), ChessError::RatedMatchRestriction.into());
+
+    // Casual matches may use all of them
+    let mut casual = new_match(false);
+    casual.set_variant(Variant::Chess960);
+    casual.set_handicap(Handicap::TimeOdds { creator_move_timeout: 20 });
+    casual.set_takebacks_enabled(true);
+    casual.check_rated_settings().unwrap();
+}
+
+#[test]
+fn rated_settlement_needs_both_profiles() {
+    // The helper only pairs up whatever the accounts deref to, so plain ratings stand in for profiles
+    let (mut white, mut black) = (1500u16, 1500u16);
+    let rated = new_match(true);
+    assert!(rated.profiles_to_rate(Some(&mut white), Some(&mut black)).unwrap().is_some());
+    assert_eq!(
+        rated.profiles_to_rate(Some(&mut white), None).unwrap_err(),
+        ChessError::PlayerProfileRequired.into()
+    );
+    assert_eq!(
+        rated.profiles_to_rate(None::<&mut u16>, None).unwrap_err(),
+        ChessError::PlayerProfileRequired.into()
+    );
+
+    // Casual matches settle without touching profiles, even when they are passed
+    let casual = new_match(false);
+    assert!(casual.profiles_to_rate(Some(&mut white), Some(&mut black)).unwrap().is_none());
+    assert!(casual.profiles_to_rate(None::<&mut u16>, None).unwrap().is_none());
+}
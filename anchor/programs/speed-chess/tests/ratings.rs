@@ -0,0 +1,170 @@
+// tests/ratings.rs
+// Checks the on-chain rating arithmetic against known values and a floating-point Glicko-2.
+use counter::state::GlickoRating;
+use counter::utils::*;
+use proptest::prelude::*;
+
+#[test]
+fn expected_scores_follow_the_elo_table() {
+    assert_eq!(expected_score_milli(1500, 1500), 500);
+    assert_eq!(expected_score_milli(1700, 1500), 760);
+    assert_eq!(expected_score_milli(1500, 1700), 240);
+    assert_eq!(expected_score_milli(1512, 1500), 517); // Interpolated between 0 and 25
+    // Gaps beyond 400 points count as 400
+    assert_eq!(expected_score_milli(2800, 1000), 909);
+    assert_eq!(expected_score_milli(1000, 2800), 91);
+}
+
+#[test]
+fn elo_updates_use_games_played_k_factor() {
+    // New players move 40 * 0.5 = 20 points on an even win
+    assert_eq!(elo_update(1200, 0, 1200, WIN_SCORE_MILLI), 1220);
+    assert_eq!(elo_update(1200, 0, 1200, LOSS_SCORE_MILLI), 1180);
+    assert_eq!(elo_update(1200, 0, 1200, DRAW_SCORE_MILLI), 1200);
+    // Established players move half as far
+    assert_eq!(elo_update(1200, PROVISIONAL_GAMES, 1200, WIN_SCORE_MILLI), 1210);
+    // A draw against a stronger player gains rating: 20 * (0.5 - 0.24) = 5.2
+    assert_eq!(elo_update(1500, PROVISIONAL_GAMES, 1700, DRAW_SCORE_MILLI), 1505);
+    assert_eq!(elo_update(1700, PROVISIONAL_GAMES, 1500, DRAW_SCORE_MILLI), 1695);
+    // Ratings never drop below the floor
+    assert_eq!(elo_update(MIN_RATING, 0, 1000, LOSS_SCORE_MILLI), MIN_RATING);
+}
+
+// Floating-point Glicko-2 straight from the paper, for comparison with the fixed-point version.
+mod reference {
+    const SCALE: f64 = 173.7178;
+    const TAU: f64 = 0.5;
+
+    fn g(phi: f64) -> f64 {
+        1.0 / (1.0 + 3.0 * phi * phi / (std::f64::consts::PI * std::f64::consts::PI)).sqrt()
+    }
+
+    // (rating, deviation, volatility) after one game scoring `score` against the opponent.
+    pub fn update(player: (f64, f64, f64), opponent: (f64, f64), score: f64) -> (f64, f64, f64) {
+        let (mu, phi, sigma) = ((player.0 - 1500.0) / SCALE, player.1 / SCALE, player.2);
+        let (opponent_mu, opponent_g) = ((opponent.0 - 1500.0) / SCALE, g(opponent.1 / SCALE));
+        let expected = 1.0 / (1.0 + (-opponent_g * (mu - opponent_mu)).exp());
+        let v = 1.0 / (opponent_g * opponent_g * expected * (1.0 - expected));
+        let delta = v * opponent_g * (score - expected);
+
+        let a = (sigma * sigma).ln();
+        let f = |x: f64| {
+            let ex = x.exp();
+            ex * (delta * delta - phi * phi - v - ex) / (2.0 * (phi * phi + v + ex).powi(2)) - (x - a) / (TAU * TAU)
+        };
+        let mut big_a = a;
+        let mut big_b = if delta * delta > phi * phi + v {
+            (delta * delta - phi * phi - v).ln()
+        } else {
+            let mut k = 1.0;
+            while f(a - k * TAU) < 0.0 {
+                k += 1.0;
+            }
+            a - k * TAU
+        };
+        let (mut f_a, mut f_b) = (f(big_a), f(big_b));
+        while (big_b - big_a).abs() > 0.000001 {
+            let big_c = big_a + (big_a - big_b) * f_a / (f_b - f_a);
+            let f_c = f(big_c);
+            if f_c * f_b <= 0.0 {
+                big_a = big_b;
+                f_a = f_b;
+            } else {
+                f_a /= 2.0;
+            }
+            big_b = big_c;
+            f_b = f_c;
+        }
+        let new_sigma = (big_a / 2.0).exp();
+        let phi_star = (phi * phi + new_sigma * new_sigma).sqrt();
+        let new_phi = 1.0 / (1.0 / (phi_star * phi_star) + 1.0 / v).sqrt();
+        let new_mu = mu + new_phi * new_phi * opponent_g * (score - expected);
+        (new_mu * SCALE + 1500.0, new_phi * SCALE, new_sigma)
+    }
+}
+
+fn to_float(value: i64) -> f64 {
+    value as f64 / GLICKO_ONE as f64
+}
+
+fn glicko(rating: f64, deviation: f64, volatility: f64) -> GlickoRating {
+    GlickoRating {
+        rating: (rating * GLICKO_ONE as f64) as i64,
+        deviation: (deviation * GLICKO_ONE as f64) as i64,
+        volatility: (volatility * GLICKO_ONE as f64) as i64,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn glicko_matches_the_paper_example() {
+    // Glickman's worked example, played as a single game against the 1400 / 30 opponent
+    let player = glicko(1500.0, 200.0, 0.06);
+    let updated = glicko_update(&player, &glicko(1400.0, 30.0, 0.06), WIN_SCORE_MILLI);
+    let (rating, deviation, volatility) = reference::update((1500.0, 200.0, 0.06), (1400.0, 30.0), 1.0);
+    assert!((to_float(updated.rating) - rating).abs() < 0.01);
+    assert!((to_float(updated.deviation) - deviation).abs() < 0.01);
+    assert!((to_float(updated.volatility) - volatility).abs() < 0.000001);
+}
+
+#[test]
+fn inactivity_grows_deviation_up_to_the_cap() {
+    let deviation = deviation_after_inactivity(50 * GLICKO_ONE, DEFAULT_GLICKO_VOLATILITY, 365 * RATING_PERIOD_SECONDS);
+    let phi = 50.0 / 173.7178;
+    let expected = (phi * phi + 365.0 * 0.06 * 0.06_f64).sqrt() * 173.7178;
+    assert!((to_float(deviation) - expected).abs() < 0.01);
+    assert_eq!(deviation_after_inactivity(50 * GLICKO_ONE, DEFAULT_GLICKO_VOLATILITY, 0), 50 * GLICKO_ONE);
+    assert_eq!(
+        deviation_after_inactivity(300 * GLICKO_ONE, DEFAULT_GLICKO_VOLATILITY, 10_000 * RATING_PERIOD_SECONDS),
+        MAX_GLICKO_DEVIATION
+    );
+}
+
+#[test]
+fn glicko_update_caps_the_new_deviation() {
+    // A win far above expectation grows the deviation past 350 in the float reference
+    let player = glicko(2706.0, 349.9, 0.06);
+    let opponent = glicko(800.0, 30.0, 0.06);
+    let (_, uncapped, _) = reference::update((2706.0, 349.9, 0.06), (800.0, 30.0), 1.0);
+    assert!(uncapped > 350.0);
+    assert_eq!(glicko_update(&player, &opponent, WIN_SCORE_MILLI).deviation, MAX_GLICKO_DEVIATION);
+}
+
+proptest! {
+    #[test]
+    fn glicko_update_tracks_the_float_reference(
+        rating in 800.0..2800.0f64,
+        deviation in 30.0..350.0f64,
+        volatility in 0.03..0.1f64,
+        opponent_rating in 800.0..2800.0f64,
+        opponent_deviation in 30.0..350.0f64,
+        score_milli in prop::sample::select(vec![WIN_SCORE_MILLI, DRAW_SCORE_MILLI, LOSS_SCORE_MILLI]),
+    ) {
+        let player = glicko(rating, deviation, volatility);
+        let opponent = glicko(opponent_rating, opponent_deviation, 0.06);
+        let updated = glicko_update(&player, &opponent, score_milli);
+        let (expected_rating, expected_deviation, expected_volatility) = reference::update(
+            (to_float(player.rating), to_float(player.deviation), to_float(player.volatility)),
+            (to_float(opponent.rating), to_float(opponent.deviation)),
+            f64::from(score_milli) / 1000.0,
+        );
+        let expected_deviation = expected_deviation.min(350.0); // glicko_update caps it at MAX_GLICKO_DEVIATION
+        prop_assert!((to_float(updated.rating) - expected_rating).abs() < 0.05, "rating {} vs {}", to_float(updated.rating), expected_rating);
+        prop_assert!((to_float(updated.deviation) - expected_deviation).abs() < 0.05, "deviation {} vs {}", to_float(updated.deviation), expected_deviation);
+        prop_assert!((to_float(updated.volatility) - expected_volatility).abs() < 0.00001, "volatility {} vs {}", to_float(updated.volatility), expected_volatility);
+    }
+
+    #[test]
+    fn inactivity_tracks_the_float_reference(
+        deviation in 30.0..350.0f64,
+        volatility in 0.03..0.1f64,
+        elapsed_seconds in 0..(3 * 365 * RATING_PERIOD_SECONDS),
+    ) {
+        let grown = deviation_after_inactivity(glicko(0.0, deviation, 0.0).deviation, glicko(0.0, 0.0, volatility).volatility, elapsed_seconds);
+        let phi = deviation / 173.7178;
+        let periods = elapsed_seconds as f64 / RATING_PERIOD_SECONDS as f64;
+        let expected = ((phi * phi + periods * volatility * volatility).sqrt() * 173.7178).min(350.0);
+        prop_assert!((to_float(grown) - expected).abs() < 0.01);
+        prop_assert!(grown >= glicko(0.0, deviation, 0.0).deviation);
+    }
+}
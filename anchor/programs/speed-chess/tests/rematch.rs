@@ -0,0 +1,93 @@
+// tests/rematch.rs
+// Who may offer a rematch, and the color-swapped match accept_rematch sets up from the original.
+use anchor_lang::prelude::*;
+use counter::errors::ChessError;
+use counter::state::*;
+
+fn concluded_match(white: Pubkey, black: Pubkey) -> ChessMatch {
+    let mut original = ChessMatch::default();
+    original.set_match_id("original");
+    original.players = [white, black];
+    original.set_variant(Variant::Chess960);
+    original.start_position_index = 0;
+    original.set_handicap(Handicap::TimeOdds { creator_move_timeout: 20 });
+    original.set_handicap_color(PlayerColor::White);
+    original.reset_position();
+    original.move_timeout_duration = 30;
+    original.set_takebacks_enabled(true);
+    original.betting_token_mint = Pubkey::new_unique();
+    original.bet_amount_player_one = 10_000_000;
+    original.bet_amount_player_two = 10_000_000;
+    original.total_pot = 20_000_000;
+    original.platform_fee_basis_points = 200;
+    original.set_payout_processed(true);
+    original.set_game_status(GameStatus::WhiteWins);
+    original.set_game_end_reason(Some(GameEndReason::Checkmate));
+    original
+}
+
+#[test]
+fn only_one_player_can_have_an_offer_pending() {
+    let (white, black) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut original = concluded_match(white, black);
+
+    assert_eq!(original.check_rematch_offer(Pubkey::new_unique()).unwrap_err(), ChessError::NotAPlayer.into());
+    original.check_rematch_offer(white).unwrap();
+    original.set_rematch_requested_by(Some(white));
+
+    // White may renew the offer; Black can only accept it
+    original.check_rematch_offer(white).unwrap();
+    assert_eq!(original.check_rematch_offer(black).unwrap_err(), ChessError::RematchAlreadyRequested.into());
+
+    // Once the offer is consumed either player may make the next one
+    original.set_rematch_requested_by(None);
+    original.check_rematch_offer(black).unwrap();
+
+    let unjoined = concluded_match(white, Pubkey::default());
+    assert_eq!(unjoined.check_rematch_offer(white).unwrap_err(), ChessError::OpponentNotJoinedYet.into());
+}
+
+#[test]
+fn accepted_rematch_swaps_colors_and_keeps_the_settings() {
+    let (white, black) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut original = concluded_match(white, black);
+    original.set_rematch_requested_by(Some(black));
+
+    let mut rematch = ChessMatch::default();
+    rematch.start_rematch_of(&original, 1_700_000_000).unwrap();
+
+    assert_eq!(rematch.match_id(), original.rematch_match_id());
+    assert_eq!(rematch.players, [black, white]);
+    assert_eq!(rematch.variant(), Variant::Chess960);
+    assert_eq!(rematch.start_position_index, 0);
+    assert_eq!(rematch.board(), original_start_board(&original));
+    // The creator, now Black, still gives the time odds
+    assert_eq!(rematch.handicap(), Handicap::TimeOdds { creator_move_timeout: 20 });
+    assert_eq!(rematch.handicap_color(), PlayerColor::Black);
+    assert_eq!(rematch.move_timeout_for(PlayerColor::Black), 20);
+    assert_eq!(rematch.move_timeout_for(PlayerColor::White), 30);
+
+    assert_eq!(rematch.game_status(), GameStatus::Active);
+    assert_eq!(rematch.game_end_reason(), None);
+    assert_eq!(rematch.current_turn(), PlayerColor::White);
+    assert_eq!(rematch.last_move_timestamp, 1_700_000_000);
+    assert!(rematch.takebacks_enabled());
+    assert_eq!(rematch.betting_token_mint, original.betting_token_mint);
+    assert_eq!((rematch.bet_amount_player_one, rematch.bet_amount_player_two, rematch.total_pot), (10_000_000, 10_000_000, 20_000_000));
+    assert_eq!(rematch.platform_fee_basis_points, 200);
+    assert!(!rematch.payout_processed());
+    assert_eq!(rematch.rematch_requested_by(), None);
+
+    // The rematch has its own id, so it can be rematched in turn
+    assert_ne!(rematch.rematch_match_id(), rematch.match_id());
+    assert_eq!(rematch.rematch_match_id().len(), MAX_MATCH_ID_LEN);
+}
+
+// Start position of `original`'s variant and index, without any odds applied.
+fn original_start_board(original: &ChessMatch) -> [[Option<Piece>; 8]; 8] {
+    let mut position = ChessMatch::default();
+    position.set_variant(original.variant());
+    position.start_position_index = original.start_position_index;
+    position.reset_position();
+    position.board()
+}
@@ -0,0 +1,40 @@
+// tests/session.rs
+// Which wallet a gameplay instruction acts for when signed by a wallet or a session key.
+use anchor_lang::prelude::*;
+use counter::errors::ChessError;
+use counter::state::SessionToken;
+
+fn session(chess_match: Pubkey, player: Pubkey, session_signer: Pubkey) -> SessionToken {
+    SessionToken { chess_match, player, session_signer, expires_at: 1_000, bump: 255 }
+}
+
+#[test]
+fn session_keys_act_for_their_player_until_expiry() {
+    let (chess_match, player, session_signer) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let token = session(chess_match, player, session_signer);
+
+    // Without a token the signer acts for itself
+    assert_eq!(SessionToken::acting_player(None, &player, &chess_match, 0).unwrap(), player);
+    assert_eq!(SessionToken::acting_player(Some(&token), &session_signer, &chess_match, 999).unwrap(), player);
+    assert_eq!(
+        SessionToken::acting_player(Some(&token), &session_signer, &chess_match, 1_000).unwrap_err(),
+        ChessError::SessionExpired.into()
+    );
+}
+
+#[test]
+fn session_tokens_are_bound_to_their_key_and_match() {
+    let (chess_match, player, session_signer) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let token = session(chess_match, player, session_signer);
+
+    let other_signer = Pubkey::new_unique();
+    assert_eq!(
+        SessionToken::acting_player(Some(&token), &other_signer, &chess_match, 0).unwrap_err(),
+        ChessError::InvalidSessionToken.into()
+    );
+    let other_match = Pubkey::new_unique();
+    assert_eq!(
+        SessionToken::acting_player(Some(&token), &session_signer, &other_match, 0).unwrap_err(),
+        ChessError::InvalidSessionToken.into()
+    );
+}
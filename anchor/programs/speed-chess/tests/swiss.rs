@@ -0,0 +1,78 @@
+// tests/swiss.rs
+// Swiss pairings, tiebreaks and tied prize splits.
+use anchor_lang::prelude::*;
+use counter::state::{ArenaPlayer, PlayerColor, SwissGame, TournamentPlayer, BYE};
+use counter::utils::*;
+
+fn players(count: usize) -> Vec<TournamentPlayer> {
+    (0..count)
+        .map(|_| TournamentPlayer {
+            player: Pubkey::new_unique(),
+            rating: None,
+            eliminated_in_round: 0,
+            score: 0,
+            history: Vec::new(),
+            arena: ArenaPlayer::default(),
+        })
+        .collect()
+}
+
+// Records a round of (White, Black, White's result in half points) games plus byes.
+fn play_round(players: &mut [TournamentPlayer], games: &[(u8, u8, u8)], byes: &[u8]) {
+    for &(white, black, result) in games {
+        record_swiss_game(&mut players[white as usize], SwissGame { opponent: black, color: Some(PlayerColor::White), result });
+        record_swiss_game(&mut players[black as usize], SwissGame { opponent: white, color: Some(PlayerColor::Black), result: 2 - result });
+    }
+    for &player in byes {
+        record_swiss_game(&mut players[player as usize], SwissGame { opponent: BYE, color: None, result: 2 });
+    }
+}
+
+#[test]
+fn first_round_pairs_top_half_against_bottom_half() {
+    // Colors alternate by board
+    assert_eq!(swiss_pairings(&players(6)), vec![(0, 3), (4, 1), (2, 5)]);
+    // The lowest seed sits out an odd round
+    assert_eq!(swiss_pairings(&players(5)), vec![(0, 2), (3, 1), (4, BYE)]);
+}
+
+#[test]
+fn later_rounds_avoid_repeats_balance_colors_and_rotate_the_bye() {
+    let mut players = players(5);
+    play_round(&mut players, &[(0, 2, 2), (3, 1, 1)], &[4]);
+
+    // Leaders 0 and 4 meet unless that forces the 1-3 rematch, so 0 drops to 1 instead.
+    // 1 had Black and 4 had the bye, so both get White; player 2 takes the next bye.
+    assert_eq!(swiss_pairings(&players), vec![(1, 0), (4, 3), (2, BYE)]);
+}
+
+#[test]
+fn standings_break_ties_and_tied_players_split_prizes() {
+    let mut players = players(5);
+    play_round(&mut players, &[(0, 2, 2), (3, 1, 1)], &[4]);
+    play_round(&mut players, &[(1, 0, 2), (4, 3, 1)], &[2]);
+
+    let standings = swiss_standings(&players);
+    let order: Vec<Pubkey> = standings.iter().map(|standing| standing.player).collect();
+    assert_eq!(order, [1, 4, 3, 0, 2].map(|index| players[index].player));
+    let tiebreaks: Vec<(u16, u16, u32)> = standings
+        .iter()
+        .map(|standing| (standing.score, standing.buchholz, standing.sonneborn_berger))
+        .collect();
+    assert_eq!(tiebreaks, vec![(3, 4, 6), (3, 2, 2), (2, 6, 6), (2, 5, 4), (2, 2, 0)]);
+
+    // 1 and 4 share 1st and 2nd, the three players on 2 points share 3rd (and the unpaid 4th and 5th)
+    assert!(swiss_payout_table_is_valid(&[5000, 3000, 2000]));
+    assert!(!swiss_payout_table_is_valid(&[5000, 3000]));
+    let prizes = swiss_prize_amounts(&players, &[5000, 3000, 2000], 1_000);
+    assert_eq!(
+        prizes,
+        vec![
+            (players[1].player, 400),
+            (players[4].player, 400),
+            (players[3].player, 68),
+            (players[0].player, 66),
+            (players[2].player, 66),
+        ]
+    );
+}
@@ -0,0 +1,67 @@
+// tests/takeback.rs
+// How many plies a takeback rewinds, and that the move history loses exactly those plies.
+use counter::state::*;
+use counter::utils::chess_logic::{take_back, validate_and_apply_move};
+
+fn play(game_state: &mut ChessMatch, move_history: &mut MoveHistory, from: (u8, u8), to: (u8, u8)) {
+    let color = game_state.current_turn();
+    assert_eq!(validate_and_apply_move(game_state, from.0, from.1, to.0, to.1, color, None).unwrap(), MoveResult::Normal);
+    move_history.push(&RecordedMove { from_row: from.0, from_col: from.1, to_row: to.0, to_col: to.1, promotion: None, clock_seconds: 1 });
+}
+
+fn new_game() -> (ChessMatch, MoveHistory) {
+    let mut game_state = ChessMatch::default();
+    game_state.reset_position();
+    (game_state, MoveHistory { match_id: "takeback".to_string(), bump: 0, moves: Vec::new() })
+}
+
+#[test]
+fn requester_not_on_move_takes_back_one_ply() {
+    let (mut game_state, mut history) = new_game();
+    play(&mut game_state, &mut history, (1, 4), (3, 4)); // 1. e4
+    play(&mut game_state, &mut history, (6, 4), (4, 4)); // 1... e5
+    let after_e5 = game_state.board();
+    play(&mut game_state, &mut history, (0, 6), (2, 5)); // 2. Nf3
+
+    // White just moved, so only Nf3 goes
+    assert_eq!(game_state.takeback_plies_for(PlayerColor::White), 1);
+    assert!(game_state.can_take_back(PlayerColor::White));
+    assert_eq!(take_back(&mut game_state, &mut history, PlayerColor::White).unwrap(), 1);
+    assert_eq!(game_state.board(), after_e5);
+    assert_eq!(game_state.current_turn(), PlayerColor::White);
+    assert_eq!(history.moves.len(), 2);
+    assert_eq!(history.decode_moves().last().unwrap().to_row, 4); // ...e5 is now the last ply
+}
+
+#[test]
+fn requester_on_move_takes_back_their_move_and_the_reply() {
+    let (mut game_state, mut history) = new_game();
+    play(&mut game_state, &mut history, (1, 4), (3, 4)); // 1. e4
+    let after_e4 = game_state.board();
+    play(&mut game_state, &mut history, (6, 4), (4, 4)); // 1... e5
+    play(&mut game_state, &mut history, (0, 6), (2, 5)); // 2. Nf3
+
+    // Black is on move: Nf3 and Black's own e5 are both undone
+    assert_eq!(game_state.takeback_plies_for(PlayerColor::Black), 2);
+    assert_eq!(take_back(&mut game_state, &mut history, PlayerColor::Black).unwrap(), 2);
+    assert_eq!(game_state.board(), after_e4);
+    assert_eq!(game_state.current_turn(), PlayerColor::Black);
+    assert_eq!(history.moves.len(), 1);
+}
+
+#[test]
+fn takeback_needs_the_plies_in_the_undo_window() {
+    let (mut game_state, mut history) = new_game();
+    // White is on move with nothing played: rewinding 2 plies would go past the start
+    assert!(!game_state.can_take_back(PlayerColor::White));
+    play(&mut game_state, &mut history, (1, 4), (3, 4)); // 1. e4
+    assert!(game_state.can_take_back(PlayerColor::White));
+    assert!(!game_state.can_take_back(PlayerColor::Black));
+
+    // The window only holds MAX_UNDO_PLIES, so one takeback empties it
+    play(&mut game_state, &mut history, (6, 4), (4, 4)); // 1... e5
+    play(&mut game_state, &mut history, (0, 6), (2, 5)); // 2. Nf3
+    take_back(&mut game_state, &mut history, PlayerColor::Black).unwrap();
+    assert!(!game_state.can_take_back(PlayerColor::White));
+    assert!(take_back(&mut game_state, &mut history, PlayerColor::White).is_err());
+}
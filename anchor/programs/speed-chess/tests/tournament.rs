@@ -0,0 +1,117 @@
+// tests/tournament.rs
+// Bracket seeding, byes, Armageddon replays of drawn games, prize splits and closing recorded games in tournaments.
+use anchor_lang::prelude::*;
+use counter::state::{
+    ArenaPlayer, ChessMatch, GameStatus, Handicap, PlayerColor, Tournament, TournamentFormat, TournamentPlayer, TournamentStatus,
+};
+use counter::utils::*;
+
+fn player(rating: Option<u16>, eliminated_in_round: u8) -> TournamentPlayer {
+    TournamentPlayer {
+        player: Pubkey::new_unique(),
+        rating,
+        eliminated_in_round,
+        score: 0,
+        history: Vec::new(),
+        arena: ArenaPlayer::default(),
+    }
+}
+
+#[test]
+fn top_seeds_are_spread_across_the_bracket_and_get_the_byes() {
+    assert_eq!(seeding_order(8), vec![1, 8, 4, 5, 2, 7, 3, 6]);
+
+    let mut players = vec![player(None, 0), player(Some(1800), 0), player(Some(1500), 0), player(Some(1100), 0), player(None, 0)];
+    let unrated_first = players[0].player;
+    sort_by_seed(&mut players);
+    let ratings: Vec<Option<u16>> = players.iter().map(|entry| entry.rating).collect();
+    assert_eq!(ratings, vec![Some(1800), Some(1500), None, None, Some(1100)]);
+    assert_eq!(players[2].player, unrated_first); // Ties keep registration order
+
+    // Five players in a bracket of eight: seeds 1-3 get byes, 4 plays 5
+    let seeded: Vec<Pubkey> = players.iter().map(|entry| entry.player).collect();
+    let bracket = first_round_bracket(&seeded);
+    let bye = Pubkey::default();
+    assert_eq!(bracket, vec![seeded[0], bye, seeded[3], seeded[4], seeded[1], bye, seeded[2], bye]);
+    assert_eq!(winners_by_bye(&bracket), vec![seeded[0], bye, seeded[1], seeded[2]]);
+}
+
+#[test]
+fn prizes_follow_the_payout_table() {
+    assert!(payout_table_is_valid(&[5000, 3000, 1000]));
+    assert!(payout_table_is_valid(&[10000]));
+    assert!(!payout_table_is_valid(&[5000, 3000, 2000])); // Two semifinalists would be paid 2000 each
+    assert!(!payout_table_is_valid(&[]));
+
+    // Three rounds: the finalist lost in round 3, semifinalists in round 2, the rest earn nothing
+    let champion = player(None, 0);
+    let finalist = player(None, 3);
+    let semifinalist = player(None, 2);
+    let quarterfinalist = player(None, 1);
+    let players = [quarterfinalist, semifinalist.clone(), finalist.clone(), champion.clone()];
+    let prizes = prize_amounts(&players, 3, &[5000, 3000, 1000], 1_000);
+    // Only one semifinalist was paid, so the champion also takes the other semifinal share
+    assert_eq!(prizes, vec![(champion.player, 600), (semifinalist.player, 100), (finalist.player, 300)]);
+}
+
+#[test]
+fn drawn_knockout_pairing_is_replayed_as_armageddon() {
+    let mut tournament = Tournament {
+        tournament_id: "knockout".to_string(),
+        organizer: Pubkey::new_unique(),
+        bump: 255,
+        status: TournamentStatus::InProgress,
+        format: TournamentFormat::Knockout,
+        betting_token_mint: Pubkey::new_unique(),
+        entry_fee: 0,
+        prize_pool: 0,
+        platform_fee_basis_points: 0,
+        payout_table: vec![10000],
+        move_timeout_duration: 60,
+        players: Vec::new(),
+        current_round: 2,
+        total_rounds: 3,
+        bracket: vec![Pubkey::new_unique(); 4],
+        round_winners: vec![Pubkey::default(); 2],
+        results_pending: 0,
+        armageddon_pairings: 0,
+        games_created: 0,
+    };
+    let first_game = tournament.round_match_id(2, 1);
+    assert_eq!(tournament.pairing_match_id(1), first_game);
+    assert_eq!(tournament.pairing_handicap(1), Handicap::None);
+
+    // After the draw the pairing plays a fresh game, and only that pairing
+    tournament.set_armageddon(1);
+    assert!(tournament.is_armageddon(1) && !tournament.is_armageddon(0));
+    let replay = tournament.pairing_match_id(1);
+    assert_eq!(replay, tournament.armageddon_match_id(2, 1));
+    assert_ne!(replay, first_game);
+    assert_eq!(tournament.pairing_match_id(0), tournament.round_match_id(2, 0));
+
+    // Black, who wins a drawn Armageddon, gets 80% of the move timeout
+    let mut game = ChessMatch::default();
+    game.move_timeout_duration = tournament.move_timeout_duration;
+    game.set_handicap(tournament.pairing_handicap(1));
+    game.set_handicap_color(PlayerColor::Black);
+    assert_eq!(game.move_timeout_for(PlayerColor::White), 60);
+    assert_eq!(game.move_timeout_for(PlayerColor::Black), 48);
+}
+
+#[test]
+fn recorded_result_flag_is_independent_of_the_other_flags() {
+    let mut game = ChessMatch::default();
+    game.set_payout_processed(true);
+    game.set_rated(true);
+    assert!(!game.result_recorded() && !game.is_concluded());
+
+    game.set_game_status(GameStatus::Draw);
+    game.set_result_recorded(true);
+    assert!(game.result_recorded() && game.is_concluded());
+    assert!(game.payout_processed() && game.rated());
+    game.set_result_recorded(false);
+    assert!(!game.result_recorded() && game.payout_processed());
+
+    game.set_game_status(GameStatus::Active);
+    assert!(!game.is_concluded());
+}
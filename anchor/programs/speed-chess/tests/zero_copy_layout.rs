@@ -0,0 +1,162 @@
+// tests/zero_copy_layout.rs
+// The zero-copy ChessMatch: account size and rent, and lossless packing of the board and packed fields.
+use anchor_lang::prelude::*;
+use counter::state::*;
+use counter::utils::chess_logic::{undo_last_move, validate_and_apply_move};
+
+// ChessMatch as it was stored with Borsh before the switch to zero-copy, field for field
+#[allow(dead_code)]
+#[derive(InitSpace)]
+struct BorshChessMatch {
+    #[max_len(MAX_MATCH_ID_LEN)]
+    match_id: String,
+    players: [Pubkey; MAX_PLAYERS],
+    current_player_idx: u8,
+    current_turn: PlayerColor,
+    last_move_timestamp: i64,
+    move_timeout_duration: i64,
+    handicap: Handicap,
+    handicap_color: PlayerColor,
+    join_deadline: Option<i64>,
+    lobby_page: u32,
+    game_status: GameStatus,
+    game_end_reason: Option<GameEndReason>,
+    variant: Variant,
+    start_position_index: u16,
+    board: [[Option<Piece>; 8]; 8],
+    castling_rights: CastlingRights,
+    en_passant_target: Option<EnPassantSquare>,
+    halfmove_clock: u8,
+    fullmove_number: u16,
+    checks_given: [u8; 2],
+    pockets: [BorshPocket; 2],
+    promoted_squares: u64,
+    rated: bool,
+    tournament: Option<Pubkey>,
+    berserk: [bool; 2],
+    delegated: bool,
+    takebacks_enabled: bool,
+    takeback_requested_by: Option<PlayerColor>,
+    premove: Option<Premove>,
+    undo_history: [Option<UndoRecord>; MAX_UNDO_PLIES],
+    betting_token_mint: Pubkey,
+    bet_amount_player_one: u64,
+    bet_amount_player_two: u64,
+    total_pot: u64,
+    platform_fee_basis_points: u16,
+    payout_processed: bool,
+    rematch_requested_by: Option<Pubkey>,
+    bump: u8,
+}
+
+#[allow(dead_code)]
+#[derive(InitSpace)]
+struct BorshPocket {
+    pawns: u8,
+    knights: u8,
+    bishops: u8,
+    rooks: u8,
+    queens: u8,
+}
+
+const BORSH_ACCOUNT_SIZE: usize = 8 + BorshChessMatch::INIT_SPACE;
+
+fn play(game_state: &mut ChessMatch, from: (u8, u8), to: (u8, u8)) -> MoveResult {
+    let color = game_state.current_turn();
+    validate_and_apply_move(game_state, from.0, from.1, to.0, to.1, color, None).unwrap()
+}
+
+#[test]
+fn packed_match_account_is_smaller_and_cheaper() {
+    let account_size = 8 + ChessMatch::INIT_SPACE;
+    assert_eq!(account_size, 440);
+    assert_eq!(BORSH_ACCOUNT_SIZE, 620);
+
+    let rent = Rent::default();
+    assert_eq!(rent.minimum_balance(BORSH_ACCOUNT_SIZE), 5_206_080);
+    assert_eq!(rent.minimum_balance(account_size), 3_953_280);
+    // 1_252_800 lamports less locked up per match
+    assert_eq!(rent.minimum_balance(BORSH_ACCOUNT_SIZE) - rent.minimum_balance(account_size), 1_252_800);
+}
+
+#[test]
+fn board_and_packed_fields_round_trip() {
+    let mut game_state = ChessMatch::default();
+    game_state.set_variant(Variant::Chess960);
+    game_state.start_position_index = 0; // BBQNNRKR
+    game_state.reset_position();
+    let board = game_state.board();
+    assert_eq!(board[0][0], Some(Piece { piece_type: PieceType::Bishop, color: PlayerColor::White }));
+    assert_eq!(board[7][7], Some(Piece { piece_type: PieceType::Rook, color: PlayerColor::Black }));
+    assert_eq!(board[4], [None; 8]);
+
+    let mut copy = ChessMatch::default();
+    copy.set_board(&board);
+    assert_eq!(copy.board(), board);
+    copy.set_piece_at(3, 5, Some(Piece { piece_type: PieceType::Queen, color: PlayerColor::Black }));
+    copy.set_piece_at(3, 4, None);
+    assert_eq!(copy.piece_at(3, 5), Some(Piece { piece_type: PieceType::Queen, color: PlayerColor::Black }));
+    assert_eq!(copy.piece_at(3, 4), None);
+
+    let rights = CastlingRights { white_kingside: Some(7), white_queenside: Some(0), black_kingside: None, black_queenside: Some(2) };
+    game_state.set_castling_rights(rights);
+    assert_eq!(game_state.castling_rights(), rights);
+    game_state.set_en_passant_target(Some(EnPassantSquare { row: 5, col: 7 }));
+    assert_eq!(game_state.en_passant_target(), Some(EnPassantSquare { row: 5, col: 7 }));
+    game_state.set_en_passant_target(None);
+    assert_eq!(game_state.en_passant_target(), None);
+
+    let drop = Premove { color: PlayerColor::Black, from_row: 0, from_col: 0, to_row: 2, to_col: 5, promotion: None, drop_piece: Some(PieceType::Knight) };
+    game_state.set_premove(Some(drop));
+    assert_eq!(game_state.premove(), Some(drop));
+    let promotion = Premove { color: PlayerColor::White, from_row: 6, from_col: 7, to_row: 7, to_col: 7, promotion: Some(PieceType::Rook), drop_piece: None };
+    game_state.set_premove(Some(promotion));
+    assert_eq!(game_state.premove(), Some(promotion));
+
+    game_state.set_handicap(Handicap::TimeOdds { creator_move_timeout: 45 });
+    game_state.set_handicap_color(PlayerColor::Black);
+    assert_eq!(game_state.handicap(), Handicap::TimeOdds { creator_move_timeout: 45 });
+    assert_eq!(game_state.handicap_color(), PlayerColor::Black);
+    game_state.set_join_deadline(Some(0));
+    assert_eq!(game_state.join_deadline(), Some(0));
+    game_state.set_game_end_reason(Some(GameEndReason::Checkmate));
+    assert_eq!(game_state.game_end_reason(), Some(GameEndReason::Checkmate));
+
+    let match_id = "m".repeat(MAX_MATCH_ID_LEN);
+    game_state.set_match_id(&match_id);
+    assert_eq!(game_state.match_id(), match_id);
+    game_state.set_match_id("short");
+    assert_eq!(game_state.match_id_bytes(), b"short");
+}
+
+#[test]
+fn takebacks_rewind_through_packed_undo_records() {
+    let mut game_state = ChessMatch::default();
+    game_state.reset_position();
+    let start = game_state.board();
+    play(&mut game_state, (1, 4), (3, 4)); // 1. e4
+    play(&mut game_state, (6, 3), (4, 3)); // 1... d5
+    assert_eq!(game_state.en_passant_target(), Some(EnPassantSquare { row: 5, col: 3 }));
+    undo_last_move(&mut game_state).unwrap();
+    assert_eq!(game_state.en_passant_target(), Some(EnPassantSquare { row: 2, col: 4 }));
+    undo_last_move(&mut game_state).unwrap();
+    assert_eq!(game_state.board(), start);
+    assert_eq!(game_state.current_turn(), PlayerColor::White);
+    assert!(undo_last_move(&mut game_state).is_err());
+
+    // Atomic: the pieces blown off around the capture come back with the takeback
+    let mut atomic = ChessMatch::default();
+    atomic.set_variant(Variant::Atomic);
+    atomic.reset_position();
+    play(&mut atomic, (0, 6), (2, 5)); // 1. Nf3
+    play(&mut atomic, (6, 4), (5, 4)); // 1... e6
+    play(&mut atomic, (2, 5), (4, 6)); // 2. Ng5
+    play(&mut atomic, (6, 0), (5, 0)); // 2... a6
+    let before_capture = atomic.board();
+    let rights_before_capture = atomic.castling_rights();
+    assert!(matches!(play(&mut atomic, (4, 6), (6, 5)), MoveResult::VariantWin { winner: PlayerColor::White, .. })); // 3. Nxf7
+    assert_eq!(atomic.piece_at(7, 4), None); // Black king exploded
+    undo_last_move(&mut atomic).unwrap();
+    assert_eq!(atomic.board(), before_capture);
+    assert_eq!(atomic.castling_rights(), rights_before_capture);
+}
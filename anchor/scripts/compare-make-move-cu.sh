@@ -0,0 +1,32 @@
+#!/usr/bin/env bash
+# Measures make_move compute units on both sides of the zero-copy ChessMatch change (d6ca79f).
+# Each revision is built in its own worktree and runs the probe from badfcbd, which targets the
+# instruction interface those two revisions share. Needs the Solana and Anchor CLIs and node_modules.
+#
+#   npm run anchor-cu              # or: bash anchor/scripts/compare-make-move-cu.sh
+set -euo pipefail
+
+repo=$(git rev-parse --show-toplevel)
+probe_rev=badfcbd
+revisions=(d6ca79f~1 d6ca79f)
+labels=("Borsh ChessMatch" "zero-copy ChessMatch")
+
+results=()
+for i in "${!revisions[@]}"; do
+  rev=${revisions[$i]}
+  worktree=$(mktemp -d)
+  git -C "$repo" worktree add --quiet --detach "$worktree" "$rev"
+  trap 'git -C "$repo" worktree remove --force "$worktree"' EXIT
+
+  git -C "$repo" show "$probe_rev:anchor/tests/compute_units.test.ts" > "$worktree/anchor/tests/compute_units.test.ts"
+  ln -s "$repo/node_modules" "$worktree/node_modules"
+
+  echo "== ${labels[$i]} ($rev)" >&2
+  line=$(cd "$worktree/anchor" && anchor test -- -t "compute units" 2>&1 | tee /dev/stderr | grep "make_move compute units per ply")
+  results+=("${labels[$i]} ($(git -C "$repo" rev-parse --short "$rev")): ${line#*: }")
+
+  git -C "$repo" worktree remove --force "$worktree"
+  trap - EXIT
+done
+
+printf '%s\n' "${results[@]}"
@@ -0,0 +1,22 @@
+#!/usr/bin/env bash
+# Vendors MagicBlock's delegation program for `anchor test` (see [[test.genesis]] in Anchor.toml).
+#
+#   bash scripts/fetch-dlp.sh          # dump the deployed program and pin its sha256
+#   bash scripts/fetch-dlp.sh --check  # verify the vendored binary against the pin (CI)
+#
+# Commit tests/fixtures/dlp.so and dlp.so.sha256 together; updating the pin is a deliberate change.
+set -euo pipefail
+
+cd "$(dirname "$0")/.."
+program_id=DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh
+binary=tests/fixtures/dlp.so
+pin=tests/fixtures/dlp.so.sha256
+
+if [[ "${1:-}" == "--check" ]]; then
+  sha256sum --check --strict "$pin"
+  exit
+fi
+
+solana program dump --url "${DLP_SOURCE_URL:-https://api.mainnet-beta.solana.com}" "$program_id" "$binary"
+sha256sum "$binary" > "$pin"
+cat "$pin"
@@ -0,0 +1,116 @@
+// compute_units.test.ts
+// Compute units make_move consumes on the local test validator, read back from each transaction's
+// metadata. scripts/compare-make-move-cu.sh (npm run anchor-cu) runs it on both sides of the zero-copy
+// ChessMatch change to compare the two layouts.
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { SpeedChess } from "../target/types/speed_chess";
+import { TOKEN_PROGRAM_ID, createMint, createAccount, mintTo, getMint } from "@solana/spl-token";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram, SYSVAR_SLOT_HASHES_PUBKEY } from "@solana/web3.js";
+import { assert } from "chai";
+import { getKeypairFromFile } from "@solana-developers/helpers";
+
+// 1. e4 e5 2. Nf3 Nc6 3. Bb5 a6: quiet moves, a capture-free opening and both sides moving
+const MOVES = [
+  [1, 4, 3, 4], [6, 4, 4, 4],
+  [0, 6, 2, 5], [7, 1, 5, 2],
+  [0, 5, 4, 1], [6, 0, 5, 0],
+];
+
+describe("make_move compute units", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SpeedChess as Program<SpeedChess>;
+
+  const whitePlayer = Keypair.generate();
+  const blackPlayer = Keypair.generate();
+  const matchId = `cu-${Date.now()}`;
+  const betAmount = new BN(10_000_000);
+
+  const pda = (...seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+  const chessMatchPda = pda(Buffer.from("chess_match"), Buffer.from(matchId));
+  const moveHistoryPda = pda(Buffer.from("move_history"), Buffer.from(matchId));
+  const escrowPda = pda(Buffer.from("match_escrow"), Buffer.from(matchId));
+  const lobbyPagePda = pda(Buffer.from("lobby"), Buffer.alloc(4));
+
+  beforeAll(async () => {
+    const sendMintKp = await getKeypairFromFile("tests/test-keys/SENDYLjLBaTgjyfXtPP2aHUt91WhNzX7iUfpThyApht.json");
+    for (const player of [whitePlayer, blackPlayer]) {
+      const sig = await provider.connection.requestAirdrop(player.publicKey, 2 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig, "confirmed");
+    }
+    try { await getMint(provider.connection, sendMintKp.publicKey); }
+    catch (e) { await createMint(provider.connection, whitePlayer, whitePlayer.publicKey, null, 6, sendMintKp); }
+
+    const accounts: PublicKey[] = [];
+    for (const player of [whitePlayer, blackPlayer]) {
+      const ata = await createAccount(provider.connection, player, sendMintKp.publicKey, player.publicKey, Keypair.generate());
+      await mintTo(provider.connection, whitePlayer, sendMintKp.publicKey, ata, whitePlayer, betAmount.toNumber());
+      accounts.push(ata);
+    }
+
+    if (!(await provider.connection.getAccountInfo(lobbyPagePda))) {
+      await program.methods.createLobbyPage(0).accountsPartial({ lobbyPage: lobbyPagePda, previousPage: null, payer: whitePlayer.publicKey })
+        .signers([whitePlayer]).rpc({ commitment: "confirmed" });
+    }
+    await program.methods
+      .initializeMatch({
+        matchId,
+        betAmount,
+        moveTimeoutDuration: new BN(60),
+        platformFeeBasisPoints: 200,
+        joinDeadline: null,
+        takebacksEnabled: false,
+        variant: { standard: {} },
+        handicap: { none: {} },
+        rated: false,
+        startPositionCommitment: null,
+      })
+      .accountsPartial({
+        chessMatch: chessMatchPda,
+        moveHistory: moveHistoryPda,
+        playerSigner: whitePlayer.publicKey,
+        bettingTokenMintAccount: sendMintKp.publicKey,
+        playerTokenAccount: accounts[0],
+        matchEscrowTokenAccount: escrowPda,
+        lobbyPage: lobbyPagePda,
+        creatorProfile: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([whitePlayer])
+      .rpc({ commitment: "confirmed" });
+    await program.methods
+      .joinMatch(betAmount)
+      .accountsPartial({
+        chessMatch: chessMatchPda,
+        playerTwoSigner: blackPlayer.publicKey,
+        playerTokenAccount: accounts[1],
+        matchEscrowTokenAccount: escrowPda,
+        lobbyPage: lobbyPagePda,
+        recentSlotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([blackPlayer])
+      .rpc({ commitment: "confirmed" });
+  }, 100000);
+
+  it("reports the compute units of each move", async () => {
+    const units: number[] = [];
+    for (const [ply, [fromRow, fromCol, toRow, toCol]] of MOVES.entries()) {
+      const player = ply % 2 === 0 ? whitePlayer : blackPlayer;
+      const signature = await program.methods
+        .makeMove({ fromRow, fromCol, toRow, toCol, promotion: null, dropPiece: null })
+        .accountsPartial({ chessMatch: chessMatchPda, moveHistory: moveHistoryPda, player: player.publicKey, sessionToken: null, systemProgram: SystemProgram.programId })
+        .signers([player])
+        .rpc({ commitment: "confirmed" });
+      const transaction = await provider.connection.getTransaction(signature, { commitment: "confirmed", maxSupportedTransactionVersion: 0 });
+      const consumed = transaction?.meta?.computeUnitsConsumed;
+      assert.isNumber(consumed, "the validator should report compute units");
+      units.push(consumed!);
+    }
+    const average = Math.round(units.reduce((sum, value) => sum + value, 0) / units.length);
+    console.log(`make_move compute units per ply: ${units.join(", ")} (average ${average})`);
+  }, 100000);
+});
@@ -0,0 +1,168 @@
+// ephemeral_rollup.test.ts
+// Delegation round trip: the base layer is the local test validator (with MagicBlock's delegation
+// program loaded from tests/fixtures/dlp.so via Anchor.toml), the rollup is a local ephemeral
+// validator started alongside it, e.g. `ephemeral-validator` pointed at the local test validator.
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { SpeedChess } from "../target/types/speed_chess";
+import { TOKEN_PROGRAM_ID, createMint, createAccount, mintTo, getMint } from "@solana/spl-token";
+import { Connection, Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram, SYSVAR_SLOT_HASHES_PUBKEY } from "@solana/web3.js";
+import { assert } from "chai";
+import { getKeypairFromFile } from "@solana-developers/helpers";
+
+const DELEGATION_PROGRAM_ID = new PublicKey("DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh");
+const MAGIC_PROGRAM_ID = new PublicKey("Magic11111111111111111111111111111111111111");
+const MAGIC_CONTEXT_ID = new PublicKey("MagicContext1111111111111111111111111111111");
+const EPHEMERAL_RPC = process.env.EPHEMERAL_PROVIDER_ENDPOINT ?? "http://localhost:7799";
+// ChessMatch::flags bits (programs/speed-chess/src/state/chess_match.rs)
+const FLAG_BLACK_TO_MOVE = 1 << 0;
+const FLAG_DELEGATED = 1 << 6;
+
+describe("Ephemeral rollup delegation", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SpeedChess as Program<SpeedChess>;
+
+  const whitePlayer = Keypair.generate();
+  const blackPlayer = Keypair.generate();
+  const matchId = `er-${Date.now()}`;
+  const betAmount = new BN(10_000_000);
+
+  const pda = (...seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+  const delegationPda = (prefix: string, account: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from(prefix), account.toBuffer()], DELEGATION_PROGRAM_ID)[0];
+
+  const chessMatchPda = pda(Buffer.from("chess_match"), Buffer.from(matchId));
+  const moveHistoryPda = pda(Buffer.from("move_history"), Buffer.from(matchId));
+  const escrowPda = pda(Buffer.from("match_escrow"), Buffer.from(matchId));
+  const lobbyPagePda = pda(Buffer.from("lobby"), Buffer.alloc(4));
+
+  // Polls `connection` until `account` is owned by `owner` (delegation and undelegation land asynchronously).
+  const waitForOwner = async (connection: Connection, account: PublicKey, owner: PublicKey) => {
+    for (let attempt = 0; attempt < 60; attempt++) {
+      const info = await connection.getAccountInfo(account, "confirmed");
+      if (info?.owner.equals(owner)) return;
+      await new Promise((resolve) => setTimeout(resolve, 500));
+    }
+    throw new Error(`${account.toBase58()} never became owned by ${owner.toBase58()}`);
+  };
+
+  beforeAll(async () => {
+    const sendMintKp = await getKeypairFromFile("tests/test-keys/SENDYLjLBaTgjyfXtPP2aHUt91WhNzX7iUfpThyApht.json");
+    for (const player of [whitePlayer, blackPlayer]) {
+      const sig = await provider.connection.requestAirdrop(player.publicKey, 2 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig, "confirmed");
+    }
+    try { await getMint(provider.connection, sendMintKp.publicKey); }
+    catch (e) { await createMint(provider.connection, whitePlayer, whitePlayer.publicKey, null, 6, sendMintKp); }
+
+    const accounts: PublicKey[] = [];
+    for (const player of [whitePlayer, blackPlayer]) {
+      const ata = await createAccount(provider.connection, player, sendMintKp.publicKey, player.publicKey, Keypair.generate());
+      await mintTo(provider.connection, whitePlayer, sendMintKp.publicKey, ata, whitePlayer, betAmount.toNumber());
+      accounts.push(ata);
+    }
+
+    if (!(await provider.connection.getAccountInfo(lobbyPagePda))) {
+      await program.methods.createLobbyPage(0).accountsPartial({ lobbyPage: lobbyPagePda, previousPage: null, payer: whitePlayer.publicKey })
+        .signers([whitePlayer]).rpc({ commitment: "confirmed" });
+    }
+    await program.methods
+      .initializeMatch({
+        matchId,
+        betAmount,
+        moveTimeoutDuration: new BN(60),
+        platformFeeBasisPoints: 200,
+        joinDeadline: null,
+        takebacksEnabled: false,
+        variant: { standard: {} },
+        handicap: { none: {} },
+        rated: false,
+        startPositionCommitment: null,
+      })
+      .accountsPartial({
+        chessMatch: chessMatchPda,
+        moveHistory: moveHistoryPda,
+        playerSigner: whitePlayer.publicKey,
+        bettingTokenMintAccount: sendMintKp.publicKey,
+        playerTokenAccount: accounts[0],
+        matchEscrowTokenAccount: escrowPda,
+        lobbyPage: lobbyPagePda,
+        creatorProfile: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([whitePlayer])
+      .rpc({ commitment: "confirmed" });
+    await program.methods
+      .joinMatch(betAmount)
+      .accountsPartial({
+        chessMatch: chessMatchPda,
+        playerTwoSigner: blackPlayer.publicKey,
+        playerTokenAccount: accounts[1],
+        matchEscrowTokenAccount: escrowPda,
+        lobbyPage: lobbyPagePda,
+        recentSlotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([blackPlayer])
+      .rpc({ commitment: "confirmed" });
+  }, 100000);
+
+  it("plays a move in the rollup and commits it back before settlement", async () => {
+    // 1. Delegate on the base layer
+    await program.methods
+      .delegateMatch(matchId, null)
+      .accountsPartial({
+        chessMatch: chessMatchPda,
+        moveHistory: moveHistoryPda,
+        chessMatchBuffer: pda(Buffer.from("buffer"), chessMatchPda.toBuffer()),
+        moveHistoryBuffer: pda(Buffer.from("buffer"), moveHistoryPda.toBuffer()),
+        chessMatchDelegationRecord: delegationPda("delegation", chessMatchPda),
+        chessMatchDelegationMetadata: delegationPda("delegation-metadata", chessMatchPda),
+        moveHistoryDelegationRecord: delegationPda("delegation", moveHistoryPda),
+        moveHistoryDelegationMetadata: delegationPda("delegation-metadata", moveHistoryPda),
+        playerSigner: whitePlayer.publicKey,
+        ownerProgram: program.programId,
+        delegationProgram: DELEGATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([whitePlayer])
+      .rpc({ commitment: "confirmed" });
+    const baseInfo = await provider.connection.getAccountInfo(chessMatchPda, "confirmed");
+    assert.ok(baseInfo?.owner.equals(DELEGATION_PROGRAM_ID), "match should be owned by the delegation program");
+
+    // 2. Play 1. e4 in the rollup
+    const ephemeralConnection = new Connection(EPHEMERAL_RPC, "confirmed");
+    const ephemeralProgram = new Program<SpeedChess>(program.idl, new anchor.AnchorProvider(ephemeralConnection, provider.wallet, {}));
+    await waitForOwner(ephemeralConnection, chessMatchPda, program.programId);
+    await ephemeralProgram.methods
+      .makeMove({ fromRow: 1, fromCol: 4, toRow: 3, toCol: 4, promotion: null, dropPiece: null })
+      .accountsPartial({ chessMatch: chessMatchPda, moveHistory: moveHistoryPda, player: whitePlayer.publicKey, sessionToken: null, systemProgram: SystemProgram.programId })
+      .signers([whitePlayer])
+      .rpc({ skipPreflight: true });
+    const rollupState = await ephemeralProgram.account.chessMatch.fetch(chessMatchPda);
+    assert.ok(rollupState.flags & FLAG_BLACK_TO_MOVE, "black should be on move");
+
+    // 3. Undelegate: the committed state lands back on the base layer, owned by this program again
+    await ephemeralProgram.methods
+      .undelegateMatch()
+      .accountsPartial({
+        chessMatch: chessMatchPda,
+        moveHistory: moveHistoryPda,
+        payer: whitePlayer.publicKey,
+        magicContext: MAGIC_CONTEXT_ID,
+        magicProgram: MAGIC_PROGRAM_ID,
+      })
+      .signers([whitePlayer])
+      .rpc({ skipPreflight: true });
+    await waitForOwner(provider.connection, chessMatchPda, program.programId);
+
+    const baseState = await program.account.chessMatch.fetch(chessMatchPda);
+    assert.strictEqual(baseState.flags & FLAG_DELEGATED, 0);
+    assert.ok(baseState.flags & FLAG_BLACK_TO_MOVE, "black should be on move");
+    const history = await program.account.moveHistory.fetch(moveHistoryPda);
+    assert.strictEqual(history.moves.length, 1);
+  }, 120000);
+});